@@ -0,0 +1,20 @@
+-- Reservations of a bonus chore slot by a user. A claim counts toward the
+-- chore's max_claims while it is 'active' (reserved, not yet expired) or
+-- 'fulfilled' (a completion was recorded against it).
+CREATE TABLE bonus_chore_claims (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    chore_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'fulfilled', 'released', 'expired')),
+    claimed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at DATETIME NOT NULL,
+    completion_id INTEGER,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (chore_id) REFERENCES chores(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (completion_id) REFERENCES chore_completions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_bonus_chore_claims_chore_status ON bonus_chore_claims(chore_id, status);
+CREATE INDEX idx_bonus_chore_claims_user ON bonus_chore_claims(user_id);
@@ -0,0 +1,2 @@
+DROP TABLE chore_checklist_items;
+DROP TABLE chore_templates;
@@ -0,0 +1,28 @@
+-- Reusable chore definitions that can be instantiated (and shared as JSON packs)
+CREATE TABLE chore_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    payment_type TEXT NOT NULL CHECK (payment_type IN ('daily', 'weekly')),
+    amount_cents INTEGER NOT NULL,
+    -- Same bitmask as chores.required_days
+    required_days INTEGER NOT NULL DEFAULT 0,
+    -- JSON array of checklist step strings
+    checklist TEXT NOT NULL DEFAULT '[]',
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Ordered steps shown with a chore
+CREATE TABLE chore_checklist_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    chore_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    item_text TEXT NOT NULL,
+    FOREIGN KEY (chore_id) REFERENCES chores(id) ON DELETE CASCADE,
+    UNIQUE(chore_id, position)
+);
+
+CREATE INDEX idx_chore_templates_uuid ON chore_templates(uuid);
+CREATE INDEX idx_chore_templates_name ON chore_templates(name);
@@ -0,0 +1,20 @@
+-- Vacation and sick-day pauses. While a pause covers a date the user's chores
+-- are not due: the date is skipped by streaks and left out of perfect-week and
+-- completion-rate calculations. A NULL chore_id pauses every chore for the user.
+CREATE TABLE pause_periods (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    chore_id INTEGER,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    reason TEXT,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    CHECK (end_date >= start_date),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (chore_id) REFERENCES chores(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_pause_periods_user_dates ON pause_periods(user_id, start_date, end_date);
@@ -0,0 +1 @@
+DROP TABLE household_calendar_days;
@@ -0,0 +1,16 @@
+-- Household-wide calendar overrides. A 'skip' day has no chores due at all; an
+-- 'alternate' day follows the schedule of another weekday (alternate_weekday,
+-- Monday = 0 … Sunday = 6), e.g. a school holiday run like a Saturday.
+CREATE TABLE household_calendar_days (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    date DATE NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'skip' CHECK (kind IN ('skip', 'alternate')),
+    alternate_weekday INTEGER CHECK (alternate_weekday BETWEEN 0 AND 6),
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'ics')),
+    ics_uid TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((kind = 'alternate') = (alternate_weekday IS NOT NULL))
+);
@@ -0,0 +1,5 @@
+DROP TABLE scheduled_job_runs;
+
+ALTER TABLE admins DROP COLUMN notify_weekly_summary;
+ALTER TABLE admins DROP COLUMN notify_bonus_claimed;
+ALTER TABLE admins DROP COLUMN notify_pending_approval;
@@ -0,0 +1,10 @@
+-- Per-admin opt-in for email notifications. Everything starts switched off.
+ALTER TABLE admins ADD COLUMN notify_pending_approval BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE admins ADD COLUMN notify_bonus_claimed BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE admins ADD COLUMN notify_weekly_summary BOOLEAN NOT NULL DEFAULT 0;
+
+-- Last time each background job ran, so restarts don't repeat (or skip) a run.
+CREATE TABLE scheduled_job_runs (
+    name TEXT PRIMARY KEY NOT NULL,
+    last_run_at DATETIME NOT NULL
+);
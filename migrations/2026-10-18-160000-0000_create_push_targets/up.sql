@@ -0,0 +1,22 @@
+-- Self-hosted push (ntfy or Gotify) destinations. Each target belongs to exactly
+-- one recipient: an admin (gets submission alerts) or a kid (gets reminders for
+-- chores due today). ntfy needs a topic and optionally an access token; Gotify
+-- needs an application token.
+CREATE TABLE push_targets (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    provider TEXT NOT NULL CHECK (provider IN ('ntfy', 'gotify')),
+    server_url TEXT NOT NULL,
+    topic TEXT,
+    token TEXT,
+    admin_id INTEGER,
+    user_id INTEGER,
+    active BOOLEAN NOT NULL DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((admin_id IS NULL) <> (user_id IS NULL)),
+    CHECK (provider <> 'ntfy' OR topic IS NOT NULL),
+    CHECK (provider <> 'gotify' OR token IS NOT NULL),
+    FOREIGN KEY (admin_id) REFERENCES admins(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+DROP TABLE web_push_subscriptions;
+DROP TABLE vapid_keys;
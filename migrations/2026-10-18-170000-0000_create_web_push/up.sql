@@ -0,0 +1,27 @@
+-- VAPID (RFC 8292) key pair identifying this server to browser push services.
+-- Generated on first use; a single row. Keys are base64url without padding: the
+-- private key is the raw P-256 scalar, the public key the uncompressed point.
+CREATE TABLE vapid_keys (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    private_key TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Browser push subscriptions of the installed PWA, each owned by one admin or kid.
+-- p256dh and auth are the subscription keys used for RFC 8291 encryption.
+CREATE TABLE web_push_subscriptions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    endpoint TEXT NOT NULL UNIQUE,
+    p256dh TEXT NOT NULL,
+    auth TEXT NOT NULL,
+    admin_id INTEGER,
+    user_id INTEGER,
+    user_agent TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    last_success_at DATETIME,
+    CHECK ((admin_id IS NULL) <> (user_id IS NULL)),
+    FOREIGN KEY (admin_id) REFERENCES admins(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
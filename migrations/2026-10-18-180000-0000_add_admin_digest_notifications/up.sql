@@ -0,0 +1,2 @@
+-- Per-admin opt-in for the scheduled digest email. Starts switched off like the others.
+ALTER TABLE admins ADD COLUMN notify_digest BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_chore_completions_user_date;
@@ -0,0 +1,2 @@
+-- Earnings analytics group a user's approved completions by date range.
+CREATE INDEX idx_chore_completions_user_date ON chore_completions(user_id, completed_date);
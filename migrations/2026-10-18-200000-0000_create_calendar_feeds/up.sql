@@ -0,0 +1,9 @@
+-- Secret tokens for each kid's iCalendar feed. The token is the only credential
+-- calendar clients can send, so rotating it revokes every existing subscription.
+CREATE TABLE calendar_feeds (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL UNIQUE,
+    token TEXT NOT NULL UNIQUE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
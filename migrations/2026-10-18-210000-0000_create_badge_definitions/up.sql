@@ -0,0 +1,35 @@
+-- Badges as data. `badge_key` is what user_badges.badge_type stores, so it never
+-- changes once badges have been earned. A badge is earned once the rule's measure
+-- reaches `threshold`:
+--   completion_count  approved completions (of `chore_id` only, when set)
+--   earnings_total    approved earnings in cents
+--   streak_days       longest run of days with every scheduled chore approved
+--   perfect_weeks     weeks with every required chore done
+CREATE TABLE badge_definitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    badge_key TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    rule_type TEXT NOT NULL,
+    threshold INTEGER NOT NULL CHECK (threshold > 0),
+    chore_id INTEGER,
+    active BOOLEAN NOT NULL DEFAULT 1,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (chore_id) REFERENCES chores(id) ON DELETE CASCADE
+);
+
+INSERT INTO badge_definitions (uuid, badge_key, name, description, rule_type, threshold, position)
+VALUES
+    ('6c1d2a4e-0f3b-4d8e-9a51-3b7e2c9d0a11', 'first_chore', 'First Chore',
+     'Had a first chore approved', 'completion_count', 1, 1),
+    ('a83f5b27-4c6d-4e19-8b02-5d9f1e7c3b22', 'ten_dollars_earned', '$10 Earned',
+     'Earned $10 in total', 'earnings_total', 1000, 2),
+    ('d4e7c9a1-2b5f-4a3c-9e68-7f1b3d5a2c33', 'fifty_dollars_earned', '$50 Earned',
+     'Earned $50 in total', 'earnings_total', 5000, 3),
+    ('1f9b3e6d-8a2c-4f57-b4d1-9c6e2a8f4d44', 'perfect_week', 'Perfect Week',
+     'Did every chore of a week', 'perfect_weeks', 1, 4),
+    ('5b2a8d4f-7e1c-4b96-a3f5-2e8d6c1b9e55', 'five_day_streak', '5-Day Streak',
+     'Did every scheduled chore five days in a row', 'streak_days', 5, 5);
@@ -0,0 +1,2 @@
+DROP TABLE streak_days;
+DROP TABLE user_streaks;
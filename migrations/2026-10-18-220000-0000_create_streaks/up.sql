@@ -0,0 +1,27 @@
+-- Each kid's streak of perfect days (something due and every due chore approved),
+-- evaluated day by day up to `evaluated_through`. Days with nothing due are skipped.
+CREATE TABLE user_streaks (
+    user_id INTEGER PRIMARY KEY NOT NULL,
+    current_streak INTEGER NOT NULL DEFAULT 0,
+    longest_streak INTEGER NOT NULL DEFAULT 0,
+    last_qualifying_date DATE,
+    freezes_available INTEGER NOT NULL DEFAULT 0,
+    evaluated_through DATE,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Outcome of every evaluated day that had chores due, with the state after it, so
+-- a late approval or a new pause can rewind to that day and replay from there.
+-- outcome: 'qualified', 'frozen' (a missed day covered by a freeze) or 'missed'.
+CREATE TABLE streak_days (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    date DATE NOT NULL,
+    outcome TEXT NOT NULL,
+    streak INTEGER NOT NULL,
+    longest INTEGER NOT NULL,
+    freezes INTEGER NOT NULL,
+    UNIQUE (user_id, date),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+DROP TABLE level_thresholds;
+ALTER TABLE chore_completions DROP COLUMN points;
+ALTER TABLE chores DROP COLUMN points;
@@ -0,0 +1,26 @@
+-- Points are a second currency next to money: a chore can award points, cents or both.
+-- Completions snapshot their points the same way they snapshot amount_cents, and
+-- approved points add up to a kid's XP.
+ALTER TABLE chores ADD COLUMN points INTEGER NOT NULL DEFAULT 0 CHECK (points >= 0);
+ALTER TABLE chore_completions ADD COLUMN points INTEGER NOT NULL DEFAULT 0;
+
+-- XP needed to reach each level above 1. Rows without a user are the household
+-- defaults; a user with rows of their own uses only those.
+CREATE TABLE level_thresholds (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER,
+    level INTEGER NOT NULL CHECK (level > 1),
+    xp INTEGER NOT NULL CHECK (xp > 0),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_level_thresholds_user_level
+    ON level_thresholds (COALESCE(user_id, 0), level);
+
+INSERT INTO level_thresholds (user_id, level, xp)
+VALUES
+    (NULL, 2, 100),
+    (NULL, 3, 250),
+    (NULL, 4, 500),
+    (NULL, 5, 1000),
+    (NULL, 6, 2000);
@@ -0,0 +1,38 @@
+-- Rewards kids can redeem, priced in points or in cents of their allowance.
+CREATE TABLE rewards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    currency TEXT NOT NULL CHECK (currency IN ('points', 'cents')),
+    cost INTEGER NOT NULL CHECK (cost > 0),
+    active BOOLEAN NOT NULL DEFAULT 1,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- A kid's request for a reward. Name, currency and cost are copied from the reward so
+-- the history stays right when the catalog changes.
+-- status: 'pending', then 'approved' or 'rejected' by an admin. Approved redemptions
+-- come off the balance; cents ones are settled with the next payout (`paid_out_at`).
+CREATE TABLE reward_redemptions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    reward_id INTEGER,
+    user_id INTEGER NOT NULL,
+    reward_name TEXT NOT NULL,
+    currency TEXT NOT NULL CHECK (currency IN ('points', 'cents')),
+    cost INTEGER NOT NULL CHECK (cost > 0),
+    status TEXT NOT NULL DEFAULT 'pending',
+    requested_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    decided_at DATETIME,
+    decided_by_admin_id INTEGER,
+    decision_note TEXT,
+    paid_out_at DATETIME,
+    FOREIGN KEY (reward_id) REFERENCES rewards(id) ON DELETE SET NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (decided_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_reward_redemptions_user_status ON reward_redemptions (user_id, status);
@@ -0,0 +1,4 @@
+DROP TABLE savings_goal_allocations;
+DROP TABLE savings_goals;
+DELETE FROM user_images WHERE kind <> 'profile';
+ALTER TABLE user_images DROP COLUMN kind;
@@ -0,0 +1,40 @@
+-- Savings goal pictures live in user_images next to profile pictures; `kind` keeps
+-- them apart so a new profile picture does not replace them.
+ALTER TABLE user_images ADD COLUMN kind TEXT NOT NULL DEFAULT 'profile';
+
+-- Something a kid is saving for. Progress is either their current balance or the sum
+-- of amounts explicitly set aside for the goal (progress_source 'balance' or
+-- 'allocations'). reached_at is set the first time progress meets the target.
+CREATE TABLE savings_goals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    target_cents INTEGER NOT NULL CHECK (target_cents > 0),
+    target_date DATE,
+    image_id INTEGER,
+    progress_source TEXT NOT NULL DEFAULT 'balance'
+        CHECK (progress_source IN ('balance', 'allocations')),
+    reached_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (image_id) REFERENCES user_images(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_savings_goals_user_id ON savings_goals (user_id);
+
+-- Money set aside for (or taken back from) a goal
+CREATE TABLE savings_goal_allocations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    goal_id INTEGER NOT NULL,
+    amount_cents INTEGER NOT NULL CHECK (amount_cents <> 0),
+    note TEXT,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (goal_id) REFERENCES savings_goals(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_savings_goal_allocations_goal_id ON savings_goal_allocations (goal_id);
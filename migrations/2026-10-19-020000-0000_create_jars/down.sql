@@ -0,0 +1,2 @@
+DROP TABLE jar_entries;
+DROP TABLE jar_splits;
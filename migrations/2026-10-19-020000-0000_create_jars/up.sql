@@ -0,0 +1,39 @@
+-- How a kid's earnings are split between their spend, save and give jars.
+-- apply_on: 'approval' splits each completion when it is approved, 'payout' splits what
+-- is paid out. Kids without a row keep everything in the spend jar.
+CREATE TABLE jar_splits (
+    user_id INTEGER PRIMARY KEY NOT NULL,
+    spend_percent INTEGER NOT NULL CHECK (spend_percent BETWEEN 0 AND 100),
+    save_percent INTEGER NOT NULL CHECK (save_percent BETWEEN 0 AND 100),
+    give_percent INTEGER NOT NULL CHECK (give_percent BETWEEN 0 AND 100),
+    apply_on TEXT NOT NULL DEFAULT 'approval' CHECK (apply_on IN ('approval', 'payout')),
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    CHECK (spend_percent + save_percent + give_percent = 100),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Ledger of money moving into, out of and between jars; a jar's balance is the sum of
+-- its entries.
+-- kind: 'completion' (split of an approved completion, removed with it), 'payout' (split
+-- of a payout), 'reward' (cents rewards settled by a payout, taken from spend) or
+-- 'transfer' (moved by an admin). Entries that are part of a payout share its
+-- `paid_out_at`.
+CREATE TABLE jar_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    jar TEXT NOT NULL CHECK (jar IN ('spend', 'save', 'give')),
+    amount_cents INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('completion', 'payout', 'reward', 'transfer')),
+    completion_id INTEGER,
+    paid_out_at DATETIME,
+    note TEXT,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (completion_id) REFERENCES chore_completions(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_jar_entries_user_jar ON jar_entries (user_id, jar);
+CREATE INDEX idx_jar_entries_completion ON jar_entries (completion_id);
@@ -0,0 +1,26 @@
+DROP TABLE interest_credits;
+DROP TABLE interest_rates;
+
+CREATE TABLE jar_entries_old (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    jar TEXT NOT NULL CHECK (jar IN ('spend', 'save', 'give')),
+    amount_cents INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('completion', 'payout', 'reward', 'transfer')),
+    completion_id INTEGER,
+    paid_out_at DATETIME,
+    note TEXT,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (completion_id) REFERENCES chore_completions(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+INSERT INTO jar_entries_old SELECT * FROM jar_entries WHERE kind <> 'interest';
+DROP TABLE jar_entries;
+ALTER TABLE jar_entries_old RENAME TO jar_entries;
+
+CREATE INDEX idx_jar_entries_user_jar ON jar_entries (user_id, jar);
+CREATE INDEX idx_jar_entries_completion ON jar_entries (completion_id);
@@ -0,0 +1,63 @@
+-- Interest credited to the save jar is booked as its own kind of jar entry. SQLite
+-- cannot change a CHECK constraint, so jar_entries is rebuilt.
+CREATE TABLE jar_entries_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    jar TEXT NOT NULL CHECK (jar IN ('spend', 'save', 'give')),
+    amount_cents INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('completion', 'payout', 'reward', 'transfer', 'interest')),
+    completion_id INTEGER,
+    paid_out_at DATETIME,
+    note TEXT,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (completion_id) REFERENCES chore_completions(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+INSERT INTO jar_entries_new SELECT * FROM jar_entries;
+DROP TABLE jar_entries;
+ALTER TABLE jar_entries_new RENAME TO jar_entries;
+
+CREATE INDEX idx_jar_entries_user_jar ON jar_entries (user_id, jar);
+CREATE INDEX idx_jar_entries_completion ON jar_entries (completion_id);
+
+-- Interest on the save jar. The row without a user is the household rate; a user's own
+-- row replaces it. rate_bps is per period in basis points (250 = 2.5%).
+-- period: 'weekly' (Monday to Sunday) or 'monthly'.
+-- rounding: 'quarter' rounds to the nearest 25 cents like weekly chore shares, 'exact'
+-- to the nearest cent.
+CREATE TABLE interest_rates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER,
+    rate_bps INTEGER NOT NULL CHECK (rate_bps BETWEEN 0 AND 10000),
+    period TEXT NOT NULL CHECK (period IN ('weekly', 'monthly')),
+    rounding TEXT NOT NULL CHECK (rounding IN ('quarter', 'exact')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_interest_rates_user ON interest_rates (COALESCE(user_id, 0));
+
+-- One row per user and period interest was worked out for, with the inputs so every
+-- credit can be checked by hand. jar_entry_id is the save jar entry; NULL when the
+-- interest rounded to nothing.
+CREATE TABLE interest_credits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    balance_cents INTEGER NOT NULL,
+    rate_bps INTEGER NOT NULL,
+    rounding TEXT NOT NULL,
+    amount_cents INTEGER NOT NULL,
+    jar_entry_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, period_start),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (jar_entry_id) REFERENCES jar_entries(id) ON DELETE SET NULL
+);
@@ -0,0 +1,34 @@
+-- Cooperative goals: the participants together work towards a target between two
+-- dates, e.g. 50 chores this month for a pizza night.
+-- metric: 'completions' counts approved completions, 'earned_cents' and 'points' total
+-- what they earned. Only completions of `chore_id` count when it is set.
+-- completed_at is set the first time progress meets the target.
+CREATE TABLE challenges (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    description TEXT,
+    prize TEXT,
+    metric TEXT NOT NULL CHECK (metric IN ('completions', 'earned_cents', 'points')),
+    target INTEGER NOT NULL CHECK (target > 0),
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    chore_id INTEGER,
+    completed_at DATETIME,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    CHECK (end_date >= start_date),
+    FOREIGN KEY (chore_id) REFERENCES chores(id) ON DELETE SET NULL,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+CREATE TABLE challenge_participants (
+    challenge_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    PRIMARY KEY (challenge_id, user_id),
+    FOREIGN KEY (challenge_id) REFERENCES challenges(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_challenge_participants_user ON challenge_participants (user_id);
@@ -0,0 +1,4 @@
+UPDATE badge_definitions
+SET description = 'Had chores approved five days in a row'
+WHERE badge_key = 'five_day_streak'
+  AND description = 'Did every scheduled chore five days in a row';
@@ -0,0 +1,6 @@
+-- Streak badges follow StreakSvc's perfect days now, so bonus chores alone no longer
+-- count. Descriptions an admin already changed are left alone.
+UPDATE badge_definitions
+SET description = 'Did every scheduled chore five days in a row'
+WHERE badge_key = 'five_day_streak'
+  AND description = 'Had chores approved five days in a row';
@@ -0,0 +1 @@
+ALTER TABLE chore_templates DROP COLUMN points;
@@ -0,0 +1,2 @@
+-- Templates carry points like chores do, so points-only chores can be templated.
+ALTER TABLE chore_templates ADD COLUMN points INTEGER NOT NULL DEFAULT 0 CHECK (points >= 0);
@@ -0,0 +1,34 @@
+CREATE TABLE interest_credit_entries AS
+    SELECT id, jar_entry_id FROM interest_credits WHERE jar_entry_id IS NOT NULL;
+
+CREATE TABLE jar_entries_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    jar TEXT NOT NULL CHECK (jar IN ('spend', 'save', 'give')),
+    amount_cents INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('completion', 'payout', 'reward', 'transfer', 'interest')),
+    completion_id INTEGER,
+    paid_out_at DATETIME,
+    note TEXT,
+    created_by_admin_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (completion_id) REFERENCES chore_completions(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by_admin_id) REFERENCES admins(id) ON DELETE SET NULL
+);
+
+INSERT INTO jar_entries_new SELECT * FROM jar_entries;
+DROP TABLE jar_entries;
+ALTER TABLE jar_entries_new RENAME TO jar_entries;
+
+CREATE INDEX idx_jar_entries_user_jar ON jar_entries (user_id, jar);
+CREATE INDEX idx_jar_entries_completion ON jar_entries (completion_id);
+
+-- Dropping the old table cleared interest_credits.jar_entry_id; put it back
+UPDATE interest_credits
+SET jar_entry_id = (
+    SELECT e.jar_entry_id FROM interest_credit_entries e WHERE e.id = interest_credits.id
+)
+WHERE id IN (SELECT id FROM interest_credit_entries);
+DROP TABLE interest_credit_entries;
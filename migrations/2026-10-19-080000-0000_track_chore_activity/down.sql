@@ -0,0 +1,2 @@
+ALTER TABLE chores DROP COLUMN deactivated_at;
+ALTER TABLE chores DROP COLUMN activated_at;
@@ -0,0 +1,7 @@
+-- When a chore was last switched on or off, so completion stats only expect it while it
+-- was active. Chores that are off already are taken to have been switched off at their
+-- last update.
+ALTER TABLE chores ADD COLUMN activated_at DATETIME;
+ALTER TABLE chores ADD COLUMN deactivated_at DATETIME;
+
+UPDATE chores SET deactivated_at = COALESCE(updated_at, created_at) WHERE active = 0;
@@ -0,0 +1,21 @@
+-- What each payout took off approved cents redemptions. A payout only settles what it can
+-- cover, oldest redemption first, and carries the rest forward to the next one; a
+-- redemption's `paid_out_at` is set once it is settled in full.
+CREATE TABLE reward_settlements (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    redemption_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    amount_cents INTEGER NOT NULL CHECK (amount_cents > 0),
+    paid_out_at DATETIME NOT NULL,
+    FOREIGN KEY (redemption_id) REFERENCES reward_redemptions(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_reward_settlements_redemption ON reward_settlements (redemption_id);
+CREATE INDEX idx_reward_settlements_user_paid ON reward_settlements (user_id, paid_out_at);
+
+-- Redemptions settled so far were always settled in full
+INSERT INTO reward_settlements (redemption_id, user_id, amount_cents, paid_out_at)
+SELECT id, user_id, cost, paid_out_at
+FROM reward_redemptions
+WHERE status = 'approved' AND currency = 'cents' AND paid_out_at IS NOT NULL;
@@ -0,0 +1,40 @@
+use crate::api::AppError;
+use crate::context::GraphQLContext;
+use crate::svc::CalendarFeedSvc;
+
+use anyhow::anyhow;
+use axum::extract::Path;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+
+/// Builds the calendar router. `GET /calendar/{token}.ics` serves a kid's chore schedule
+/// to calendar apps; the token in the path is the only credential.
+pub fn calendar_routes() -> Router {
+    Router::new().route("/{token}", get(calendar_feed))
+}
+
+async fn calendar_feed(
+    Extension(context): Extension<GraphQLContext>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token).to_owned();
+    let ics = tokio::task::spawn_blocking(move || {
+        // Same answer for unknown and revoked tokens
+        let user_id = CalendarFeedSvc::user_for_token(&context, &token)?
+            .ok_or_else(|| anyhow!("Unauthorized"))?;
+        CalendarFeedSvc::render(&context, user_id)
+    })
+    .await
+    .map_err(|e| AppError(anyhow!(e)))?
+    .map_err(AppError)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "private, max-age=900"),
+        ],
+        ics,
+    ))
+}
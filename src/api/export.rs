@@ -0,0 +1,93 @@
+use crate::api::{AppError, require_admin_cookie};
+use crate::context::GraphQLContext;
+use crate::svc::chore_completion::ChoreCompletionFilter;
+use crate::svc::export::{ExportFormat, ExportSvc};
+
+use anyhow::anyhow;
+use axum::body::{Body, Bytes};
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use axum_extra::extract::CookieJar;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// `ChoreCompletionFilter` as query parameters, plus the output format (`csv` by default).
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportQuery {
+    format: Option<String>,
+    user_id: Option<i32>,
+    chore_id: Option<i32>,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+    approved_only: Option<bool>,
+    unpaid_only: Option<bool>,
+    paid_only: Option<bool>,
+}
+
+impl From<&ExportQuery> for ChoreCompletionFilter {
+    fn from(query: &ExportQuery) -> Self {
+        Self {
+            user_id: query.user_id,
+            chore_id: query.chore_id,
+            date_from: query.date_from,
+            date_to: query.date_to,
+            approved_only: query.approved_only,
+            unpaid_only: query.unpaid_only,
+            paid_only: query.paid_only,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+/// Builds the export router. `GET /export?format=csv|json&userId=..&dateFrom=..` (also
+/// served at `/export/completions`) streams every matching completion.
+pub fn export_routes() -> Router {
+    Router::new()
+        .route("/", get(export_completions))
+        .route("/completions", get(export_completions))
+}
+
+async fn export_completions(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    require_admin_cookie(&context, &jar)?;
+    let format = match query.format.as_deref() {
+        None => ExportFormat::Csv,
+        Some(name) => ExportFormat::from_name(name)
+            .ok_or_else(|| AppError(anyhow!("Unknown export format '{name}'")))?,
+    };
+    let filter = ChoreCompletionFilter::from(&query);
+
+    // Pages are read on the blocking pool and streamed out as they are formatted
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(4);
+    tokio::task::spawn_blocking(move || {
+        let result = ExportSvc::write(&context, &filter, format, |chunk| {
+            tx.blocking_send(Ok(Bytes::from(chunk))).is_ok()
+        });
+        if let Err(e) = result {
+            tracing::warn!("Export failed: {:?}", e);
+            // Abort the response so the client doesn't take a truncated file as complete
+            let _ = tx.blocking_send(Err(std::io::Error::other(e.to_string())));
+        }
+    });
+
+    let filename = format!(
+        "attachment; filename=\"completions.{}\"",
+        format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_owned()),
+            (header::CONTENT_DISPOSITION, filename),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    ))
+}
@@ -1,7 +1,7 @@
 #![allow(clippy::collapsible_if)]
-use crate::api::AppError;
+use crate::api::{AppError, require_admin_cookie};
 use crate::context::GraphQLContext;
-use crate::svc::{AdminSvc, UserImageSvc, UserSvc};
+use crate::svc::{SavingsGoalSvc, UserImageSvc, UserSvc};
 
 use anyhow::{Context, anyhow};
 use axum::extract::{Multipart, Path};
@@ -16,25 +16,16 @@ use uuid::Uuid;
 const MAX_IMAGE_SIZE: usize = 5 * 1024 * 1024;
 const IMAGE_CACHE_CONTROL: &str = "public, max-age=86400";
 
-fn require_admin_cookie(context: &GraphQLContext, jar: &CookieJar) -> Result<i32, AppError> {
-    let token = jar
-        .get("admin_session")
-        .ok_or_else(|| AppError(anyhow::anyhow!("Unauthorized")))?
-        .value()
-        .to_owned();
-    AdminSvc::get_session(context, &token)
-        .map_err(AppError)?
-        .and_then(|a| a.id)
-        .ok_or_else(|| AppError(anyhow::anyhow!("Unauthorized")))
-}
-
-/// Builds the image router for uploading, fetching, and deleting user profile images.
+/// Builds the image router for uploading, fetching, and deleting user profile images and
+/// savings goal pictures.
 pub fn image_routes() -> Router {
     Router::new()
         .route("/upload/{user_uuid}", post(upload_user_image))
         .route("/user/{user_id}", get(get_user_image))
         .route("/{image_uuid}", get(get_image_by_uuid))
         .route("/user/{user_id}", delete(delete_image_by_user_id))
+        .route("/goal/{goal_uuid}", post(upload_goal_image))
+        .route("/goal/{goal_uuid}", delete(delete_goal_image))
 }
 
 async fn delete_image_by_user_id(
@@ -111,6 +102,56 @@ async fn upload_user_image(
     Err(AppError(anyhow!("No image field found in the upload")))
 }
 
+// Savings goal picture upload handler; replaces the goal's previous picture
+async fn upload_goal_image(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    Path(goal_uuid): Path<String>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    require_admin_cookie(&context, &jar)?;
+    let goal = SavingsGoalSvc::get(&context, &goal_uuid).context("fetching savings goal")?;
+
+    while let Some(field) = multipart.next_field().await.context("reading multipart")? {
+        if field.name() != Some("image") {
+            continue;
+        }
+        let content_type = field.content_type().unwrap_or("image/jpeg").to_owned();
+        if !content_type.starts_with("image/") {
+            return Err(AppError(anyhow!("Only image files are allowed")));
+        }
+
+        let data = field.bytes().await.context("could not read image data")?;
+        if data.len() > MAX_IMAGE_SIZE {
+            return Err(AppError(anyhow!("Image too large (max 5MB)")));
+        }
+
+        let file_size = i32::try_from(data.len()).unwrap_or(0);
+        let image_input = crate::models::UserImageInput {
+            user_id: goal.user_id,
+            image_data: data.to_vec(),
+            content_type,
+            file_size,
+        };
+        SavingsGoalSvc::set_image(&context, &goal.uuid, image_input).context("saving image")?;
+
+        return Ok((StatusCode::OK, "Image uploaded successfully"));
+    }
+
+    Err(AppError(anyhow!("No image field found in the upload")))
+}
+
+async fn delete_goal_image(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    Path(goal_uuid): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    require_admin_cookie(&context, &jar)?;
+    SavingsGoalSvc::remove_image(&context, &goal_uuid).context("failed to delete image")?;
+
+    Ok((StatusCode::OK, "Image deleted successfully"))
+}
+
 // Get user image handler
 async fn get_user_image(
     Extension(context): Extension<GraphQLContext>,
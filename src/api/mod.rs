@@ -3,13 +3,19 @@ use axum::http::HeaderMap;
 use axum::response::IntoResponse;
 use axum::response::Response;
 use axum::{Router, http::StatusCode, routing::get};
+use axum_extra::extract::CookieJar;
 use serde::Serialize;
 
 use crate::context::GraphQLContext;
+use crate::svc::AdminSvc;
 
 pub mod auth;
+pub mod calendar;
+pub mod export;
 pub mod graphql;
 pub mod images;
+pub mod push;
+pub mod statements;
 
 pub fn api_routes(_context: GraphQLContext) -> Router {
     Router::new().route("/test", get(test))
@@ -53,3 +59,20 @@ where
         Self(err.into())
     }
 }
+
+/// Id of the admin whose `admin_session` cookie came with the request, for REST endpoints
+/// that sit outside GraphQL.
+pub(crate) fn require_admin_cookie(
+    context: &GraphQLContext,
+    jar: &CookieJar,
+) -> Result<i32, AppError> {
+    let token = jar
+        .get("admin_session")
+        .ok_or_else(|| AppError(anyhow::anyhow!("Unauthorized")))?
+        .value()
+        .to_owned();
+    AdminSvc::get_session(context, &token)
+        .map_err(AppError)?
+        .and_then(|a| a.id)
+        .ok_or_else(|| AppError(anyhow::anyhow!("Unauthorized")))
+}
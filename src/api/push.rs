@@ -0,0 +1,106 @@
+use crate::api::{AppError, require_admin_cookie};
+use crate::context::GraphQLContext;
+use crate::models::WebPushSubscription;
+use crate::svc::{UserSvc, WebPushSvc};
+
+use anyhow::Context;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use axum_extra::extract::CookieJar;
+use serde::{Deserialize, Serialize};
+
+/// The browser's `PushSubscription.toJSON()`.
+#[derive(Debug, Deserialize)]
+struct BrowserSubscription {
+    endpoint: String,
+    keys: BrowserSubscriptionKeys,
+}
+
+#[derive(Debug, Deserialize)]
+struct BrowserSubscriptionKeys {
+    p256dh: String,
+    auth: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscribeRequest {
+    subscription: BrowserSubscription,
+    /// Registers the device for a kid instead of the signed-in admin.
+    user_uuid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnsubscribeRequest {
+    endpoint: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VapidPublicKey {
+    public_key: String,
+}
+
+/// Builds the Web Push router used by the PWA service worker registration.
+pub fn push_routes() -> Router {
+    Router::new()
+        .route("/vapid-public-key", get(vapid_public_key))
+        .route("/subscriptions", post(subscribe).delete(unsubscribe))
+}
+
+async fn vapid_public_key(
+    Extension(context): Extension<GraphQLContext>,
+) -> Result<impl IntoResponse, AppError> {
+    let public_key = WebPushSvc::vapid_public_key(&context)?;
+    Ok(Json(VapidPublicKey { public_key }))
+}
+
+async fn subscribe(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    headers: HeaderMap,
+    Json(request): Json<SubscribeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    // An endpoint that is subscribed again changes owner, so only admins may register one
+    let signed_in_admin_id = require_admin_cookie(&context, &jar)?;
+    let (admin_id, user_id) = match request.user_uuid {
+        Some(user_uuid) => {
+            let user = UserSvc::get(&context, &user_uuid).context("fetching user")?;
+            (None, user.id)
+        }
+        None => (Some(signed_in_admin_id), None),
+    };
+
+    let subscription = WebPushSvc::subscribe(
+        &context,
+        &WebPushSubscription {
+            id: None,
+            uuid: crate::uuid_or_generate(None),
+            endpoint: request.subscription.endpoint,
+            p256dh: request.subscription.keys.p256dh,
+            auth: request.subscription.keys.auth,
+            admin_id,
+            user_id,
+            user_agent: headers
+                .get(header::USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+            created_at: None,
+            last_success_at: None,
+        },
+    )?;
+
+    Ok((StatusCode::CREATED, subscription.uuid))
+}
+
+async fn unsubscribe(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    Json(request): Json<UnsubscribeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    require_admin_cookie(&context, &jar)?;
+    WebPushSvc::unsubscribe(&context, &request.endpoint)?;
+    Ok(StatusCode::NO_CONTENT)
+}
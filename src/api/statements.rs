@@ -0,0 +1,88 @@
+use crate::api::{AppError, require_admin_cookie};
+use crate::context::GraphQLContext;
+use crate::svc::{StatementSvc, UserSvc};
+
+use anyhow::anyhow;
+use axum::extract::Path;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use axum_extra::extract::CookieJar;
+
+/// Builds the statement router. `GET /statements/{year}/{month}` downloads every kid's
+/// statement for the month as one PDF; `/statements/{year}/{month}/{user_uuid}` just one.
+pub fn statement_routes() -> Router {
+    Router::new()
+        .route("/{year}/{month}", get(all_statements))
+        .route("/{year}/{month}/{user_uuid}", get(user_statement))
+}
+
+async fn all_statements(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    Path((year, month)): Path<(i32, u32)>,
+) -> Result<impl IntoResponse, AppError> {
+    require_admin_cookie(&context, &jar)?;
+    let bytes = tokio::task::spawn_blocking(move || {
+        StatementSvc::build_all(&context, year, month).map(|s| StatementSvc::pdf(&s))
+    })
+    .await
+    .map_err(|e| AppError(anyhow!(e)))?
+    .map_err(AppError)?;
+    Ok(pdf_response(
+        bytes,
+        format!("statements-{year}-{month:02}.pdf"),
+    ))
+}
+
+async fn user_statement(
+    Extension(context): Extension<GraphQLContext>,
+    jar: CookieJar,
+    Path((year, month, user_uuid)): Path<(i32, u32, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    require_admin_cookie(&context, &jar)?;
+    let (bytes, name) = tokio::task::spawn_blocking(move || {
+        let user_id = UserSvc::get(&context, &user_uuid)?
+            .id
+            .ok_or_else(|| anyhow!("User has no id"))?;
+        let statement = StatementSvc::build(&context, user_id, year, month)?;
+        let name = statement.user.name.clone();
+        Ok::<_, anyhow::Error>((StatementSvc::pdf(&[statement]), name))
+    })
+    .await
+    .map_err(|e| AppError(anyhow!(e)))?
+    .map_err(AppError)?;
+    Ok(pdf_response(
+        bytes,
+        format!("statement-{}-{year}-{month:02}.pdf", filename_part(&name)),
+    ))
+}
+
+fn pdf_response(bytes: Vec<u8>, filename: String) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        bytes,
+    )
+}
+
+/// A kid's name reduced to characters that are safe in a header and a file name.
+fn filename_part(name: &str) -> String {
+    let part: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    part.trim_matches('-').to_owned()
+}
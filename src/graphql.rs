@@ -1,17 +1,38 @@
 #![allow(clippy::too_many_arguments)]
+use anyhow::Context;
 use chrono::NaiveDate;
-use juniper::{EmptySubscription, FieldError, FieldResult, RootNode};
+use juniper::{EmptySubscription, FieldError, FieldResult, RootNode, graphql_value};
 use tracing::error;
 
 use crate::{
     context::GraphQLContext,
     models::{
-        Admin, AdminInput, Chore, ChoreCompletion, ChoreCompletionInput, ChoreCompletionNote,
-        ChoreCompletionNoteInput, ChoreInput, UnpaidTotal, User, UserBadge, UserInput,
+        Admin, AdminInput, AdminNotificationPrefs, AdminNotificationPrefsInput, BadgeDefinition,
+        BadgeDefinitionInput, BonusChoreClaim, CalendarDay, CalendarDayInput, CalendarDayKind,
+        CalendarFeed, Challenge, ChallengeInput, Chore, ChoreCompletion, ChoreCompletionInput,
+        ChoreCompletionNote, ChoreCompletionNoteInput, ChoreInput, ChoreTemplate,
+        ChoreTemplateInput, DeliveryStatus, InterestCredit, InterestRate, InterestRateInput, Jar,
+        JarEntry, JarSplit, JarSplitInput, LevelThreshold, PausePeriod, PausePeriodInput,
+        PushTarget, PushTargetInput, RedemptionStatus, Reward, RewardInput, RewardRedemption,
+        SavingsGoal, SavingsGoalAllocation, SavingsGoalInput, UnpaidTotal, User, UserBadge,
+        UserInput, UserStreak, WebPushSubscription, WebhookDelivery, WebhookEndpoint,
+        WebhookEndpointInput,
     },
     svc::{
-        AdminSvc, ChoreCompletionNoteSvc, ChoreCompletionSvc, ChoreSvc, UserSvc,
-        chore_completion::ChoreCompletionFilter, user::UserBalance,
+        AdminSvc, AnalyticsSvc, BadgeSvc, BonusClaimSvc, CalendarFeedSvc, CalendarSvc,
+        ChallengeSvc, ChoreCompletionNoteSvc, ChoreCompletionSvc, ChoreSvc, ChoreTemplateSvc,
+        DigestSvc, InterestSvc, JarSvc, LeaderboardSvc, NotificationSvc, PauseSvc, PointsSvc,
+        PushSvc, RewardSvc, SavingsGoalSvc, ScheduleSvc, StreakSvc, UserSvc, WebPushSvc,
+        WebhookSvc,
+        analytics::{EarningsAnalytics, EarningsBucket},
+        badge::BadgeProgress,
+        bonus_claim::ClaimError,
+        chore_completion::{ChoreCompletionFilter, CompletionError},
+        digest::Digest,
+        jar::JarAmounts,
+        leaderboard::{LeaderboardEntry, LeaderboardMetric},
+        schedule::{CompletionRate, CompletionStats},
+        user::UserBalance,
     },
 };
 
@@ -47,6 +68,14 @@ impl Query {
         graphql_translate_anyhow(AdminSvc::get(context, &admin_uuid))
     }
 
+    // Email notification opt-ins of the signed-in admin
+    pub fn my_notification_preferences(
+        context: &GraphQLContext,
+    ) -> FieldResult<AdminNotificationPrefs> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(NotificationSvc::preferences(context, admin_id))
+    }
+
     pub fn list_admins(
         context: &GraphQLContext,
         limit: Option<i32>,
@@ -79,6 +108,140 @@ impl Query {
         graphql_translate_anyhow(ChoreSvc::list_bonus_chores(context, date))
     }
 
+    // Bonus chore claims: who holds (or held) a slot on which bonus chore
+    pub fn list_bonus_chore_claims(
+        context: &GraphQLContext,
+        chore_id: Option<i32>,
+        user_id: Option<i32>,
+        held_only: Option<bool>,
+    ) -> FieldResult<Vec<BonusChoreClaim>> {
+        let held_only = held_only.unwrap_or(false);
+        graphql_translate_anyhow(BonusClaimSvc::list(context, chore_id, user_id, held_only))
+    }
+
+    // Recurring chores a user still has to do on a date (paused ones are left out)
+    pub fn due_chores(
+        context: &GraphQLContext,
+        user_id: i32,
+        date: NaiveDate,
+    ) -> FieldResult<Vec<Chore>> {
+        graphql_translate_anyhow(ScheduleSvc::due_chores(context, user_id, date))
+    }
+
+    pub fn completion_rate(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> FieldResult<CompletionRate> {
+        graphql_translate_anyhow(ScheduleSvc::completion_rate(context, user_id, from, to))
+    }
+
+    // Completion rate per chore and weekday, with perfect-day streaks
+    pub fn completion_stats(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> FieldResult<CompletionStats> {
+        graphql_translate_anyhow(ScheduleSvc::completion_stats(context, user_id, from, to))
+    }
+
+    // Kids ranked over a date range, optionally normalized by each kid's workload
+    pub fn leaderboard(
+        context: &GraphQLContext,
+        metric: LeaderboardMetric,
+        from: NaiveDate,
+        to: NaiveDate,
+        normalize: Option<bool>,
+        exclude_user_ids: Option<Vec<i32>>,
+    ) -> FieldResult<Vec<LeaderboardEntry>> {
+        graphql_translate_anyhow(LeaderboardSvc::build(
+            context,
+            metric,
+            from,
+            to,
+            normalize.unwrap_or(false),
+            &exclude_user_ids.unwrap_or_default(),
+        ))
+    }
+
+    // Per-kid summary of done, pending, missed and owed, with text and HTML renderings
+    pub fn digest(context: &GraphQLContext, from: NaiveDate, to: NaiveDate) -> FieldResult<Digest> {
+        graphql_translate_anyhow(DigestSvc::build(context, from, to))
+    }
+
+    // Approved earnings bucketed by day, week or month, per user or per user and chore
+    pub fn earnings_analytics(
+        context: &GraphQLContext,
+        from: NaiveDate,
+        to: NaiveDate,
+        bucket: EarningsBucket,
+        user_id: Option<i32>,
+        chore_id: Option<i32>,
+        by_chore: Option<bool>,
+    ) -> FieldResult<EarningsAnalytics> {
+        graphql_translate_anyhow(AnalyticsSvc::earnings(
+            context,
+            from,
+            to,
+            bucket,
+            user_id,
+            chore_id,
+            by_chore.unwrap_or(false),
+        ))
+    }
+
+    // Household calendar of skip and alternate-schedule days
+    pub fn list_calendar_days(
+        context: &GraphQLContext,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> FieldResult<Vec<CalendarDay>> {
+        graphql_translate_anyhow(CalendarSvc::list(context, from, to))
+    }
+
+    // A kid's iCalendar feed; the token is a credential, so only admins see it
+    pub fn calendar_feed(
+        context: &GraphQLContext,
+        user_id: i32,
+    ) -> FieldResult<Option<CalendarFeed>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(CalendarFeedSvc::get(context, user_id))
+    }
+
+    // Vacation and sick-day pauses
+    pub fn list_pause_periods(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        active_on: Option<NaiveDate>,
+    ) -> FieldResult<Vec<PausePeriod>> {
+        graphql_translate_anyhow(PauseSvc::list(context, user_id, active_on))
+    }
+
+    // Chore templates
+    pub async fn get_chore_template(
+        context: &GraphQLContext,
+        template_uuid: String,
+    ) -> FieldResult<ChoreTemplate> {
+        graphql_translate_anyhow(ChoreTemplateSvc::get(context, &template_uuid))
+    }
+
+    pub fn list_chore_templates(
+        context: &GraphQLContext,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> FieldResult<Vec<ChoreTemplate>> {
+        let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT);
+        let offset = offset.unwrap_or(DEFAULT_LIST_OFFSET);
+        graphql_translate_anyhow(ChoreTemplateSvc::list(context, limit, offset))
+    }
+
+    // Shareable JSON library of every template
+    pub fn export_chore_templates(context: &GraphQLContext) -> FieldResult<String> {
+        graphql_translate_anyhow(ChoreTemplateSvc::export_library(context))
+    }
+
     // Chore Completions
     pub async fn get_chore_completion(
         context: &GraphQLContext,
@@ -142,7 +305,75 @@ impl Query {
         ))
     }
 
+    // ntfy/Gotify push targets of admins and kids
+    pub fn list_push_targets(
+        context: &GraphQLContext,
+        admin_id: Option<i32>,
+        user_id: Option<i32>,
+    ) -> FieldResult<Vec<PushTarget>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(PushSvc::list(context, admin_id, user_id))
+    }
+
+    // Web Push: the PWA needs the public key to subscribe, so it is not admin-only
+    pub fn vapid_public_key(context: &GraphQLContext) -> FieldResult<String> {
+        graphql_translate_anyhow(WebPushSvc::vapid_public_key(context))
+    }
+
+    pub fn list_web_push_subscriptions(
+        context: &GraphQLContext,
+        admin_id: Option<i32>,
+        user_id: Option<i32>,
+    ) -> FieldResult<Vec<WebPushSubscription>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebPushSvc::list(context, admin_id, user_id))
+    }
+
+    // Webhooks
+    pub fn list_webhook_endpoints(context: &GraphQLContext) -> FieldResult<Vec<WebhookEndpoint>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebhookSvc::list_endpoints(context))
+    }
+
+    // Delivery log, newest first
+    pub fn list_webhook_deliveries(
+        context: &GraphQLContext,
+        endpoint_uuid: Option<String>,
+        status: Option<DeliveryStatus>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> FieldResult<Vec<WebhookDelivery>> {
+        context.require_admin()?;
+        let limit = limit.unwrap_or(DEFAULT_LIST_LIMIT);
+        let offset = offset.unwrap_or(DEFAULT_LIST_OFFSET);
+        graphql_translate_anyhow(WebhookSvc::list_deliveries(
+            context,
+            endpoint_uuid.as_deref(),
+            status,
+            limit,
+            offset,
+        ))
+    }
+
     // Badges
+    pub fn list_badge_definitions(
+        context: &GraphQLContext,
+        include_inactive: Option<bool>,
+    ) -> FieldResult<Vec<BadgeDefinition>> {
+        graphql_translate_anyhow(BadgeSvc::list_definitions(
+            context,
+            include_inactive.unwrap_or(false),
+        ))
+    }
+
+    // Progress towards each active badge, for progress bars
+    pub fn badge_progress(
+        context: &GraphQLContext,
+        user_id: i32,
+    ) -> FieldResult<Vec<BadgeProgress>> {
+        graphql_translate_anyhow(BadgeSvc::progress(context, user_id))
+    }
+
     pub fn user_badges(context: &GraphQLContext, user_id: i32) -> FieldResult<Vec<UserBadge>> {
         use crate::schema::user_badges::dsl;
         use diesel::prelude::*;
@@ -155,6 +386,90 @@ impl Query {
             },
         ))
     }
+
+    // Streaks
+    pub fn streak(context: &GraphQLContext, user_id: i32) -> FieldResult<UserStreak> {
+        graphql_translate_anyhow(StreakSvc::get(context, user_id))
+    }
+
+    // XP needed for each level; a user's own thresholds, or the household defaults
+    pub fn level_thresholds(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+    ) -> FieldResult<Vec<LevelThreshold>> {
+        graphql_translate_anyhow(PointsSvc::thresholds(context, user_id))
+    }
+
+    // Rewards store
+    pub fn list_rewards(
+        context: &GraphQLContext,
+        include_inactive: Option<bool>,
+    ) -> FieldResult<Vec<Reward>> {
+        graphql_translate_anyhow(RewardSvc::list(context, include_inactive.unwrap_or(false)))
+    }
+
+    // Redemption history, newest first; filter by status for the approval queue
+    pub fn list_reward_redemptions(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        status: Option<RedemptionStatus>,
+    ) -> FieldResult<Vec<RewardRedemption>> {
+        graphql_translate_anyhow(RewardSvc::redemptions(context, user_id, status))
+    }
+
+    // Savings goals, per user or for everyone
+    pub fn savings_goals(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+    ) -> FieldResult<Vec<SavingsGoal>> {
+        graphql_translate_anyhow(SavingsGoalSvc::list(context, user_id))
+    }
+
+    // Spend, save and give jars
+    pub fn jar_balances(context: &GraphQLContext, user_id: i32) -> FieldResult<JarAmounts> {
+        graphql_translate_anyhow(JarSvc::balances(context, user_id))
+    }
+
+    pub fn jar_split(context: &GraphQLContext, user_id: i32) -> FieldResult<JarSplit> {
+        graphql_translate_anyhow(JarSvc::split(context, user_id))
+    }
+
+    // Jar history, newest first
+    pub fn jar_entries(
+        context: &GraphQLContext,
+        user_id: i32,
+        jar: Option<Jar>,
+    ) -> FieldResult<Vec<JarEntry>> {
+        graphql_translate_anyhow(JarSvc::entries(context, user_id, jar))
+    }
+
+    // Family challenges, newest first; with a user only those they take part in
+    pub fn challenges(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+    ) -> FieldResult<Vec<Challenge>> {
+        graphql_translate_anyhow(ChallengeSvc::list(context, user_id))
+    }
+
+    pub fn challenge(context: &GraphQLContext, challenge_uuid: String) -> FieldResult<Challenge> {
+        graphql_translate_anyhow(ChallengeSvc::get(context, &challenge_uuid))
+    }
+
+    // Interest on the save jar: the rate in effect for a user, or the household rate
+    pub fn interest_rate(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+    ) -> FieldResult<Option<InterestRate>> {
+        graphql_translate_anyhow(InterestSvc::rate(context, user_id))
+    }
+
+    // Interest credited to a user, newest period first, with the inputs of each
+    pub fn interest_credits(
+        context: &GraphQLContext,
+        user_id: i32,
+    ) -> FieldResult<Vec<InterestCredit>> {
+        graphql_translate_anyhow(InterestSvc::credits(context, user_id))
+    }
 }
 
 /// GraphQL mutation root: all write operations are implemented here.
@@ -190,6 +505,32 @@ impl Mutation {
         graphql_translate_anyhow(AdminSvc::update(context, &admin.into()))
     }
 
+    pub async fn update_notification_preferences(
+        context: &GraphQLContext,
+        prefs: AdminNotificationPrefsInput,
+    ) -> FieldResult<AdminNotificationPrefs> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(NotificationSvc::update_preferences(
+            context, admin_id, &prefs,
+        ))
+    }
+
+    // Send a test email to the signed-in admin to check the SMTP settings
+    pub async fn send_test_email(context: &GraphQLContext) -> FieldResult<bool> {
+        let admin_id = context.require_admin()?;
+        // SMTP is blocking and can take up to its timeout, so keep it off the async workers
+        let context = context.clone();
+        graphql_translate_anyhow(
+            tokio::task::spawn_blocking(move || {
+                NotificationSvc::send_test_email(&context, admin_id)
+            })
+            .await
+            .context("Could not send test email")
+            .and_then(|sent| sent),
+        )?;
+        Ok(true)
+    }
+
     // Chores
     pub async fn create_chore(context: &GraphQLContext, chore: ChoreInput) -> FieldResult<Chore> {
         context.require_admin()?;
@@ -215,6 +556,141 @@ impl Mutation {
         Ok(true)
     }
 
+    pub async fn set_chore_checklist(
+        context: &GraphQLContext,
+        chore_id: i32,
+        items: Vec<String>,
+    ) -> FieldResult<Vec<String>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChoreSvc::set_checklist(context, chore_id, &items))
+    }
+
+    // Household calendar
+    pub async fn save_calendar_day(
+        context: &GraphQLContext,
+        day: CalendarDayInput,
+    ) -> FieldResult<CalendarDay> {
+        context.require_admin()?;
+        graphql_translate_anyhow(CalendarSvc::upsert(context, &day.into()))
+    }
+
+    pub async fn delete_calendar_day(
+        context: &GraphQLContext,
+        day_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(CalendarSvc::delete(context, &day_uuid))?;
+        Ok(true)
+    }
+
+    // Import an ICS file (e.g. a school calendar); every event date becomes a `kind` day
+    pub async fn import_calendar_ics(
+        context: &GraphQLContext,
+        ics: String,
+        kind: CalendarDayKind,
+        alternate_weekday: Option<i32>,
+    ) -> FieldResult<Vec<CalendarDay>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(CalendarSvc::import_ics(
+            context,
+            &ics,
+            kind,
+            alternate_weekday,
+        ))
+    }
+
+    // Creates a kid's iCalendar feed, or replaces its token so old subscriptions stop working
+    pub async fn rotate_calendar_feed(
+        context: &GraphQLContext,
+        user_id: i32,
+    ) -> FieldResult<CalendarFeed> {
+        context.require_admin()?;
+        graphql_translate_anyhow(CalendarFeedSvc::rotate(context, user_id))
+    }
+
+    pub async fn revoke_calendar_feed(context: &GraphQLContext, user_id: i32) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(CalendarFeedSvc::revoke(context, user_id))?;
+        Ok(true)
+    }
+
+    // Vacation and sick-day pauses
+    pub async fn create_pause_period(
+        context: &GraphQLContext,
+        pause: PausePeriodInput,
+    ) -> FieldResult<PausePeriod> {
+        let admin_id = context.require_admin()?;
+        let mut pause = PausePeriod::from(pause);
+        pause.created_by_admin_id = Some(admin_id);
+        graphql_translate_anyhow(PauseSvc::create(context, &pause))
+    }
+
+    pub async fn update_pause_period(
+        context: &GraphQLContext,
+        pause: PausePeriodInput,
+    ) -> FieldResult<PausePeriod> {
+        context.require_admin()?;
+        graphql_translate_anyhow(PauseSvc::update(context, &pause.into()))
+    }
+
+    pub async fn delete_pause_period(
+        context: &GraphQLContext,
+        pause_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(PauseSvc::delete(context, &pause_uuid))?;
+        Ok(true)
+    }
+
+    // Chore templates
+    pub async fn create_chore_template(
+        context: &GraphQLContext,
+        template: ChoreTemplateInput,
+    ) -> FieldResult<ChoreTemplate> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChoreTemplateSvc::create(context, &template.into()))
+    }
+
+    pub async fn update_chore_template(
+        context: &GraphQLContext,
+        template: ChoreTemplateInput,
+    ) -> FieldResult<ChoreTemplate> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChoreTemplateSvc::update(context, &template.into()))
+    }
+
+    pub async fn delete_chore_template(
+        context: &GraphQLContext,
+        template_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChoreTemplateSvc::delete(context, &template_uuid))?;
+        Ok(true)
+    }
+
+    // Create a chore from a template and assign it in one step
+    pub async fn instantiate_chore_template(
+        context: &GraphQLContext,
+        template_uuid: String,
+        user_ids: Vec<i32>,
+    ) -> FieldResult<Chore> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(ChoreTemplateSvc::instantiate(
+            context,
+            &template_uuid,
+            admin_id,
+            &user_ids,
+        ))
+    }
+
+    pub async fn import_chore_templates(
+        context: &GraphQLContext,
+        library: String,
+    ) -> FieldResult<Vec<ChoreTemplate>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChoreTemplateSvc::import_library(context, &library))
+    }
+
     // Assign user to chore
     pub async fn assign_user_to_chore(
         context: &GraphQLContext,
@@ -237,6 +713,22 @@ impl Mutation {
         Ok(true)
     }
 
+    // Bonus chore claims
+    pub async fn claim_bonus_chore(
+        context: &GraphQLContext,
+        chore_id: i32,
+        user_id: i32,
+    ) -> FieldResult<BonusChoreClaim> {
+        graphql_translate_anyhow(BonusClaimSvc::claim(context, chore_id, user_id))
+    }
+
+    pub async fn release_bonus_chore_claim(
+        context: &GraphQLContext,
+        claim_uuid: String,
+    ) -> FieldResult<BonusChoreClaim> {
+        graphql_translate_anyhow(BonusClaimSvc::release(context, &claim_uuid))
+    }
+
     // Chore Completions
     pub async fn create_chore_completion(
         context: &GraphQLContext,
@@ -277,6 +769,328 @@ impl Mutation {
         Ok(true)
     }
 
+    // Reject a completion that is still waiting for approval
+    pub async fn reject_chore_completion(
+        context: &GraphQLContext,
+        completion_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChoreCompletionSvc::reject(context, &completion_uuid))?;
+        Ok(true)
+    }
+
+    // Push targets (ntfy/Gotify)
+    pub async fn create_push_target(
+        context: &GraphQLContext,
+        target: PushTargetInput,
+    ) -> FieldResult<PushTarget> {
+        let admin_id = context.require_admin()?;
+        let mut target = PushTarget::from(target);
+        if target.admin_id.is_none() && target.user_id.is_none() {
+            target.admin_id = Some(admin_id);
+        }
+        graphql_translate_anyhow(PushSvc::create(context, &target))
+    }
+
+    pub async fn update_push_target(
+        context: &GraphQLContext,
+        target: PushTargetInput,
+    ) -> FieldResult<PushTarget> {
+        let admin_id = context.require_admin()?;
+        let mut target = PushTarget::from(target);
+        if target.admin_id.is_none() && target.user_id.is_none() {
+            target.admin_id = Some(admin_id);
+        }
+        graphql_translate_anyhow(PushSvc::update(context, &target))
+    }
+
+    pub async fn delete_push_target(
+        context: &GraphQLContext,
+        target_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(PushSvc::delete(context, &target_uuid))?;
+        Ok(true)
+    }
+
+    // Send a test message to a push target to check its settings
+    pub async fn send_test_push(
+        context: &GraphQLContext,
+        target_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        // The blocking HTTP client must not be built or wait on an async worker
+        let context = context.clone();
+        graphql_translate_anyhow(
+            tokio::task::spawn_blocking(move || PushSvc::send_test(&context, &target_uuid))
+                .await
+                .context("Could not send test push")
+                .and_then(|sent| sent),
+        )?;
+        Ok(true)
+    }
+
+    // Web Push. Rotating the VAPID keys drops every subscription; returns the new public key
+    pub async fn rotate_vapid_keys(context: &GraphQLContext) -> FieldResult<String> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebPushSvc::rotate_vapid_keys(context))
+    }
+
+    pub async fn delete_web_push_subscription(
+        context: &GraphQLContext,
+        subscription_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebPushSvc::delete(context, &subscription_uuid))?;
+        Ok(true)
+    }
+
+    // Badge definitions. Changes apply the next time a user's badges are checked
+    pub async fn create_badge_definition(
+        context: &GraphQLContext,
+        definition: BadgeDefinitionInput,
+    ) -> FieldResult<BadgeDefinition> {
+        context.require_admin()?;
+        graphql_translate_anyhow(BadgeSvc::create_definition(context, &definition.into()))
+    }
+
+    pub async fn update_badge_definition(
+        context: &GraphQLContext,
+        definition: BadgeDefinitionInput,
+    ) -> FieldResult<BadgeDefinition> {
+        context.require_admin()?;
+        graphql_translate_anyhow(BadgeSvc::update_definition(context, &definition.into()))
+    }
+
+    pub async fn delete_badge_definition(
+        context: &GraphQLContext,
+        definition_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(BadgeSvc::delete_definition(context, &definition_uuid))?;
+        Ok(true)
+    }
+
+    // Rewards store
+    pub async fn create_reward(
+        context: &GraphQLContext,
+        reward: RewardInput,
+    ) -> FieldResult<Reward> {
+        context.require_admin()?;
+        graphql_translate_anyhow(RewardSvc::create(context, &reward.into()))
+    }
+
+    pub async fn update_reward(
+        context: &GraphQLContext,
+        reward: RewardInput,
+    ) -> FieldResult<Reward> {
+        context.require_admin()?;
+        graphql_translate_anyhow(RewardSvc::update(context, &reward.into()))
+    }
+
+    pub async fn delete_reward(context: &GraphQLContext, reward_uuid: String) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(RewardSvc::delete(context, &reward_uuid))?;
+        Ok(true)
+    }
+
+    // A kid asks for a reward; it waits for an admin to approve or reject it
+    pub async fn request_reward(
+        context: &GraphQLContext,
+        reward_uuid: String,
+        user_id: i32,
+    ) -> FieldResult<RewardRedemption> {
+        graphql_translate_anyhow(RewardSvc::request(context, &reward_uuid, user_id))
+    }
+
+    pub async fn approve_reward_redemption(
+        context: &GraphQLContext,
+        redemption_uuid: String,
+    ) -> FieldResult<RewardRedemption> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(RewardSvc::approve(context, &redemption_uuid, admin_id))
+    }
+
+    pub async fn reject_reward_redemption(
+        context: &GraphQLContext,
+        redemption_uuid: String,
+        note: Option<String>,
+    ) -> FieldResult<RewardRedemption> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(RewardSvc::reject(context, &redemption_uuid, admin_id, note))
+    }
+
+    // Savings goals. Pictures are uploaded through `/images/goal/{goalUuid}`
+    pub async fn create_savings_goal(
+        context: &GraphQLContext,
+        goal: SavingsGoalInput,
+    ) -> FieldResult<SavingsGoal> {
+        context.require_admin()?;
+        graphql_translate_anyhow(SavingsGoalSvc::create(context, &goal.into()))
+    }
+
+    pub async fn update_savings_goal(
+        context: &GraphQLContext,
+        goal: SavingsGoalInput,
+    ) -> FieldResult<SavingsGoal> {
+        context.require_admin()?;
+        graphql_translate_anyhow(SavingsGoalSvc::update(context, &goal.into()))
+    }
+
+    pub async fn delete_savings_goal(
+        context: &GraphQLContext,
+        goal_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(SavingsGoalSvc::delete(context, &goal_uuid))?;
+        Ok(true)
+    }
+
+    // Set money aside for a goal that tracks allocations; negative amounts take it back out
+    pub async fn allocate_to_savings_goal(
+        context: &GraphQLContext,
+        goal_uuid: String,
+        amount_cents: i32,
+        note: Option<String>,
+    ) -> FieldResult<SavingsGoalAllocation> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(SavingsGoalSvc::allocate(
+            context,
+            &goal_uuid,
+            amount_cents,
+            note,
+            admin_id,
+        ))
+    }
+
+    // Jars: the split applies to what is earned from now on
+    pub async fn set_jar_split(
+        context: &GraphQLContext,
+        split: JarSplitInput,
+    ) -> FieldResult<JarSplit> {
+        context.require_admin()?;
+        graphql_translate_anyhow(JarSvc::set_split(context, &split.into()))
+    }
+
+    pub async fn transfer_between_jars(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: Jar,
+        to: Jar,
+        amount_cents: i32,
+        note: Option<String>,
+    ) -> FieldResult<JarAmounts> {
+        let admin_id = context.require_admin()?;
+        graphql_translate_anyhow(JarSvc::transfer(
+            context,
+            user_id,
+            from,
+            to,
+            amount_cents,
+            note,
+            admin_id,
+        ))
+    }
+
+    // Family challenges
+    pub async fn create_challenge(
+        context: &GraphQLContext,
+        challenge: ChallengeInput,
+        user_ids: Vec<i32>,
+    ) -> FieldResult<Challenge> {
+        let admin_id = context.require_admin()?;
+        let challenge = Challenge {
+            created_by_admin_id: Some(admin_id),
+            ..challenge.into()
+        };
+        graphql_translate_anyhow(ChallengeSvc::create(context, &challenge, &user_ids))
+    }
+
+    pub async fn update_challenge(
+        context: &GraphQLContext,
+        challenge: ChallengeInput,
+        user_ids: Vec<i32>,
+    ) -> FieldResult<Challenge> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChallengeSvc::update(context, &challenge.into(), &user_ids))
+    }
+
+    pub async fn delete_challenge(
+        context: &GraphQLContext,
+        challenge_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(ChallengeSvc::delete(context, &challenge_uuid))?;
+        Ok(true)
+    }
+
+    // Interest rates. Without a user this sets the household rate; clearing a user's
+    // rate puts them back on it
+    pub async fn set_interest_rate(
+        context: &GraphQLContext,
+        rate: InterestRateInput,
+    ) -> FieldResult<InterestRate> {
+        context.require_admin()?;
+        graphql_translate_anyhow(InterestSvc::set_rate(context, &rate.into()))
+    }
+
+    pub async fn clear_interest_rate(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(InterestSvc::clear_rate(context, user_id))?;
+        Ok(true)
+    }
+
+    // Level thresholds: `xp` lists the XP for level 2, 3 and so on. Without a user this
+    // sets the household defaults; an empty list puts a user back on them
+    pub async fn set_level_thresholds(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        xp: Vec<i32>,
+    ) -> FieldResult<Vec<LevelThreshold>> {
+        context.require_admin()?;
+        graphql_translate_anyhow(PointsSvc::set_thresholds(context, user_id, &xp))
+    }
+
+    // Webhooks
+    pub async fn create_webhook_endpoint(
+        context: &GraphQLContext,
+        endpoint: WebhookEndpointInput,
+    ) -> FieldResult<WebhookEndpoint> {
+        let admin_id = context.require_admin()?;
+        let mut endpoint = WebhookEndpoint::from(endpoint);
+        endpoint.created_by_admin_id = Some(admin_id);
+        graphql_translate_anyhow(WebhookSvc::create_endpoint(context, &endpoint))
+    }
+
+    pub async fn update_webhook_endpoint(
+        context: &GraphQLContext,
+        endpoint: WebhookEndpointInput,
+    ) -> FieldResult<WebhookEndpoint> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebhookSvc::update_endpoint(context, &endpoint.into()))
+    }
+
+    pub async fn delete_webhook_endpoint(
+        context: &GraphQLContext,
+        endpoint_uuid: String,
+    ) -> FieldResult<bool> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebhookSvc::delete_endpoint(context, &endpoint_uuid))?;
+        Ok(true)
+    }
+
+    // Queue a delivery again, e.g. after it failed every attempt
+    pub async fn retry_webhook_delivery(
+        context: &GraphQLContext,
+        delivery_uuid: String,
+    ) -> FieldResult<WebhookDelivery> {
+        context.require_admin()?;
+        graphql_translate_anyhow(WebhookSvc::retry_delivery(context, &delivery_uuid))
+    }
+
     // Chore Completion Notes
     pub async fn create_chore_completion_note(
         context: &GraphQLContext,
@@ -313,11 +1127,24 @@ pub fn create_schema() -> Schema {
 }
 
 /// Converts an `anyhow::Result` into a Juniper `FieldResult`, logging the error on failure.
+/// Known domain errors carry a `code` extension so clients can branch on them.
 pub fn graphql_translate_anyhow<T>(res: anyhow::Result<T>) -> FieldResult<T> {
     match res {
         Ok(t) => Ok(t),
         Err(e) => {
             error!("GraphQL error: {:#?}", e);
+            if let Some(completion_error) = e.downcast_ref::<CompletionError>() {
+                return Err(FieldError::new(
+                    completion_error,
+                    graphql_value!({ "code": completion_error.code() }),
+                ));
+            }
+            if let Some(claim_error) = e.downcast_ref::<ClaimError>() {
+                return Err(FieldError::new(
+                    claim_error,
+                    graphql_value!({ "code": claim_error.code() }),
+                ));
+            }
             Err(FieldError::from(e))
         }
     }
@@ -12,7 +12,9 @@ pub mod context;
 pub mod db;
 pub mod graphql;
 pub mod models;
+pub mod pdf;
 pub mod routes;
+pub mod scheduler;
 pub mod schema;
 pub mod svc;
 
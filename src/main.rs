@@ -39,6 +39,8 @@ async fn main() -> Result<()> {
         Err(e) => error!("Could not run migrations {:?}", e),
     };
 
+    chore_tracker::scheduler::spawn(context.clone());
+
     let app = app(context.clone()).await;
 
     let (tx, mut rx) = mpsc::channel(1);
@@ -14,7 +14,11 @@ use uuid::Uuid;
 use crate::{
     context::GraphQLContext,
     schema::*,
-    svc::{ChoreCompletionNoteSvc, UserImageSvc},
+    svc::{
+        BadgeSvc, BonusClaimSvc, ChallengeSvc, ChoreCompletionNoteSvc, ChoreSvc, JarSvc, PointsSvc,
+        SavingsGoalSvc, UserImageSvc, UserSvc, WebhookSvc, challenge::ChallengeContribution,
+        jar::JarAmounts, points::UserLevel,
+    },
 };
 
 // Enums
@@ -66,6 +70,158 @@ impl From<AuthorType> for String {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, GraphQLEnum)]
+pub enum ClaimStatus {
+    Active,
+    Fulfilled,
+    Released,
+    Expired,
+}
+
+impl<T: AsRef<str>> From<T> for ClaimStatus {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "fulfilled" => Self::Fulfilled,
+            "released" => Self::Released,
+            "expired" => Self::Expired,
+            _ => Self::Active,
+        }
+    }
+}
+
+impl From<ClaimStatus> for String {
+    fn from(cs: ClaimStatus) -> Self {
+        match cs {
+            ClaimStatus::Active => "active".to_owned(),
+            ClaimStatus::Fulfilled => "fulfilled".to_owned(),
+            ClaimStatus::Released => "released".to_owned(),
+            ClaimStatus::Expired => "expired".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum CalendarDayKind {
+    Skip,
+    Alternate,
+}
+
+impl<T: AsRef<str>> From<T> for CalendarDayKind {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "alternate" => Self::Alternate,
+            _ => Self::Skip,
+        }
+    }
+}
+
+impl From<CalendarDayKind> for String {
+    fn from(kind: CalendarDayKind) -> Self {
+        match kind {
+            CalendarDayKind::Skip => "skip".to_owned(),
+            CalendarDayKind::Alternate => "alternate".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, GraphQLEnum)]
+pub enum WebhookEventType {
+    CompletionCreated,
+    CompletionApproved,
+    CompletionRejected,
+    PayoutMade,
+    BadgeEarned,
+    DigestCreated,
+    SavingsGoalReached,
+    ChallengeCompleted,
+}
+
+impl WebhookEventType {
+    /// Event name used in payloads, headers and the stored subscription list.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CompletionCreated => "completion.created",
+            Self::CompletionApproved => "completion.approved",
+            Self::CompletionRejected => "completion.rejected",
+            Self::PayoutMade => "payout.made",
+            Self::BadgeEarned => "badge.earned",
+            Self::DigestCreated => "digest.created",
+            Self::SavingsGoalReached => "savings_goal.reached",
+            Self::ChallengeCompleted => "challenge.completed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|event_type| event_type.as_str() == s)
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::CompletionCreated,
+            Self::CompletionApproved,
+            Self::CompletionRejected,
+            Self::PayoutMade,
+            Self::BadgeEarned,
+            Self::DigestCreated,
+            Self::SavingsGoalReached,
+            Self::ChallengeCompleted,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl<T: AsRef<str>> From<T> for DeliveryStatus {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "delivered" => Self::Delivered,
+            "failed" => Self::Failed,
+            _ => Self::Pending,
+        }
+    }
+}
+
+impl From<DeliveryStatus> for String {
+    fn from(status: DeliveryStatus) -> Self {
+        match status {
+            DeliveryStatus::Pending => "pending".to_owned(),
+            DeliveryStatus::Delivered => "delivered".to_owned(),
+            DeliveryStatus::Failed => "failed".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum PushProvider {
+    Ntfy,
+    Gotify,
+}
+
+impl<T: AsRef<str>> From<T> for PushProvider {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "gotify" => Self::Gotify,
+            _ => Self::Ntfy,
+        }
+    }
+}
+
+impl From<PushProvider> for String {
+    fn from(provider: PushProvider) -> Self {
+        match provider {
+            PushProvider::Ntfy => "ntfy".to_owned(),
+            PushProvider::Gotify => "gotify".to_owned(),
+        }
+    }
+}
+
 // User model
 #[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
 #[diesel(primary_key(id))]
@@ -110,6 +266,27 @@ impl User {
     pub fn image_id(&self) -> Option<i32> {
         self.image_id
     }
+    /// Level reached with the XP from approved completions.
+    pub fn level(&self, context: &GraphQLContext) -> juniper::FieldResult<UserLevel> {
+        let user_id = self
+            .id
+            .ok_or_else(|| juniper::FieldError::new("User has no id", juniper::Value::null()))?;
+        Ok(PointsSvc::level(context, user_id).context("fetching user level")?)
+    }
+    /// Points available to spend.
+    pub fn points_balance(&self, context: &GraphQLContext) -> juniper::FieldResult<i32> {
+        let user_id = self
+            .id
+            .ok_or_else(|| juniper::FieldError::new("User has no id", juniper::Value::null()))?;
+        Ok(PointsSvc::balance(context, user_id).context("fetching points balance")?)
+    }
+    /// Balances of the spend, save and give jars.
+    pub fn jars(&self, context: &GraphQLContext) -> juniper::FieldResult<JarAmounts> {
+        let user_id = self
+            .id
+            .ok_or_else(|| juniper::FieldError::new("User has no id", juniper::Value::null()))?;
+        Ok(JarSvc::balances(context, user_id).context("fetching jar balances")?)
+    }
 }
 
 // User image model for storing images in database
@@ -233,6 +410,28 @@ impl From<AdminInput> for Admin {
     }
 }
 
+// Admin email notification opt-ins, stored as columns on `admins`
+#[derive(Queryable, Debug, Clone, Selectable, GraphQLObject)]
+#[diesel(table_name = admins)]
+pub struct AdminNotificationPrefs {
+    #[diesel(column_name = id)]
+    pub admin_id: Option<i32>,
+    pub email: String,
+    pub notify_pending_approval: bool,
+    pub notify_bonus_claimed: bool,
+    pub notify_weekly_summary: bool,
+    pub notify_digest: bool,
+}
+
+#[derive(GraphQLInputObject, AsChangeset, Debug, Clone, Default)]
+#[diesel(table_name = admins)]
+pub struct AdminNotificationPrefsInput {
+    pub notify_pending_approval: Option<bool>,
+    pub notify_bonus_claimed: Option<bool>,
+    pub notify_weekly_summary: Option<bool>,
+    pub notify_digest: Option<bool>,
+}
+
 // AdminSession model
 #[derive(
     Queryable, Debug, Identifiable, Insertable, Selectable, AsChangeset,
@@ -265,6 +464,11 @@ pub struct Chore {
     pub updated_at: Option<NaiveDateTime>,
     pub bonus_date: Option<NaiveDate>,
     pub max_claims: Option<i32>,
+    pub points: i32,
+    /// When the chore was last switched back on; `None` means since it was created.
+    pub activated_at: Option<NaiveDateTime>,
+    /// When the chore was switched off, while it is inactive.
+    pub deactivated_at: Option<NaiveDateTime>,
 }
 
 #[juniper::graphql_object(context = GraphQLContext)]
@@ -308,6 +512,35 @@ impl Chore {
     pub fn max_claims(&self) -> Option<i32> {
         self.max_claims
     }
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+    /// Claims currently holding a slot on this bonus chore (active or fulfilled).
+    pub fn claims(&self, context: &GraphQLContext) -> juniper::FieldResult<Vec<BonusChoreClaim>> {
+        let chore_id = self
+            .id
+            .ok_or_else(|| juniper::FieldError::new("Chore has no id", juniper::Value::null()))?;
+        Ok(BonusClaimSvc::list(context, Some(chore_id), None, true)
+            .context("fetching bonus chore claims")?)
+    }
+    /// Slots still open on a capped bonus chore; `None` when the chore is unlimited.
+    pub fn remaining_claims(&self, context: &GraphQLContext) -> juniper::FieldResult<Option<i32>> {
+        let Some(cap) = self.max_claims else {
+            return Ok(None);
+        };
+        let chore_id = self
+            .id
+            .ok_or_else(|| juniper::FieldError::new("Chore has no id", juniper::Value::null()))?;
+        let held = BonusClaimSvc::held_count(context, chore_id).context("counting bonus claims")?;
+        Ok(Some((cap - held).max(0)))
+    }
+    /// Ordered checklist steps for this chore (empty when none were defined).
+    pub fn checklist(&self, context: &GraphQLContext) -> juniper::FieldResult<Vec<String>> {
+        let chore_id = self
+            .id
+            .ok_or_else(|| juniper::FieldError::new("Chore has no id", juniper::Value::null()))?;
+        Ok(ChoreSvc::get_checklist(context, chore_id).context("fetching chore checklist")?)
+    }
     pub fn assigned_users(&self, context: &GraphQLContext) -> juniper::FieldResult<Vec<User>> {
         use crate::schema::chore_assignments::dsl::*;
         use crate::schema::users::dsl as users_dsl;
@@ -340,6 +573,8 @@ pub struct ChoreInput {
     pub created_by_admin_id: i32,
     pub bonus_date: Option<NaiveDate>,
     pub max_claims: Option<i32>,
+    /// Points awarded per week (weekly chores) or per completion; defaults to none.
+    pub points: Option<i32>,
 }
 
 impl From<ChoreInput> for Chore {
@@ -358,6 +593,114 @@ impl From<ChoreInput> for Chore {
             updated_at: None,
             bonus_date: input.bonus_date,
             max_claims: input.max_claims,
+            points: input.points.unwrap_or(0),
+            activated_at: None,
+            deactivated_at: None,
+        }
+    }
+}
+
+// Chore checklist item model
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = chore_checklist_items)]
+pub struct ChoreChecklistItem {
+    pub id: Option<i32>,
+    pub chore_id: i32,
+    pub position: i32,
+    pub item_text: String,
+}
+
+// Chore template model
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = chore_templates)]
+pub struct ChoreTemplate {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub payment_type: String, // Will be converted to/from PaymentType enum in GraphQL
+    pub amount_cents: i32,
+    pub required_days: i32, // Bitmask for days of week, same as Chore
+    pub checklist: String,  // JSON array of checklist steps
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub points: i32,
+}
+
+impl ChoreTemplate {
+    /// Decodes the stored checklist JSON; malformed data yields an empty checklist.
+    pub fn checklist_items(&self) -> Vec<String> {
+        serde_json::from_str(&self.checklist).unwrap_or_default()
+    }
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl ChoreTemplate {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn payment_type(&self) -> PaymentType {
+        PaymentType::from(&self.payment_type)
+    }
+    pub fn amount_cents(&self) -> i32 {
+        self.amount_cents
+    }
+    pub fn required_days(&self) -> i32 {
+        self.required_days
+    }
+    pub fn checklist(&self) -> Vec<String> {
+        self.checklist_items()
+    }
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct ChoreTemplateInput {
+    pub uuid: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub payment_type: PaymentType,
+    pub amount_cents: i32,
+    pub required_days: i32,
+    pub checklist: Option<Vec<String>>,
+    /// Points for chores made from the template, like `ChoreInput::points`; defaults to none.
+    pub points: Option<i32>,
+}
+
+impl From<ChoreTemplateInput> for ChoreTemplate {
+    fn from(input: ChoreTemplateInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            name: input.name,
+            description: input.description,
+            payment_type: input.payment_type.into(),
+            amount_cents: input.amount_cents,
+            required_days: input.required_days,
+            checklist: serde_json::to_string(&input.checklist.unwrap_or_default())
+                .unwrap_or_else(|_| "[]".to_owned()),
+            created_at: None,
+            updated_at: None,
+            points: input.points.unwrap_or(0),
         }
     }
 }
@@ -390,6 +733,62 @@ impl From<ChoreAssignmentInput> for ChoreAssignment {
     }
 }
 
+// Bonus Chore Claim model
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = bonus_chore_claims)]
+pub struct BonusChoreClaim {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub chore_id: i32,
+    pub user_id: i32,
+    pub status: String, // Will be converted to/from ClaimStatus enum in GraphQL
+    pub claimed_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub completion_id: Option<i32>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl BonusChoreClaim {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn chore_id(&self) -> i32 {
+        self.chore_id
+    }
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn status(&self) -> ClaimStatus {
+        ClaimStatus::from(&self.status)
+    }
+    pub fn claimed_at(&self) -> NaiveDateTime {
+        self.claimed_at
+    }
+    pub fn expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+    pub fn completion_id(&self) -> Option<i32> {
+        self.completion_id
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+
+    // Relationship fields
+    pub async fn chore(&self, context: &GraphQLContext) -> juniper::FieldResult<Chore> {
+        Ok(ChoreSvc::get_by_id(context, self.chore_id).context("fetching chore for claim")?)
+    }
+
+    pub async fn user(&self, context: &GraphQLContext) -> juniper::FieldResult<User> {
+        Ok(UserSvc::get_by_id(context, self.user_id).context("fetching user for claim")?)
+    }
+}
+
 // Chore Completion model
 #[derive(Queryable, Debug, Identifiable, Insertable, Selectable, AsChangeset)]
 #[diesel(primary_key(id))]
@@ -408,6 +807,7 @@ pub struct ChoreCompletion {
     pub paid_out_at: Option<NaiveDateTime>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub points: i32,
 }
 
 // Custom GraphQL object implementation for ChoreCompletion to add relationships
@@ -437,6 +837,10 @@ impl ChoreCompletion {
         self.amount_cents
     }
 
+    pub fn points(&self) -> i32 {
+        self.points
+    }
+
     pub fn approved(&self) -> bool {
         self.approved
     }
@@ -556,6 +960,22 @@ impl PaymentType {
             }
         }
     }
+
+    /// Calculates the points for a single chore completion, split like the payment but
+    /// rounded to whole points
+    pub fn calculate_completion_points(
+        payment_type: &Self,
+        chore_points: i32,
+        required_days: i32,
+    ) -> i32 {
+        let assigned_days_count = Self::get_assigned_days_count(required_days);
+        match payment_type {
+            Self::Weekly if assigned_days_count > 0 => {
+                (chore_points as f64 / assigned_days_count as f64).round() as i32
+            }
+            _ => chore_points,
+        }
+    }
 }
 
 #[derive(GraphQLInputObject, Debug, Clone)]
@@ -645,116 +1065,1684 @@ impl From<ChoreCompletionNoteInput> for ChoreCompletionNote {
     }
 }
 
-// BadgeType enum
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum BadgeType {
-    FirstChore,
-    TenDollarsEarned,
-    FiftyDollarsEarned,
-    PerfectWeek,
-    FiveDayStreak,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum BadgeRuleType {
+    /// Approved completions, of one chore when the badge names one.
+    CompletionCount,
+    /// Approved earnings in cents.
+    EarningsTotal,
+    /// Longest streak of days with every scheduled chore done.
+    StreakDays,
+    /// Weeks with every required chore done.
+    PerfectWeeks,
 }
 
-impl BadgeType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::FirstChore => "first_chore",
-            Self::TenDollarsEarned => "ten_dollars_earned",
-            Self::FiftyDollarsEarned => "fifty_dollars_earned",
-            Self::PerfectWeek => "perfect_week",
-            Self::FiveDayStreak => "five_day_streak",
+impl<T: AsRef<str>> From<T> for BadgeRuleType {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "earnings_total" => Self::EarningsTotal,
+            "streak_days" => Self::StreakDays,
+            "perfect_weeks" => Self::PerfectWeeks,
+            _ => Self::CompletionCount,
         }
     }
+}
 
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "first_chore" => Some(Self::FirstChore),
-            "ten_dollars_earned" => Some(Self::TenDollarsEarned),
-            "fifty_dollars_earned" => Some(Self::FiftyDollarsEarned),
-            "perfect_week" => Some(Self::PerfectWeek),
-            "five_day_streak" => Some(Self::FiveDayStreak),
-            _ => None,
+impl From<BadgeRuleType> for String {
+    fn from(rule_type: BadgeRuleType) -> Self {
+        match rule_type {
+            BadgeRuleType::CompletionCount => "completion_count".to_owned(),
+            BadgeRuleType::EarningsTotal => "earnings_total".to_owned(),
+            BadgeRuleType::StreakDays => "streak_days".to_owned(),
+            BadgeRuleType::PerfectWeeks => "perfect_weeks".to_owned(),
         }
     }
-
-    pub fn all() -> Vec<Self> {
-        vec![
-            Self::FirstChore,
-            Self::TenDollarsEarned,
-            Self::FiftyDollarsEarned,
-            Self::PerfectWeek,
-            Self::FiveDayStreak,
-        ]
-    }
 }
 
-// UserBadge model
-#[derive(Queryable, Debug)]
-pub struct UserBadge {
-    pub id: i32,
-    pub user_id: i32,
-    pub badge_type: String,
-    pub earned_at: NaiveDateTime,
+// Badge definition: a rule and the threshold at which it awards the badge
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = badge_definitions)]
+pub struct BadgeDefinition {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub badge_key: String, // Stored in user_badges.badge_type
+    pub name: String,
+    pub description: Option<String>,
+    pub rule_type: String, // Will be converted to/from BadgeRuleType enum in GraphQL
+    pub threshold: i32,
+    pub chore_id: Option<i32>, // Only for completion_count rules
+    pub active: bool,
+    pub position: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
 }
 
 #[juniper::graphql_object(context = GraphQLContext)]
-impl UserBadge {
-    pub fn id(&self) -> i32 {
+impl BadgeDefinition {
+    pub fn id(&self) -> Option<i32> {
         self.id
     }
-
-    pub fn user_id(&self) -> i32 {
-        self.user_id
+    pub fn uuid(&self) -> &str {
+        &self.uuid
     }
-
-    pub fn badge_type(&self) -> &str {
-        &self.badge_type
+    /// Identifier stored as `badgeType` on earned badges.
+    pub fn badge_key(&self) -> &str {
+        &self.badge_key
     }
-
-    pub fn earned_at(&self) -> NaiveDateTime {
-        self.earned_at
+    pub fn name(&self) -> &str {
+        &self.name
     }
-}
-
-// Helper GraphQL object for unpaid totals
-#[derive(Debug, Clone)]
-pub struct UnpaidTotal {
-    pub user: User,
-    pub amount_cents: i32,
-}
-
-#[juniper::graphql_object(context = GraphQLContext)]
-impl UnpaidTotal {
-    pub fn user(&self) -> &User {
-        &self.user
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn rule_type(&self) -> BadgeRuleType {
+        BadgeRuleType::from(&self.rule_type)
+    }
+    pub fn threshold(&self) -> i32 {
+        self.threshold
+    }
+    pub fn chore_id(&self) -> Option<i32> {
+        self.chore_id
+    }
+    pub fn active(&self) -> bool {
+        self.active
+    }
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct BadgeDefinitionInput {
+    pub uuid: Option<String>,
+    /// Cannot be changed once the badge exists.
+    pub badge_key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub rule_type: BadgeRuleType,
+    pub threshold: i32,
+    pub chore_id: Option<i32>,
+    pub active: Option<bool>,
+    pub position: Option<i32>,
+}
+
+impl From<BadgeDefinitionInput> for BadgeDefinition {
+    fn from(input: BadgeDefinitionInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            badge_key: input.badge_key,
+            name: input.name,
+            description: input.description,
+            rule_type: input.rule_type.into(),
+            threshold: input.threshold,
+            chore_id: input.chore_id,
+            active: input.active.unwrap_or(true),
+            position: input.position.unwrap_or(0),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// UserBadge model
+#[derive(Queryable, Debug)]
+pub struct UserBadge {
+    pub id: i32,
+    pub user_id: i32,
+    pub badge_type: String,
+    pub earned_at: NaiveDateTime,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl UserBadge {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn badge_type(&self) -> &str {
+        &self.badge_type
+    }
+
+    /// The definition the badge was earned under, unless it has since been deleted.
+    pub fn definition(
+        &self,
+        context: &GraphQLContext,
+    ) -> juniper::FieldResult<Option<BadgeDefinition>> {
+        Ok(BadgeSvc::get_definition_by_key(context, &self.badge_type)
+            .context("fetching badge definition")?)
+    }
+
+    pub fn earned_at(&self) -> NaiveDateTime {
+        self.earned_at
+    }
+}
+
+// Pause period model: a span of dates during which a user's chores (or one chore) are not due
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = pause_periods)]
+pub struct PausePeriod {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub user_id: i32,
+    pub chore_id: Option<i32>, // None pauses every chore for the user
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate, // Inclusive
+    pub reason: Option<String>,
+    pub created_by_admin_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl PausePeriod {
+    /// Whether this pause excuses `chore_id` on `date`.
+    pub fn covers(&self, chore_id: i32, date: NaiveDate) -> bool {
+        self.start_date <= date
+            && date <= self.end_date
+            && self.chore_id.map_or(true, |id| id == chore_id)
+    }
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl PausePeriod {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn chore_id(&self) -> Option<i32> {
+        self.chore_id
+    }
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+    pub fn end_date(&self) -> NaiveDate {
+        self.end_date
+    }
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+    pub fn created_by_admin_id(&self) -> Option<i32> {
+        self.created_by_admin_id
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+
+    // Relationships
+    pub async fn user(&self, context: &GraphQLContext) -> juniper::FieldResult<User> {
+        Ok(UserSvc::get_by_id(context, self.user_id).context("fetching paused user")?)
+    }
+    pub async fn chore(&self, context: &GraphQLContext) -> juniper::FieldResult<Option<Chore>> {
+        let Some(chore_id) = self.chore_id else {
+            return Ok(None);
+        };
+        Ok(Some(
+            ChoreSvc::get_by_id(context, chore_id).context("fetching paused chore")?,
+        ))
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct PausePeriodInput {
+    pub uuid: Option<String>,
+    pub user_id: i32,
+    pub chore_id: Option<i32>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
+impl From<PausePeriodInput> for PausePeriod {
+    fn from(input: PausePeriodInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            user_id: input.user_id,
+            chore_id: input.chore_id,
+            start_date: input.start_date,
+            end_date: input.end_date,
+            reason: input.reason,
+            created_by_admin_id: None,
+            created_at: None,
+        }
+    }
+}
+
+// Household calendar day: a holiday or trip date that overrides the weekday schedule
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = household_calendar_days)]
+pub struct CalendarDay {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub date: NaiveDate,
+    pub name: String,
+    pub kind: String, // Will be converted to/from CalendarDayKind enum in GraphQL
+    pub alternate_weekday: Option<i32>, // Monday = 0 … Sunday = 6, only for alternate days
+    pub source: String, // "manual" or "ics"
+    pub ics_uid: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl CalendarDay {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn kind(&self) -> CalendarDayKind {
+        CalendarDayKind::from(&self.kind)
+    }
+    /// Weekday whose schedule applies on an alternate day (Monday = 0 … Sunday = 6).
+    pub fn alternate_weekday(&self) -> Option<i32> {
+        self.alternate_weekday
+    }
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct CalendarDayInput {
+    pub uuid: Option<String>,
+    pub date: NaiveDate,
+    pub name: String,
+    pub kind: CalendarDayKind,
+    pub alternate_weekday: Option<i32>,
+}
+
+impl From<CalendarDayInput> for CalendarDay {
+    fn from(input: CalendarDayInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            date: input.date,
+            name: input.name,
+            kind: input.kind.into(),
+            alternate_weekday: input.alternate_weekday,
+            source: "manual".to_owned(),
+            ics_uid: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// Push target: an ntfy topic or Gotify application that one admin or kid listens to
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = push_targets)]
+pub struct PushTarget {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub provider: String, // Will be converted to/from PushProvider enum in GraphQL
+    pub server_url: String,
+    pub topic: Option<String>,
+    pub token: Option<String>,
+    pub admin_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub active: bool,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl PushTarget {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn provider(&self) -> PushProvider {
+        PushProvider::from(&self.provider)
+    }
+    pub fn server_url(&self) -> &str {
+        &self.server_url
+    }
+    /// ntfy topic; unused for Gotify.
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+    /// Whether an access token (ntfy) or application token (Gotify) is stored. The token
+    /// itself is never returned.
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+    pub fn admin_id(&self) -> Option<i32> {
+        self.admin_id
+    }
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+    pub fn active(&self) -> bool {
+        self.active
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct PushTargetInput {
+    pub uuid: Option<String>,
+    pub provider: PushProvider,
+    pub server_url: String,
+    pub topic: Option<String>,
+    /// Left empty on update to keep the stored token.
+    pub token: Option<String>,
+    /// Set one of `adminId` and `userId`; neither means the signed-in admin.
+    pub admin_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub active: Option<bool>,
+}
+
+impl From<PushTargetInput> for PushTarget {
+    fn from(input: PushTargetInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            provider: input.provider.into(),
+            server_url: input.server_url,
+            topic: input.topic,
+            token: input.token,
+            admin_id: input.admin_id,
+            user_id: input.user_id,
+            active: input.active.unwrap_or(true),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// Web Push subscription of the installed PWA on one device, owned by an admin or a kid
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = web_push_subscriptions)]
+pub struct WebPushSubscription {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub endpoint: String,
+    pub p256dh: String, // base64url P-256 public key of the browser
+    pub auth: String,   // base64url 16-byte auth secret
+    pub admin_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub user_agent: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub last_success_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl WebPushSubscription {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+    pub fn admin_id(&self) -> Option<i32> {
+        self.admin_id
+    }
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn last_success_at(&self) -> Option<NaiveDateTime> {
+        self.last_success_at
+    }
+}
+
+// Webhook endpoint: an admin-registered URL subscribed to some event types
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = webhook_endpoints)]
+pub struct WebhookEndpoint {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub secret: String,
+    pub event_types: String, // JSON array of WebhookEventType names
+    pub active: bool,
+    pub created_by_admin_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl WebhookEndpoint {
+    /// Subscribed event types; unknown names in the stored list are ignored.
+    pub fn subscribed(&self) -> Vec<WebhookEventType> {
+        serde_json::from_str::<Vec<String>>(&self.event_types)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| WebhookEventType::from_str(name))
+            .collect()
+    }
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl WebhookEndpoint {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// Shared secret used to sign payloads (`X-Webhook-Signature`).
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+    pub fn event_types(&self) -> Vec<WebhookEventType> {
+        self.subscribed()
+    }
+    pub fn active(&self) -> bool {
+        self.active
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct WebhookEndpointInput {
+    pub uuid: Option<String>,
+    pub url: String,
+    pub description: Option<String>,
+    /// Generated when left empty on create; kept when left empty on update.
+    pub secret: Option<String>,
+    pub event_types: Vec<WebhookEventType>,
+    pub active: Option<bool>,
+}
+
+impl From<WebhookEndpointInput> for WebhookEndpoint {
+    fn from(input: WebhookEndpointInput) -> Self {
+        let names: Vec<&str> = input.event_types.iter().map(|t| t.as_str()).collect();
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            url: input.url,
+            description: input.description,
+            secret: input.secret.unwrap_or_default(),
+            event_types: serde_json::to_string(&names).unwrap_or_else(|_| "[]".to_owned()),
+            active: input.active.unwrap_or(true),
+            created_by_admin_id: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// Webhook delivery: one event sent (or being retried) to one endpoint
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDelivery {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub endpoint_id: i32,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String, // Will be converted to/from DeliveryStatus enum in GraphQL
+    pub attempts: i32,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub last_attempt_at: Option<NaiveDateTime>,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl WebhookDelivery {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn endpoint_id(&self) -> i32 {
+        self.endpoint_id
+    }
+    /// Shared by the deliveries of one event to different endpoints.
+    pub fn event_id(&self) -> &str {
+        &self.event_id
+    }
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+    pub fn status(&self) -> DeliveryStatus {
+        DeliveryStatus::from(&self.status)
+    }
+    pub fn attempts(&self) -> i32 {
+        self.attempts
+    }
+    pub fn next_attempt_at(&self) -> Option<NaiveDateTime> {
+        self.next_attempt_at
+    }
+    pub fn last_attempt_at(&self) -> Option<NaiveDateTime> {
+        self.last_attempt_at
+    }
+    pub fn response_status(&self) -> Option<i32> {
+        self.response_status
+    }
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn delivered_at(&self) -> Option<NaiveDateTime> {
+        self.delivered_at
+    }
+
+    pub async fn endpoint(
+        &self,
+        context: &GraphQLContext,
+    ) -> juniper::FieldResult<WebhookEndpoint> {
+        Ok(WebhookSvc::get_endpoint_by_id(context, self.endpoint_id)
+            .context("fetching endpoint for webhook delivery")?)
+    }
+}
+
+// Helper GraphQL object for unpaid totals
+#[derive(Debug, Clone)]
+pub struct UnpaidTotal {
+    pub user: User,
+    pub amount_cents: i32,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl UnpaidTotal {
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+    pub fn amount_cents(&self) -> i32 {
+        self.amount_cents
+    }
+    /// Balances of the user's jars, owed or already paid out.
+    pub fn jars(&self, context: &GraphQLContext) -> juniper::FieldResult<JarAmounts> {
+        self.user.jars(context)
+    }
+}
+
+impl UnpaidTotal {
+    pub fn new(user: User, amount_cents: i32) -> Self {
+        Self { user, amount_cents }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_type_from_string() {
+        assert_eq!(PaymentType::from("daily"), PaymentType::Daily);
+        assert_eq!(PaymentType::from("weekly"), PaymentType::Weekly);
+        assert_eq!(PaymentType::from("other"), PaymentType::Daily);
+        assert_eq!(PaymentType::from("WEEkly"), PaymentType::Weekly);
+    }
+    #[test]
+    fn test_author_type_from_string() {
+        assert_eq!(AuthorType::from("user"), AuthorType::User);
+        assert_eq!(AuthorType::from("admin"), AuthorType::Admin);
+        assert_eq!(AuthorType::from("other"), AuthorType::User);
+        assert_eq!(AuthorType::from("ADmin"), AuthorType::Admin);
+    }
+}
+
+// Secret token of a kid's iCalendar feed
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = calendar_feeds)]
+pub struct CalendarFeed {
+    pub id: Option<i32>,
+    pub user_id: i32,
+    pub token: String,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl CalendarFeed {
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+    /// Path of the feed relative to the server root, for calendar subscriptions.
+    pub fn path(&self) -> String {
+        format!("/calendar/{}.ics", self.token)
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+}
+
+// Persisted streak of perfect days of one kid
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(user_id))]
+#[diesel(table_name = user_streaks)]
+pub struct UserStreak {
+    pub user_id: i32,
+    pub current_streak: i32,
+    pub longest_streak: i32,
+    pub last_qualifying_date: Option<NaiveDate>,
+    pub freezes_available: i32,
+    pub evaluated_through: Option<NaiveDate>, // Days after this are not counted yet
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl UserStreak {
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    /// Perfect days in a row, not counting days with nothing due.
+    pub fn current_streak(&self) -> i32 {
+        self.current_streak
+    }
+    pub fn longest_streak(&self) -> i32 {
+        self.longest_streak
+    }
+    pub fn last_qualifying_date(&self) -> Option<NaiveDate> {
+        self.last_qualifying_date
+    }
+    /// Freezes that will each cover one missed day.
+    pub fn freezes_available(&self) -> i32 {
+        self.freezes_available
+    }
+    pub fn evaluated_through(&self) -> Option<NaiveDate> {
+        self.evaluated_through
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+// One evaluated day of a streak, with the state after it
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = streak_days)]
+pub struct StreakDay {
+    pub id: Option<i32>,
+    pub user_id: i32,
+    pub date: NaiveDate,
+    pub outcome: String, // "qualified", "frozen" or "missed"
+    pub streak: i32,
+    pub longest: i32,
+    pub freezes: i32,
+}
+
+// XP needed to reach a level; household default when `user_id` is None
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = level_thresholds)]
+pub struct LevelThreshold {
+    pub id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub level: i32,
+    pub xp: i32,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl LevelThreshold {
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+    pub fn xp(&self) -> i32 {
+        self.xp
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum RewardCurrency {
+    Points,
+    Cents,
+}
+
+impl<T: AsRef<str>> From<T> for RewardCurrency {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "cents" => Self::Cents,
+            _ => Self::Points,
+        }
+    }
+}
+
+impl From<RewardCurrency> for String {
+    fn from(currency: RewardCurrency) -> Self {
+        match currency {
+            RewardCurrency::Points => "points".to_owned(),
+            RewardCurrency::Cents => "cents".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum RedemptionStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl<T: AsRef<str>> From<T> for RedemptionStatus {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "approved" => Self::Approved,
+            "rejected" => Self::Rejected,
+            _ => Self::Pending,
+        }
+    }
+}
+
+impl From<RedemptionStatus> for String {
+    fn from(status: RedemptionStatus) -> Self {
+        match status {
+            RedemptionStatus::Pending => "pending".to_owned(),
+            RedemptionStatus::Approved => "approved".to_owned(),
+            RedemptionStatus::Rejected => "rejected".to_owned(),
+        }
+    }
+}
+
+// Reward in the store, priced in points or cents
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = rewards)]
+pub struct Reward {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub currency: String, // Will be converted to/from RewardCurrency enum in GraphQL
+    pub cost: i32,
+    pub active: bool,
+    pub position: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl Reward {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn currency(&self) -> RewardCurrency {
+        RewardCurrency::from(&self.currency)
+    }
+    pub fn cost(&self) -> i32 {
+        self.cost
+    }
+    pub fn active(&self) -> bool {
+        self.active
+    }
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct RewardInput {
+    pub uuid: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub currency: RewardCurrency,
+    pub cost: i32,
+    pub active: Option<bool>,
+    pub position: Option<i32>,
+}
+
+impl From<RewardInput> for Reward {
+    fn from(input: RewardInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            name: input.name,
+            description: input.description,
+            currency: input.currency.into(),
+            cost: input.cost,
+            active: input.active.unwrap_or(true),
+            position: input.position.unwrap_or(0),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// A kid's request to redeem a reward, with the reward as it was when requested
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = reward_redemptions)]
+pub struct RewardRedemption {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub reward_id: Option<i32>, // None once the reward is deleted
+    pub user_id: i32,
+    pub reward_name: String,
+    pub currency: String,
+    pub cost: i32,
+    pub status: String, // Will be converted to/from RedemptionStatus enum in GraphQL
+    pub requested_at: Option<NaiveDateTime>,
+    pub decided_at: Option<NaiveDateTime>,
+    pub decided_by_admin_id: Option<i32>,
+    pub decision_note: Option<String>,
+    pub paid_out_at: Option<NaiveDateTime>, // Cents redemptions settled with a payout
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl RewardRedemption {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn reward_id(&self) -> Option<i32> {
+        self.reward_id
+    }
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn user(&self, context: &GraphQLContext) -> juniper::FieldResult<User> {
+        Ok(UserSvc::get_by_id(context, self.user_id).context("fetching user for redemption")?)
+    }
+    pub fn reward_name(&self) -> &str {
+        &self.reward_name
+    }
+    pub fn currency(&self) -> RewardCurrency {
+        RewardCurrency::from(&self.currency)
+    }
+    pub fn cost(&self) -> i32 {
+        self.cost
+    }
+    pub fn status(&self) -> RedemptionStatus {
+        RedemptionStatus::from(&self.status)
+    }
+    pub fn requested_at(&self) -> Option<NaiveDateTime> {
+        self.requested_at
+    }
+    pub fn decided_at(&self) -> Option<NaiveDateTime> {
+        self.decided_at
+    }
+    pub fn decided_by_admin_id(&self) -> Option<i32> {
+        self.decided_by_admin_id
+    }
+    /// Reason given when the request was rejected.
+    pub fn decision_note(&self) -> Option<&str> {
+        self.decision_note.as_deref()
+    }
+    pub fn paid_out_at(&self) -> Option<NaiveDateTime> {
+        self.paid_out_at
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum GoalProgressSource {
+    /// The kid's save jar counts towards the goal.
+    Balance,
+    /// Only amounts allocated to the goal out of the save jar count.
+    Allocations,
+}
+
+impl<T: AsRef<str>> From<T> for GoalProgressSource {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "allocations" => Self::Allocations,
+            _ => Self::Balance,
+        }
+    }
+}
+
+impl From<GoalProgressSource> for String {
+    fn from(source: GoalProgressSource) -> Self {
+        match source {
+            GoalProgressSource::Balance => "balance".to_owned(),
+            GoalProgressSource::Allocations => "allocations".to_owned(),
+        }
+    }
+}
+
+// Savings goal of one kid
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = savings_goals)]
+pub struct SavingsGoal {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub user_id: i32,
+    pub name: String,
+    pub target_cents: i32,
+    pub target_date: Option<NaiveDate>,
+    pub image_id: Option<i32>,   // A user_images row of kind 'goal'
+    pub progress_source: String, // Will be converted to/from GoalProgressSource enum in GraphQL
+    pub reached_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl SavingsGoal {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn target_cents(&self) -> i32 {
+        self.target_cents
+    }
+    pub fn target_date(&self) -> Option<NaiveDate> {
+        self.target_date
+    }
+    pub fn image_path(&self, context: &GraphQLContext) -> juniper::FieldResult<Option<String>> {
+        let Some(image_id) = self.image_id else {
+            return Ok(None);
+        };
+        let image = UserImageSvc::get_by_id(context, image_id).context("fetching goal image")?;
+        Ok(image
+            .and_then(|image| image.uuid)
+            .map(|image_uuid| format!("/images/{}", image_uuid)))
+    }
+    pub fn progress_source(&self) -> GoalProgressSource {
+        GoalProgressSource::from(&self.progress_source)
+    }
+    /// Saved so far, per `progressSource`.
+    pub fn progress_cents(&self, context: &GraphQLContext) -> juniper::FieldResult<i32> {
+        Ok(SavingsGoalSvc::progress(context, self).context("fetching goal progress")?)
+    }
+    /// Progress over target, capped at 1.
+    pub fn fraction(&self, context: &GraphQLContext) -> juniper::FieldResult<f64> {
+        let progress = SavingsGoalSvc::progress(context, self).context("fetching goal progress")?;
+        Ok((f64::from(progress.max(0)) / f64::from(self.target_cents)).min(1.0))
+    }
+    /// When progress first met the target.
+    pub fn reached_at(&self) -> Option<NaiveDateTime> {
+        self.reached_at
+    }
+    pub fn allocations(
+        &self,
+        context: &GraphQLContext,
+    ) -> juniper::FieldResult<Vec<SavingsGoalAllocation>> {
+        let goal_id = self.id.ok_or_else(|| {
+            juniper::FieldError::new("SavingsGoal has no id", juniper::Value::null())
+        })?;
+        Ok(SavingsGoalSvc::allocations(context, goal_id).context("fetching goal allocations")?)
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject, Debug, Clone)]
+pub struct SavingsGoalInput {
+    pub uuid: Option<String>,
+    pub user_id: i32,
+    pub name: String,
+    pub target_cents: i32,
+    pub target_date: Option<NaiveDate>,
+    pub progress_source: Option<GoalProgressSource>,
+}
+
+impl From<SavingsGoalInput> for SavingsGoal {
+    fn from(input: SavingsGoalInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            user_id: input.user_id,
+            name: input.name,
+            target_cents: input.target_cents,
+            target_date: input.target_date,
+            image_id: None,
+            progress_source: input
+                .progress_source
+                .unwrap_or(GoalProgressSource::Balance)
+                .into(),
+            reached_at: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// Money set aside for a savings goal; negative when taken back out
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, GraphQLObject)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = savings_goal_allocations)]
+pub struct SavingsGoalAllocation {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub goal_id: i32,
+    pub amount_cents: i32,
+    pub note: Option<String>,
+    pub created_by_admin_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum Jar {
+    Spend,
+    Save,
+    Give,
+}
+
+impl<T: AsRef<str>> From<T> for Jar {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "save" => Self::Save,
+            "give" => Self::Give,
+            _ => Self::Spend,
+        }
+    }
+}
+
+impl From<Jar> for String {
+    fn from(jar: Jar) -> Self {
+        match jar {
+            Jar::Spend => "spend".to_owned(),
+            Jar::Save => "save".to_owned(),
+            Jar::Give => "give".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum JarSplitTiming {
+    /// Each completion is split when it is approved.
+    Approval,
+    /// What is paid out is split at payout.
+    Payout,
+}
+
+impl<T: AsRef<str>> From<T> for JarSplitTiming {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "payout" => Self::Payout,
+            _ => Self::Approval,
+        }
+    }
+}
+
+impl From<JarSplitTiming> for String {
+    fn from(timing: JarSplitTiming) -> Self {
+        match timing {
+            JarSplitTiming::Approval => "approval".to_owned(),
+            JarSplitTiming::Payout => "payout".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum JarEntryKind {
+    /// Share of an approved completion.
+    Completion,
+    /// Share of a payout.
+    Payout,
+    /// Cents rewards settled by a payout.
+    Reward,
+    /// Moved between jars by an admin.
+    Transfer,
+    /// Interest on the save jar.
+    Interest,
+}
+
+impl<T: AsRef<str>> From<T> for JarEntryKind {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "payout" => Self::Payout,
+            "reward" => Self::Reward,
+            "transfer" => Self::Transfer,
+            "interest" => Self::Interest,
+            _ => Self::Completion,
+        }
+    }
+}
+
+impl From<JarEntryKind> for String {
+    fn from(kind: JarEntryKind) -> Self {
+        match kind {
+            JarEntryKind::Completion => "completion".to_owned(),
+            JarEntryKind::Payout => "payout".to_owned(),
+            JarEntryKind::Reward => "reward".to_owned(),
+            JarEntryKind::Transfer => "transfer".to_owned(),
+            JarEntryKind::Interest => "interest".to_owned(),
+        }
+    }
+}
+
+// How a kid's earnings are split between their spend, save and give jars
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable, AsChangeset)]
+#[diesel(primary_key(user_id))]
+#[diesel(table_name = jar_splits)]
+pub struct JarSplit {
+    pub user_id: i32,
+    pub spend_percent: i32,
+    pub save_percent: i32,
+    pub give_percent: i32,
+    pub apply_on: String, // Will be converted to/from JarSplitTiming enum in GraphQL
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl JarSplit {
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn spend_percent(&self) -> i32 {
+        self.spend_percent
+    }
+    pub fn save_percent(&self) -> i32 {
+        self.save_percent
+    }
+    pub fn give_percent(&self) -> i32 {
+        self.give_percent
+    }
+    pub fn apply_on(&self) -> JarSplitTiming {
+        JarSplitTiming::from(&self.apply_on)
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject)]
+pub struct JarSplitInput {
+    pub user_id: i32,
+    pub spend_percent: i32,
+    pub save_percent: i32,
+    pub give_percent: i32,
+    pub apply_on: Option<JarSplitTiming>,
+}
+
+impl From<JarSplitInput> for JarSplit {
+    fn from(input: JarSplitInput) -> Self {
+        Self {
+            user_id: input.user_id,
+            spend_percent: input.spend_percent,
+            save_percent: input.save_percent,
+            give_percent: input.give_percent,
+            apply_on: input.apply_on.unwrap_or(JarSplitTiming::Approval).into(),
+            updated_at: None,
+        }
+    }
+}
+
+// Money moving into, out of or between a kid's jars
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = jar_entries)]
+pub struct JarEntry {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub user_id: i32,
+    pub jar: String, // Will be converted to/from Jar enum in GraphQL
+    pub amount_cents: i32,
+    pub kind: String, // Will be converted to/from JarEntryKind enum in GraphQL
+    pub completion_id: Option<i32>,
+    pub paid_out_at: Option<NaiveDateTime>, // Shared by all entries of one payout
+    pub note: Option<String>,
+    pub created_by_admin_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl JarEntry {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn jar(&self) -> Jar {
+        Jar::from(&self.jar)
     }
     pub fn amount_cents(&self) -> i32 {
         self.amount_cents
     }
+    pub fn kind(&self) -> JarEntryKind {
+        JarEntryKind::from(&self.kind)
+    }
+    pub fn completion_id(&self) -> Option<i32> {
+        self.completion_id
+    }
+    pub fn paid_out_at(&self) -> Option<NaiveDateTime> {
+        self.paid_out_at
+    }
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+    pub fn created_by_admin_id(&self) -> Option<i32> {
+        self.created_by_admin_id
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
 }
 
-impl UnpaidTotal {
-    pub fn new(user: User, amount_cents: i32) -> Self {
-        Self { user, amount_cents }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum InterestPeriod {
+    /// Monday to Sunday.
+    Weekly,
+    /// Calendar months.
+    Monthly,
+}
+
+impl<T: AsRef<str>> From<T> for InterestPeriod {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "weekly" => Self::Weekly,
+            _ => Self::Monthly,
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl From<InterestPeriod> for String {
+    fn from(period: InterestPeriod) -> Self {
+        match period {
+            InterestPeriod::Weekly => "weekly".to_owned(),
+            InterestPeriod::Monthly => "monthly".to_owned(),
+        }
+    }
+}
 
-    #[test]
-    fn test_payment_type_from_string() {
-        assert_eq!(PaymentType::from("daily"), PaymentType::Daily);
-        assert_eq!(PaymentType::from("weekly"), PaymentType::Weekly);
-        assert_eq!(PaymentType::from("other"), PaymentType::Daily);
-        assert_eq!(PaymentType::from("WEEkly"), PaymentType::Weekly);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum InterestRounding {
+    /// To the nearest 25 cents, like weekly chore shares.
+    NearestQuarter,
+    /// To the nearest cent.
+    ExactCent,
+}
+
+impl<T: AsRef<str>> From<T> for InterestRounding {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "quarter" => Self::NearestQuarter,
+            _ => Self::ExactCent,
+        }
     }
-    #[test]
-    fn test_author_type_from_string() {
-        assert_eq!(AuthorType::from("user"), AuthorType::User);
-        assert_eq!(AuthorType::from("admin"), AuthorType::Admin);
-        assert_eq!(AuthorType::from("other"), AuthorType::User);
-        assert_eq!(AuthorType::from("ADmin"), AuthorType::Admin);
+}
+
+impl From<InterestRounding> for String {
+    fn from(rounding: InterestRounding) -> Self {
+        match rounding {
+            InterestRounding::NearestQuarter => "quarter".to_owned(),
+            InterestRounding::ExactCent => "exact".to_owned(),
+        }
+    }
+}
+
+// Interest on the save jar; household rate when `user_id` is None
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = interest_rates)]
+pub struct InterestRate {
+    pub id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub rate_bps: i32,    // Per period, 250 = 2.5%
+    pub period: String,   // Will be converted to/from InterestPeriod enum in GraphQL
+    pub rounding: String, // Will be converted to/from InterestRounding enum in GraphQL
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl InterestRate {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+    /// Interest per period in basis points (250 = 2.5%).
+    pub fn rate_bps(&self) -> i32 {
+        self.rate_bps
+    }
+    pub fn period(&self) -> InterestPeriod {
+        InterestPeriod::from(&self.period)
+    }
+    pub fn rounding(&self) -> InterestRounding {
+        InterestRounding::from(&self.rounding)
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject)]
+pub struct InterestRateInput {
+    pub user_id: Option<i32>,
+    pub rate_bps: i32,
+    pub period: InterestPeriod,
+    pub rounding: Option<InterestRounding>,
+}
+
+impl From<InterestRateInput> for InterestRate {
+    fn from(input: InterestRateInput) -> Self {
+        Self {
+            id: None,
+            user_id: input.user_id,
+            rate_bps: input.rate_bps,
+            period: input.period.into(),
+            rounding: input.rounding.unwrap_or(InterestRounding::ExactCent).into(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+// Interest worked out for one user and period, with its inputs
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = interest_credits)]
+pub struct InterestCredit {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub user_id: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub balance_cents: i32, // Save jar balance the interest was worked out on
+    pub rate_bps: i32,
+    pub rounding: String, // Will be converted to/from InterestRounding enum in GraphQL
+    pub amount_cents: i32,
+    pub jar_entry_id: Option<i32>, // None when the interest rounded to nothing
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl InterestCredit {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+    pub fn period_start(&self) -> NaiveDate {
+        self.period_start
+    }
+    pub fn period_end(&self) -> NaiveDate {
+        self.period_end
+    }
+    /// Save jar balance the interest was worked out on.
+    pub fn balance_cents(&self) -> i32 {
+        self.balance_cents
+    }
+    pub fn rate_bps(&self) -> i32 {
+        self.rate_bps
+    }
+    pub fn rounding(&self) -> InterestRounding {
+        InterestRounding::from(&self.rounding)
+    }
+    pub fn amount_cents(&self) -> i32 {
+        self.amount_cents
+    }
+    pub fn jar_entry_id(&self) -> Option<i32> {
+        self.jar_entry_id
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum ChallengeMetric {
+    /// Approved completions.
+    Completions,
+    /// Cents earned by approved completions.
+    EarnedCents,
+    /// Points earned by approved completions.
+    Points,
+}
+
+impl<T: AsRef<str>> From<T> for ChallengeMetric {
+    fn from(value: T) -> Self {
+        match value.as_ref().to_lowercase().as_str() {
+            "earned_cents" => Self::EarnedCents,
+            "points" => Self::Points,
+            _ => Self::Completions,
+        }
+    }
+}
+
+impl From<ChallengeMetric> for String {
+    fn from(metric: ChallengeMetric) -> Self {
+        match metric {
+            ChallengeMetric::Completions => "completions".to_owned(),
+            ChallengeMetric::EarnedCents => "earned_cents".to_owned(),
+            ChallengeMetric::Points => "points".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum ChallengeStatus {
+    /// Starts later.
+    Upcoming,
+    /// Running and not completed yet.
+    Active,
+    /// The target was met.
+    Completed,
+    /// Ended without meeting the target.
+    Missed,
+}
+
+// Cooperative goal of several kids over a date range
+#[derive(Queryable, Debug, Clone, Identifiable, Insertable, Selectable)]
+#[diesel(primary_key(id))]
+#[diesel(table_name = challenges)]
+pub struct Challenge {
+    pub id: Option<i32>,
+    pub uuid: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub prize: Option<String>,
+    pub metric: String, // Will be converted to/from ChallengeMetric enum in GraphQL
+    pub target: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub chore_id: Option<i32>, // Only this chore counts when set
+    pub completed_at: Option<NaiveDateTime>,
+    pub created_by_admin_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[juniper::graphql_object(context = GraphQLContext)]
+impl Challenge {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// What the family gets for completing it, e.g. a pizza night.
+    pub fn prize(&self) -> Option<&str> {
+        self.prize.as_deref()
+    }
+    pub fn metric(&self) -> ChallengeMetric {
+        ChallengeMetric::from(&self.metric)
+    }
+    pub fn target(&self) -> i32 {
+        self.target
+    }
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+    pub fn end_date(&self) -> NaiveDate {
+        self.end_date
+    }
+    pub fn chore_id(&self) -> Option<i32> {
+        self.chore_id
+    }
+    pub fn participants(&self, context: &GraphQLContext) -> juniper::FieldResult<Vec<User>> {
+        let challenge_id = self.id.ok_or_else(|| {
+            juniper::FieldError::new("Challenge has no id", juniper::Value::null())
+        })?;
+        Ok(ChallengeSvc::participants(context, challenge_id)
+            .context("fetching challenge participants")?)
+    }
+    /// Everyone's contributions added up.
+    pub fn progress(&self, context: &GraphQLContext) -> juniper::FieldResult<i32> {
+        Ok(ChallengeSvc::progress(context, self).context("fetching challenge progress")?)
+    }
+    /// Progress over target, capped at 1.
+    pub fn fraction(&self, context: &GraphQLContext) -> juniper::FieldResult<f64> {
+        let progress =
+            ChallengeSvc::progress(context, self).context("fetching challenge progress")?;
+        Ok((f64::from(progress) / f64::from(self.target)).min(1.0))
+    }
+    /// What each participant contributed, biggest first.
+    pub fn contributions(
+        &self,
+        context: &GraphQLContext,
+    ) -> juniper::FieldResult<Vec<ChallengeContribution>> {
+        Ok(ChallengeSvc::contributions(context, self)
+            .context("fetching challenge contributions")?)
+    }
+    pub fn status(&self) -> ChallengeStatus {
+        ChallengeSvc::status(self, chrono::Local::now().date_naive())
+    }
+    /// When progress first met the target.
+    pub fn completed_at(&self) -> Option<NaiveDateTime> {
+        self.completed_at
+    }
+    pub fn created_by_admin_id(&self) -> Option<i32> {
+        self.created_by_admin_id
+    }
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        self.created_at
+    }
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.updated_at
+    }
+}
+
+#[derive(GraphQLInputObject)]
+pub struct ChallengeInput {
+    pub uuid: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub prize: Option<String>,
+    pub metric: ChallengeMetric,
+    pub target: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub chore_id: Option<i32>,
+}
+
+impl From<ChallengeInput> for Challenge {
+    fn from(input: ChallengeInput) -> Self {
+        Self {
+            id: None,
+            uuid: crate::uuid_or_generate(input.uuid),
+            name: input.name,
+            description: input.description,
+            prize: input.prize,
+            metric: input.metric.into(),
+            target: input.target,
+            start_date: input.start_date,
+            end_date: input.end_date,
+            chore_id: input.chore_id,
+            completed_at: None,
+            created_by_admin_id: None,
+            created_at: None,
+            updated_at: None,
+        }
     }
 }
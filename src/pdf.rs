@@ -0,0 +1,214 @@
+//! Minimal PDF writer for printable reports.
+//!
+//! Only what statements need: US Letter pages of text in the standard Helvetica,
+//! Helvetica-Bold and Courier fonts, so no fonts are embedded. Text is encoded as
+//! WinAnsi; characters outside Latin-1 are replaced with `?`.
+
+use std::fmt::Write;
+
+pub const PAGE_WIDTH: f32 = 612.0;
+pub const PAGE_HEIGHT: f32 = 792.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+    /// Fixed width, used for right-aligned numbers.
+    Mono,
+}
+
+impl Font {
+    const fn resource(self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+            Self::Mono => "F3",
+        }
+    }
+}
+
+/// A document under construction: pages of positioned text.
+#[derive(Debug, Default)]
+pub struct PdfDocument {
+    pages: Vec<String>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_page(&mut self) {
+        self.pages.push(String::new());
+    }
+
+    pub const fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Draws `text` with its baseline starting at `(x, y)`, measured in points from the
+    /// bottom-left corner of the current page.
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        if self.pages.is_empty() {
+            self.add_page();
+        }
+        if let Some(page) = self.pages.last_mut() {
+            let _ = writeln!(
+                page,
+                "BT /{} {size} Tf {x:.2} {y:.2} Td ({}) Tj ET",
+                font.resource(),
+                escape(text)
+            );
+        }
+    }
+
+    /// Draws monospaced `text` so that it ends at `right`.
+    pub fn text_right(&mut self, right: f32, y: f32, size: f32, text: &str) {
+        // Courier glyphs are all 600/1000 em wide
+        let width = text.chars().count() as f32 * size * 0.6;
+        self.text(right - width, y, Font::Mono, size, text);
+    }
+
+    /// Draws a horizontal rule.
+    pub fn line(&mut self, x1: f32, x2: f32, y: f32) {
+        if self.pages.is_empty() {
+            self.add_page();
+        }
+        if let Some(page) = self.pages.last_mut() {
+            let _ = writeln!(page, "0.5 w {x1:.2} {y:.2} m {x2:.2} {y:.2} l S");
+        }
+    }
+
+    /// Serializes the document.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.pages.is_empty() {
+            self.add_page();
+        }
+
+        // Objects 1-5 are fixed; each page then takes a page object and a content stream
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + i * 2).collect();
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_owned(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                self.pages.len()
+            ),
+            font_object("Helvetica"),
+            font_object("Helvetica-Bold"),
+            font_object("Courier"),
+        ];
+        for (page, id) in self.pages.iter().zip(&page_ids) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> \
+                 /Contents {} 0 R >>",
+                id + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{page}endstream",
+                encode(page).len()
+            ));
+        }
+
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(&encode(object));
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = out.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.extend_from_slice(trailer.as_bytes());
+        out
+    }
+}
+
+fn font_object(base_font: &str) -> String {
+    format!("<< /Type /Font /Subtype /Type1 /BaseFont /{base_font} /Encoding /WinAnsiEncoding >>")
+}
+
+/// Escapes a PDF string literal.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\r' | '\n' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Latin-1 bytes of a serialized object, which match WinAnsi for the printable range.
+fn encode(object: &str) -> Vec<u8> {
+    object
+        .chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_structure_and_xref_offsets() {
+        let mut doc = PdfDocument::new();
+        doc.text(50.0, 700.0, Font::Bold, 16.0, "Statement (April)");
+        doc.add_page();
+        doc.text_right(560.0, 700.0, 10.0, "$1.50");
+        let bytes = doc.finish();
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(Statement \\(April\\)) Tj"));
+
+        // Every xref entry points at its object header; offsets count bytes, not the
+        // lossily decoded binary marker line
+        let xref_start: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|rest| rest.lines().next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let offsets: Vec<usize> = std::str::from_utf8(&bytes[xref_start..])
+            .unwrap()
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(offsets.len(), 9);
+        for (i, offset) in offsets.iter().enumerate() {
+            assert!(bytes[*offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_latin1_text_is_single_byte() {
+        let mut doc = PdfDocument::new();
+        doc.text(0.0, 0.0, Font::Regular, 10.0, "Zoë → ok");
+        let bytes = doc.finish();
+        assert!(bytes.windows(9).any(|w| w == b"(Zo\xEB ? ok"));
+    }
+}
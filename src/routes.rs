@@ -1,4 +1,4 @@
-use crate::api::{auth, graphql, images};
+use crate::api::{auth, calendar, export, graphql, images, push, statements};
 use crate::auth::OidcConfig;
 use crate::context::GraphQLContext;
 use crate::graphql::create_schema;
@@ -57,7 +57,8 @@ async fn index_handler() -> impl IntoResponse {
 }
 
 /// Builds the top-level Axum router with CORS, compression, static assets, and the
-/// `/graphql`, `/auth`, and `/images` sub-routers wired up.
+/// `/graphql`, `/auth`, `/images`, `/push`, `/export`, `/statements`, and `/calendar`
+/// sub-routers wired up.
 pub async fn app(context: GraphQLContext) -> Router {
     let qm_schema = create_schema();
     let mut oidc_config = OidcConfig::from_env();
@@ -90,12 +91,24 @@ pub async fn app(context: GraphQLContext) -> Router {
             6 * 1024 * 1024,
         ));
 
+    let push_routes = push::push_routes().layer(Extension(context.clone()));
+
+    let export_routes = export::export_routes().layer(Extension(context.clone()));
+
+    let calendar_routes = calendar::calendar_routes().layer(Extension(context.clone()));
+
+    let statement_routes = statements::statement_routes().layer(Extension(context.clone()));
+
     Router::new()
         .route("/assets/{*uri}", get(static_handler))
         .layer(middleware::from_fn(set_static_cache_control))
         .nest("/graphql", graphql_routes)
         .nest("/auth", auth_routes)
         .nest("/images", image_routes)
+        .nest("/push", push_routes)
+        .nest("/export", export_routes)
+        .nest("/statements", statement_routes)
+        .nest("/calendar", calendar_routes)
         .route("/", get(index_handler))
         .fallback_service(get(index_handler))
         .layer(Extension(context.clone()))
@@ -0,0 +1,269 @@
+//! Background jobs that run on a timer inside the server process.
+//!
+//! Each job has a schedule; once a minute the latest slot of every job is compared with its last
+//! recorded run (see `JobSvc`) and due jobs run on the blocking thread pool.
+
+use crate::{
+    context::GraphQLContext,
+    get_env_typed,
+    svc::{
+        InterestSvc, JobSvc, NotificationSvc, StreakSvc, UserSvc, WebhookSvc,
+        digest::DigestFrequency, notification::NotificationEvent,
+    },
+};
+use anyhow::Result;
+use chrono::{Datelike, Duration, Local, NaiveDateTime, NaiveTime, Utc, Weekday};
+
+const TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// Every `minutes` minutes, counted from midnight.
+    Every { minutes: u32 },
+    /// Once a day at `hour`:00 server-local time.
+    Daily { hour: u32 },
+    /// Once a week on `weekday` at `hour`:00 server-local time.
+    Weekly { weekday: Weekday, hour: u32 },
+}
+
+impl Schedule {
+    /// The most recent time at or before `now` this schedule was due.
+    pub fn latest_slot(self, now: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            Self::Every { minutes } => {
+                let midnight = now.date().and_time(NaiveTime::MIN);
+                let step = i64::from(minutes.max(1));
+                let elapsed = (now - midnight).num_minutes();
+                midnight + Duration::minutes(elapsed - elapsed % step)
+            }
+            Self::Daily { hour } => {
+                let time = NaiveTime::from_hms_opt(hour.min(23), 0, 0).unwrap_or_default();
+                let slot = now.date().and_time(time);
+                if slot > now {
+                    slot - Duration::days(1)
+                } else {
+                    slot
+                }
+            }
+            Self::Weekly { weekday, hour } => {
+                let today = now.date();
+                let days_back = (7 + today.weekday().num_days_from_monday()
+                    - weekday.num_days_from_monday())
+                    % 7;
+                let time = NaiveTime::from_hms_opt(hour.min(23), 0, 0).unwrap_or_default();
+                let slot = (today - Duration::days(days_back.into())).and_time(time);
+                if slot > now {
+                    slot - Duration::days(7)
+                } else {
+                    slot
+                }
+            }
+        }
+    }
+}
+
+pub struct Job {
+    pub name: &'static str,
+    pub schedule: Schedule,
+    pub run: fn(&GraphQLContext) -> Result<()>,
+}
+
+/// Every background job. Schedules come from the environment so they can be tuned per
+/// household.
+pub fn jobs() -> Vec<Job> {
+    vec![
+        Job {
+            name: "weekly_payout_summary",
+            schedule: Schedule::Weekly {
+                weekday: get_env_typed::<Weekday>("WEEKLY_SUMMARY_DAY", Weekday::Sun),
+                hour: get_env_typed::<u32>("WEEKLY_SUMMARY_HOUR", 18),
+            },
+            run: |context| {
+                NotificationSvc::notify(context, &NotificationEvent::WeeklyPayoutSummary);
+                Ok(())
+            },
+        },
+        Job {
+            name: "chore_reminders",
+            schedule: Schedule::Daily {
+                hour: get_env_typed::<u32>("CHORE_REMINDER_HOUR", 16),
+            },
+            run: |context| {
+                let today = Local::now().date_naive();
+                for user_id in UserSvc::list(context, i32::MAX, 0)?
+                    .into_iter()
+                    .filter_map(|user| user.id)
+                {
+                    NotificationSvc::notify(
+                        context,
+                        &NotificationEvent::ChoresDueToday {
+                            user_id,
+                            date: today,
+                        },
+                    );
+                }
+                Ok(())
+            },
+        },
+        Job {
+            name: "streaks",
+            schedule: Schedule::Daily {
+                hour: get_env_typed::<u32>("STREAK_HOUR", 1),
+            },
+            run: |context| {
+                // Settles yesterday for kids who have not opened the app since
+                for user_id in UserSvc::list(context, i32::MAX, 0)?
+                    .into_iter()
+                    .filter_map(|user| user.id)
+                {
+                    // One kid's failure should not hold up the rest
+                    if let Err(e) = StreakSvc::get(context, user_id) {
+                        tracing::warn!("Could not settle streak of user {}: {:?}", user_id, e);
+                    }
+                }
+                Ok(())
+            },
+        },
+        Job {
+            name: "interest",
+            schedule: Schedule::Daily {
+                hour: get_env_typed::<u32>("INTEREST_HOUR", 2),
+            },
+            run: |context| {
+                // Credits each kid once per period, making up periods the job missed
+                let today = Local::now().date_naive();
+                for user_id in UserSvc::list(context, i32::MAX, 0)?
+                    .into_iter()
+                    .filter_map(|user| user.id)
+                {
+                    if let Err(e) = InterestSvc::credit_due(context, user_id, today) {
+                        tracing::warn!("Could not credit interest for user {}: {:?}", user_id, e);
+                    }
+                }
+                Ok(())
+            },
+        },
+        Job {
+            name: "digest",
+            schedule: match DigestFrequency::from_env() {
+                Some(DigestFrequency::Daily) => Schedule::Daily {
+                    hour: DigestFrequency::hour(),
+                },
+                _ => Schedule::Weekly {
+                    weekday: DigestFrequency::weekday(),
+                    hour: DigestFrequency::hour(),
+                },
+            },
+            run: |context| {
+                let Some(frequency) = DigestFrequency::from_env() else {
+                    return Ok(());
+                };
+                let (from, to) = frequency.period(Local::now().date_naive());
+                NotificationSvc::notify(context, &NotificationEvent::Digest { from, to });
+                Ok(())
+            },
+        },
+        Job {
+            name: "webhook_deliveries",
+            schedule: Schedule::Every { minutes: 1 },
+            run: |context| {
+                WebhookSvc::deliver_due(context, Utc::now().naive_utc())?;
+                Ok(())
+            },
+        },
+    ]
+}
+
+/// Runs every job whose latest slot has not been run yet.
+pub fn run_due_jobs(context: &GraphQLContext, now: NaiveDateTime) {
+    for job in jobs() {
+        let slot = job.schedule.latest_slot(now);
+        match JobSvc::claim_run(context, job.name, slot) {
+            Ok(true) => {
+                tracing::info!("Running scheduled job {}", job.name);
+                if let Err(e) = (job.run)(context) {
+                    tracing::warn!("Scheduled job {} failed: {:?}", job.name, e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => tracing::warn!("Could not check scheduled job {}: {:?}", job.name, e),
+        }
+    }
+}
+
+/// Starts the scheduler loop on the current tokio runtime.
+pub fn spawn(context: GraphQLContext) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            let context = context.clone();
+            let now = Local::now().naive_local();
+            if let Err(e) = tokio::task::spawn_blocking(move || run_due_jobs(&context, now)).await {
+                tracing::warn!("Scheduler tick panicked: {:?}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::test_db::create_test_date;
+
+    #[test]
+    fn test_weekly_latest_slot() {
+        let schedule = Schedule::Weekly {
+            weekday: Weekday::Sun,
+            hour: 18,
+        };
+        let sunday_slot = create_test_date(2026, 4, 12).and_hms_opt(18, 0, 0).unwrap();
+
+        // Wednesday after: last Sunday's slot
+        let wednesday = create_test_date(2026, 4, 15).and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(schedule.latest_slot(wednesday), sunday_slot);
+
+        // Sunday before 18:00: the previous week's slot
+        let sunday_morning = create_test_date(2026, 4, 12).and_hms_opt(8, 0, 0).unwrap();
+        assert_eq!(
+            schedule.latest_slot(sunday_morning),
+            sunday_slot - Duration::days(7)
+        );
+
+        // Exactly on time
+        assert_eq!(schedule.latest_slot(sunday_slot), sunday_slot);
+    }
+
+    #[test]
+    fn test_daily_latest_slot() {
+        let schedule = Schedule::Daily { hour: 16 };
+        let today_slot = create_test_date(2026, 4, 15).and_hms_opt(16, 0, 0).unwrap();
+
+        let evening = create_test_date(2026, 4, 15).and_hms_opt(20, 5, 0).unwrap();
+        assert_eq!(schedule.latest_slot(evening), today_slot);
+
+        let morning = create_test_date(2026, 4, 15).and_hms_opt(7, 0, 0).unwrap();
+        assert_eq!(
+            schedule.latest_slot(morning),
+            today_slot - Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn test_every_latest_slot() {
+        let schedule = Schedule::Every { minutes: 15 };
+        let now = create_test_date(2026, 4, 15)
+            .and_hms_opt(9, 37, 12)
+            .unwrap();
+        assert_eq!(
+            schedule.latest_slot(now),
+            create_test_date(2026, 4, 15).and_hms_opt(9, 30, 0).unwrap()
+        );
+
+        let every_minute = Schedule::Every { minutes: 1 };
+        assert_eq!(
+            every_minute.latest_slot(now),
+            create_test_date(2026, 4, 15).and_hms_opt(9, 37, 0).unwrap()
+        );
+    }
+}
@@ -19,6 +19,76 @@ diesel::table! {
         oidc_subject -> Text,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        notify_pending_approval -> Bool,
+        notify_bonus_claimed -> Bool,
+        notify_weekly_summary -> Bool,
+        notify_digest -> Bool,
+    }
+}
+
+diesel::table! {
+    badge_definitions (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        badge_key -> Text,
+        name -> Text,
+        description -> Nullable<Text>,
+        rule_type -> Text,
+        threshold -> Integer,
+        chore_id -> Nullable<Integer>,
+        active -> Bool,
+        position -> Integer,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    bonus_chore_claims (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        chore_id -> Integer,
+        user_id -> Integer,
+        status -> Text,
+        claimed_at -> Timestamp,
+        expires_at -> Timestamp,
+        completion_id -> Nullable<Integer>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    calendar_feeds (id) {
+        id -> Nullable<Integer>,
+        user_id -> Integer,
+        token -> Text,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    challenge_participants (challenge_id, user_id) {
+        challenge_id -> Integer,
+        user_id -> Integer,
+    }
+}
+
+diesel::table! {
+    challenges (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        name -> Text,
+        description -> Nullable<Text>,
+        prize -> Nullable<Text>,
+        metric -> Text,
+        target -> Integer,
+        start_date -> Date,
+        end_date -> Date,
+        chore_id -> Nullable<Integer>,
+        completed_at -> Nullable<Timestamp>,
+        created_by_admin_id -> Nullable<Integer>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
     }
 }
 
@@ -31,6 +101,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chore_checklist_items (id) {
+        id -> Nullable<Integer>,
+        chore_id -> Integer,
+        position -> Integer,
+        item_text -> Text,
+    }
+}
+
 diesel::table! {
     chore_completion_notes (id) {
         id -> Nullable<Integer>,
@@ -61,6 +140,23 @@ diesel::table! {
         paid_out_at -> Nullable<Timestamp>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        points -> Integer,
+    }
+}
+
+diesel::table! {
+    chore_templates (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        name -> Text,
+        description -> Nullable<Text>,
+        payment_type -> Text,
+        amount_cents -> Integer,
+        required_days -> Integer,
+        checklist -> Text,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+        points -> Integer,
     }
 }
 
@@ -79,6 +175,208 @@ diesel::table! {
         updated_at -> Nullable<Timestamp>,
         bonus_date -> Nullable<Date>,
         max_claims -> Nullable<Integer>,
+        points -> Integer,
+        activated_at -> Nullable<Timestamp>,
+        deactivated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    household_calendar_days (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        date -> Date,
+        name -> Text,
+        kind -> Text,
+        alternate_weekday -> Nullable<Integer>,
+        source -> Text,
+        ics_uid -> Nullable<Text>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    interest_credits (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        user_id -> Integer,
+        period_start -> Date,
+        period_end -> Date,
+        balance_cents -> Integer,
+        rate_bps -> Integer,
+        rounding -> Text,
+        amount_cents -> Integer,
+        jar_entry_id -> Nullable<Integer>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    interest_rates (id) {
+        id -> Nullable<Integer>,
+        user_id -> Nullable<Integer>,
+        rate_bps -> Integer,
+        period -> Text,
+        rounding -> Text,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    jar_entries (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        user_id -> Integer,
+        jar -> Text,
+        amount_cents -> Integer,
+        kind -> Text,
+        completion_id -> Nullable<Integer>,
+        paid_out_at -> Nullable<Timestamp>,
+        note -> Nullable<Text>,
+        created_by_admin_id -> Nullable<Integer>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    jar_splits (user_id) {
+        user_id -> Integer,
+        spend_percent -> Integer,
+        save_percent -> Integer,
+        give_percent -> Integer,
+        apply_on -> Text,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    level_thresholds (id) {
+        id -> Nullable<Integer>,
+        user_id -> Nullable<Integer>,
+        level -> Integer,
+        xp -> Integer,
+    }
+}
+
+diesel::table! {
+    pause_periods (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        user_id -> Integer,
+        chore_id -> Nullable<Integer>,
+        start_date -> Date,
+        end_date -> Date,
+        reason -> Nullable<Text>,
+        created_by_admin_id -> Nullable<Integer>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    push_targets (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        provider -> Text,
+        server_url -> Text,
+        topic -> Nullable<Text>,
+        token -> Nullable<Text>,
+        admin_id -> Nullable<Integer>,
+        user_id -> Nullable<Integer>,
+        active -> Bool,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    reward_redemptions (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        reward_id -> Nullable<Integer>,
+        user_id -> Integer,
+        reward_name -> Text,
+        currency -> Text,
+        cost -> Integer,
+        status -> Text,
+        requested_at -> Nullable<Timestamp>,
+        decided_at -> Nullable<Timestamp>,
+        decided_by_admin_id -> Nullable<Integer>,
+        decision_note -> Nullable<Text>,
+        paid_out_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    reward_settlements (id) {
+        id -> Nullable<Integer>,
+        redemption_id -> Integer,
+        user_id -> Integer,
+        amount_cents -> Integer,
+        paid_out_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    rewards (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        name -> Text,
+        description -> Nullable<Text>,
+        currency -> Text,
+        cost -> Integer,
+        active -> Bool,
+        position -> Integer,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    savings_goal_allocations (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        goal_id -> Integer,
+        amount_cents -> Integer,
+        note -> Nullable<Text>,
+        created_by_admin_id -> Nullable<Integer>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    savings_goals (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        user_id -> Integer,
+        name -> Text,
+        target_cents -> Integer,
+        target_date -> Nullable<Date>,
+        image_id -> Nullable<Integer>,
+        progress_source -> Text,
+        reached_at -> Nullable<Timestamp>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    scheduled_job_runs (name) {
+        name -> Text,
+        last_run_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    streak_days (id) {
+        id -> Nullable<Integer>,
+        user_id -> Integer,
+        date -> Date,
+        outcome -> Text,
+        streak -> Integer,
+        longest -> Integer,
+        freezes -> Integer,
     }
 }
 
@@ -100,6 +398,19 @@ diesel::table! {
         file_size -> Integer,
         created_at -> Timestamp,
         uuid -> Nullable<Text>,
+        kind -> Text,
+    }
+}
+
+diesel::table! {
+    user_streaks (user_id) {
+        user_id -> Integer,
+        current_streak -> Integer,
+        longest_streak -> Integer,
+        last_qualifying_date -> Nullable<Date>,
+        freezes_available -> Integer,
+        evaluated_through -> Nullable<Date>,
+        updated_at -> Nullable<Timestamp>,
     }
 }
 
@@ -115,9 +426,77 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    vapid_keys (id) {
+        id -> Integer,
+        private_key -> Text,
+        public_key -> Text,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    web_push_subscriptions (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        endpoint -> Text,
+        p256dh -> Text,
+        auth -> Text,
+        admin_id -> Nullable<Integer>,
+        user_id -> Nullable<Integer>,
+        user_agent -> Nullable<Text>,
+        created_at -> Nullable<Timestamp>,
+        last_success_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        endpoint_id -> Integer,
+        event_id -> Text,
+        event_type -> Text,
+        payload -> Text,
+        status -> Text,
+        attempts -> Integer,
+        next_attempt_at -> Nullable<Timestamp>,
+        last_attempt_at -> Nullable<Timestamp>,
+        response_status -> Nullable<Integer>,
+        last_error -> Nullable<Text>,
+        created_at -> Nullable<Timestamp>,
+        delivered_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_endpoints (id) {
+        id -> Nullable<Integer>,
+        uuid -> Text,
+        url -> Text,
+        description -> Nullable<Text>,
+        secret -> Text,
+        event_types -> Text,
+        active -> Bool,
+        created_by_admin_id -> Nullable<Integer>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::joinable!(admin_sessions -> admins (admin_id));
+diesel::joinable!(badge_definitions -> chores (chore_id));
+diesel::joinable!(bonus_chore_claims -> chore_completions (completion_id));
+diesel::joinable!(bonus_chore_claims -> chores (chore_id));
+diesel::joinable!(bonus_chore_claims -> users (user_id));
+diesel::joinable!(calendar_feeds -> users (user_id));
+diesel::joinable!(challenge_participants -> challenges (challenge_id));
+diesel::joinable!(challenge_participants -> users (user_id));
+diesel::joinable!(challenges -> admins (created_by_admin_id));
+diesel::joinable!(challenges -> chores (chore_id));
 diesel::joinable!(chore_assignments -> chores (chore_id));
 diesel::joinable!(chore_assignments -> users (user_id));
+diesel::joinable!(chore_checklist_items -> chores (chore_id));
 diesel::joinable!(chore_completion_notes -> admins (author_admin_id));
 diesel::joinable!(chore_completion_notes -> chore_completions (chore_completion_id));
 diesel::joinable!(chore_completion_notes -> users (author_user_id));
@@ -125,16 +504,70 @@ diesel::joinable!(chore_completions -> admins (approved_by_admin_id));
 diesel::joinable!(chore_completions -> chores (chore_id));
 diesel::joinable!(chore_completions -> users (user_id));
 diesel::joinable!(chores -> admins (created_by_admin_id));
+diesel::joinable!(interest_credits -> jar_entries (jar_entry_id));
+diesel::joinable!(interest_credits -> users (user_id));
+diesel::joinable!(interest_rates -> users (user_id));
+diesel::joinable!(jar_entries -> admins (created_by_admin_id));
+diesel::joinable!(jar_entries -> chore_completions (completion_id));
+diesel::joinable!(jar_entries -> users (user_id));
+diesel::joinable!(jar_splits -> users (user_id));
+diesel::joinable!(level_thresholds -> users (user_id));
+diesel::joinable!(pause_periods -> admins (created_by_admin_id));
+diesel::joinable!(pause_periods -> chores (chore_id));
+diesel::joinable!(pause_periods -> users (user_id));
+diesel::joinable!(push_targets -> admins (admin_id));
+diesel::joinable!(push_targets -> users (user_id));
+diesel::joinable!(reward_redemptions -> admins (decided_by_admin_id));
+diesel::joinable!(reward_redemptions -> rewards (reward_id));
+diesel::joinable!(reward_redemptions -> users (user_id));
+diesel::joinable!(reward_settlements -> reward_redemptions (redemption_id));
+diesel::joinable!(reward_settlements -> users (user_id));
+diesel::joinable!(savings_goal_allocations -> admins (created_by_admin_id));
+diesel::joinable!(savings_goal_allocations -> savings_goals (goal_id));
+diesel::joinable!(savings_goals -> user_images (image_id));
+diesel::joinable!(savings_goals -> users (user_id));
+diesel::joinable!(streak_days -> users (user_id));
 diesel::joinable!(user_badges -> users (user_id));
+diesel::joinable!(web_push_subscriptions -> admins (admin_id));
+diesel::joinable!(web_push_subscriptions -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhook_endpoints (endpoint_id));
+diesel::joinable!(webhook_endpoints -> admins (created_by_admin_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     admin_sessions,
     admins,
+    badge_definitions,
+    bonus_chore_claims,
+    calendar_feeds,
+    challenge_participants,
+    challenges,
     chore_assignments,
+    chore_checklist_items,
     chore_completion_notes,
     chore_completions,
+    chore_templates,
     chores,
+    household_calendar_days,
+    interest_credits,
+    interest_rates,
+    jar_entries,
+    jar_splits,
+    level_thresholds,
+    pause_periods,
+    push_targets,
+    reward_redemptions,
+    reward_settlements,
+    rewards,
+    savings_goal_allocations,
+    savings_goals,
+    scheduled_job_runs,
+    streak_days,
     user_badges,
     user_images,
+    user_streaks,
     users,
+    vapid_keys,
+    web_push_subscriptions,
+    webhook_deliveries,
+    webhook_endpoints,
 );
@@ -128,6 +128,7 @@ impl AdminSvc {
             Some(session) => {
                 let admin: Option<Admin> = admins::table
                     .filter(admins::id.eq(session.admin_id))
+                    .select(Admin::as_select())
                     .first(&mut conn)
                     .optional()
                     .context("fetching admin for session")?;
@@ -0,0 +1,327 @@
+use crate::{context::GraphQLContext, db::get_conn};
+use anyhow::{Context, Result, bail};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use diesel::{
+    prelude::*,
+    sql_types::{Date, Integer, Nullable},
+};
+use juniper::{GraphQLEnum, GraphQLObject};
+
+/// Period that earnings are grouped into. Weeks start on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum EarningsBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl EarningsBucket {
+    /// SQLite expression for the first day of the bucket containing `completed_date`.
+    const fn sql(self) -> &'static str {
+        match self {
+            Self::Day => "date(completed_date)",
+            // 'weekday 0' moves forward to Sunday (or stays), so six days back is Monday
+            Self::Week => "date(completed_date, 'weekday 0', '-6 days')",
+            Self::Month => "date(completed_date, 'start of month')",
+        }
+    }
+
+    /// First day of the bucket containing `date`; mirrors `sql`.
+    pub fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Duration::days(date.weekday().num_days_from_monday().into()),
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    fn next(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => start + Duration::days(1),
+            Self::Week => start + Duration::days(7),
+            Self::Month => start + Months::new(1),
+        }
+    }
+
+    /// Number of buckets touched by `from..=to`, partial ones included.
+    pub fn count_between(self, from: NaiveDate, to: NaiveDate) -> i32 {
+        let mut count = 0;
+        let mut start = self.start_of(from);
+        while start <= to {
+            count += 1;
+            start = self.next(start);
+        }
+        count
+    }
+}
+
+/// Approved earnings of one user (and chore, when grouped by chore) in one bucket.
+#[derive(Debug, Clone, PartialEq, Eq, QueryableByName, GraphQLObject)]
+pub struct EarningsPoint {
+    #[diesel(sql_type = Integer)]
+    pub user_id: i32,
+    #[diesel(sql_type = Nullable<Integer>)]
+    pub chore_id: Option<i32>,
+    #[diesel(sql_type = Date)]
+    pub period_start: NaiveDate,
+    #[diesel(sql_type = Integer)]
+    pub total_cents: i32,
+    #[diesel(sql_type = Integer)]
+    pub completion_count: i32,
+}
+
+/// The buckets of one user, or of one user and chore.
+#[derive(Debug, Clone, PartialEq, GraphQLObject)]
+pub struct EarningsSeries {
+    pub user_id: i32,
+    /// Set when the analytics were grouped by chore.
+    pub chore_id: Option<i32>,
+    /// Buckets with at least one approved completion, oldest first.
+    pub points: Vec<EarningsPoint>,
+    pub total_cents: i32,
+    pub completion_count: i32,
+    /// `total_cents` spread over every bucket in the range, empty ones included.
+    pub average_cents_per_period: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, GraphQLObject)]
+pub struct EarningsAnalytics {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub bucket: EarningsBucket,
+    /// Buckets touched by the range, partial ones at either end included.
+    pub period_count: i32,
+    pub series: Vec<EarningsSeries>,
+    pub total_cents: i32,
+    pub average_cents_per_period: f64,
+}
+
+pub struct AnalyticsSvc {}
+
+impl AnalyticsSvc {
+    /// Approved earnings in `from..=to` bucketed by `bucket`, one series per user, or per
+    /// user and chore when `by_chore` is set. Only the grouping into buckets touches the
+    /// completion rows; it runs as one aggregate query.
+    pub fn earnings(
+        context: &GraphQLContext,
+        from: NaiveDate,
+        to: NaiveDate,
+        bucket: EarningsBucket,
+        user_id: Option<i32>,
+        chore_id: Option<i32>,
+        by_chore: bool,
+    ) -> Result<EarningsAnalytics> {
+        if to < from {
+            bail!("The end of the range must not be before its start");
+        }
+
+        let chore_column = if by_chore { "chore_id" } else { "NULL" };
+        let query = format!(
+            "SELECT user_id, {chore_column} AS chore_id, {bucket} AS period_start, \
+                    CAST(SUM(amount_cents) AS INTEGER) AS total_cents, \
+                    COUNT(*) AS completion_count \
+             FROM chore_completions \
+             WHERE approved = 1 AND completed_date BETWEEN ? AND ? \
+               AND (? IS NULL OR user_id = ?) AND (? IS NULL OR chore_id = ?) \
+             GROUP BY user_id, {chore_column}, period_start \
+             ORDER BY user_id, {chore_column}, period_start",
+            bucket = bucket.sql(),
+        );
+        let points: Vec<EarningsPoint> = diesel::sql_query(query)
+            .bind::<Date, _>(from)
+            .bind::<Date, _>(to)
+            .bind::<Nullable<Integer>, _>(user_id)
+            .bind::<Nullable<Integer>, _>(user_id)
+            .bind::<Nullable<Integer>, _>(chore_id)
+            .bind::<Nullable<Integer>, _>(chore_id)
+            .load(&mut get_conn(context)?)
+            .context("Could not load earnings")?;
+
+        let period_count = bucket.count_between(from, to);
+        let average = |total: i64| total as f64 / f64::from(period_count);
+
+        let mut series: Vec<EarningsSeries> = Vec::new();
+        for point in points {
+            let same_series = series
+                .last()
+                .is_some_and(|s| s.user_id == point.user_id && s.chore_id == point.chore_id);
+            if !same_series {
+                series.push(EarningsSeries {
+                    user_id: point.user_id,
+                    chore_id: point.chore_id,
+                    points: Vec::new(),
+                    total_cents: 0,
+                    completion_count: 0,
+                    average_cents_per_period: 0.0,
+                });
+            }
+            if let Some(current) = series.last_mut() {
+                current.total_cents = current.total_cents.saturating_add(point.total_cents);
+                current.completion_count += point.completion_count;
+                current.points.push(point);
+            }
+        }
+        for s in &mut series {
+            s.average_cents_per_period = average(s.total_cents.into());
+        }
+
+        let total: i64 = series.iter().map(|s| i64::from(s.total_cents)).sum();
+        Ok(EarningsAnalytics {
+            from,
+            to,
+            bucket,
+            period_count,
+            series,
+            total_cents: i32::try_from(total).unwrap_or(i32::MAX),
+            average_cents_per_period: average(total),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, PaymentType},
+        svc::ChoreCompletionSvc,
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    #[test]
+    fn test_bucket_starts_and_counts() {
+        let sunday = create_test_date(2026, 4, 12);
+        assert_eq!(
+            EarningsBucket::Week.start_of(sunday),
+            create_test_date(2026, 4, 6)
+        );
+        assert_eq!(
+            EarningsBucket::Month.start_of(sunday),
+            create_test_date(2026, 4, 1)
+        );
+        assert_eq!(
+            EarningsBucket::Week.count_between(create_test_date(2026, 4, 1), sunday),
+            2
+        );
+        assert_eq!(
+            EarningsBucket::Month.count_between(create_test_date(2026, 1, 31), sunday),
+            4
+        );
+    }
+
+    #[test]
+    fn test_earnings_bucketed_in_sql() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let alice = create_test_user(&context, "Alice").id.unwrap();
+        let bob = create_test_user(&context, "Bob").id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        let trash = create_test_chore(
+            &context,
+            "Trash",
+            PaymentType::Daily,
+            250,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+
+        // Week of April 6: Sunday the 12th belongs to it, Monday the 13th does not
+        create_approved_test_completion(
+            &context,
+            dishes,
+            alice,
+            create_test_date(2026, 4, 6),
+            admin_id,
+        );
+        create_approved_test_completion(
+            &context,
+            trash,
+            alice,
+            create_test_date(2026, 4, 12),
+            admin_id,
+        );
+        create_approved_test_completion(
+            &context,
+            dishes,
+            alice,
+            create_test_date(2026, 4, 13),
+            admin_id,
+        );
+        create_approved_test_completion(
+            &context,
+            dishes,
+            bob,
+            create_test_date(2026, 4, 7),
+            admin_id,
+        );
+        // Pending completions don't count
+        ChoreCompletionSvc::create(
+            &context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id: trash,
+                user_id: bob,
+                completed_date: create_test_date(2026, 4, 8),
+            },
+        )
+        .unwrap();
+
+        let from = create_test_date(2026, 4, 6);
+        let to = create_test_date(2026, 4, 19);
+        let weekly =
+            AnalyticsSvc::earnings(&context, from, to, EarningsBucket::Week, None, None, false)
+                .unwrap();
+        assert_eq!(weekly.period_count, 2);
+        assert_eq!(weekly.total_cents, 550);
+        assert_eq!(weekly.series.len(), 2);
+        let alice_series = &weekly.series[0];
+        assert_eq!(alice_series.user_id, alice);
+        assert_eq!(
+            alice_series
+                .points
+                .iter()
+                .map(|p| (p.period_start, p.total_cents, p.completion_count))
+                .collect::<Vec<_>>(),
+            vec![
+                (create_test_date(2026, 4, 6), 350, 2),
+                (create_test_date(2026, 4, 13), 100, 1),
+            ]
+        );
+        assert_eq!(alice_series.total_cents, 450);
+        assert!((alice_series.average_cents_per_period - 225.0).abs() < f64::EPSILON);
+
+        let by_chore = AnalyticsSvc::earnings(
+            &context,
+            from,
+            to,
+            EarningsBucket::Month,
+            Some(alice),
+            None,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            by_chore
+                .series
+                .iter()
+                .map(|s| (s.chore_id, s.total_cents))
+                .collect::<Vec<_>>(),
+            vec![(Some(dishes), 200), (Some(trash), 250)]
+        );
+    }
+}
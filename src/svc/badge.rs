@@ -1,101 +1,346 @@
-use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use anyhow::{Context, Result, bail};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use diesel::prelude::*;
+use juniper::GraphQLObject;
+use std::collections::HashMap;
 
 use crate::context::GraphQLContext;
 use crate::db::get_conn;
-use crate::models::BadgeType;
+use crate::models::{BadgeDefinition, BadgeRuleType};
+use crate::schema::badge_definitions;
+use crate::svc::{NotificationSvc, ScheduleSvc, StreakSvc, notification::NotificationEvent};
 
 pub struct BadgeSvc;
 
+/// How far a user is towards one badge, e.g. 725 of 1000 cents or 3 of 5 days.
+#[derive(Debug, Clone, PartialEq, GraphQLObject)]
+pub struct BadgeProgress {
+    pub badge_key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub rule_type: BadgeRuleType,
+    pub chore_id: Option<i32>,
+    /// The rule's measure so far: cents for earnings, days for streaks (the longest
+    /// one), weeks for perfect weeks and completions otherwise. May exceed `target`.
+    pub current: i32,
+    pub target: i32,
+    /// `current / target`, capped at 1.
+    pub fraction: f64,
+    pub earned: bool,
+    pub earned_at: Option<NaiveDateTime>,
+}
+
+/// What a user has done so far, measured once per rule type while checking badges.
+struct Measures<'a> {
+    context: &'a GraphQLContext,
+    user_id: i32,
+    completions: HashMap<Option<i32>, i64>,
+    earnings: Option<i64>,
+    streak: Option<i64>,
+    perfect_weeks: Option<i64>,
+}
+
+impl<'a> Measures<'a> {
+    fn new(context: &'a GraphQLContext, user_id: i32) -> Self {
+        Self {
+            context,
+            user_id,
+            completions: HashMap::new(),
+            earnings: None,
+            streak: None,
+            perfect_weeks: None,
+        }
+    }
+
+    /// The user's current value for the rule of `definition`.
+    fn of(&mut self, definition: &BadgeDefinition) -> Result<i64> {
+        let (context, user_id) = (self.context, self.user_id);
+        Ok(match BadgeRuleType::from(&definition.rule_type) {
+            BadgeRuleType::CompletionCount => match self.completions.get(&definition.chore_id) {
+                Some(count) => *count,
+                None => {
+                    let count = BadgeSvc::completion_count(context, user_id, definition.chore_id)?;
+                    self.completions.insert(definition.chore_id, count);
+                    count
+                }
+            },
+            BadgeRuleType::EarningsTotal => cached(&mut self.earnings, || {
+                BadgeSvc::earnings_total(context, user_id)
+            })?,
+            BadgeRuleType::StreakDays => cached(&mut self.streak, || {
+                Ok(StreakSvc::get(context, user_id)?.longest_streak.into())
+            })?,
+            BadgeRuleType::PerfectWeeks => cached(&mut self.perfect_weeks, || {
+                BadgeSvc::perfect_week_count(context, user_id)
+            })?,
+        })
+    }
+}
+
+fn cached(slot: &mut Option<i64>, measure: impl FnOnce() -> Result<i64>) -> Result<i64> {
+    if let Some(value) = *slot {
+        return Ok(value);
+    }
+    let value = measure()?;
+    *slot = Some(value);
+    Ok(value)
+}
+
 impl BadgeSvc {
     /// Check all badge conditions for the given user and insert any newly earned badges.
     /// This function is non-fatal: errors are logged but do not propagate.
     pub fn check_and_award(context: &GraphQLContext, user_id: i32) {
-        for badge_type in BadgeType::all() {
-            let earned = match Self::is_earned(context, user_id, &badge_type) {
-                Ok(b) => b,
+        let definitions = match Self::list_definitions(context, false) {
+            Ok(definitions) => definitions,
+            Err(e) => {
+                tracing::warn!("Could not load badge definitions: {:?}", e);
+                return;
+            }
+        };
+
+        let mut measures = Measures::new(context, user_id);
+        for definition in definitions {
+            let earned = match measures.of(&definition) {
+                Ok(value) => value >= i64::from(definition.threshold),
                 Err(e) => {
-                    tracing::warn!("Badge check failed for {:?}: {:?}", badge_type, e);
+                    tracing::warn!("Badge check failed for {}: {:?}", definition.badge_key, e);
                     continue;
                 }
             };
             if earned {
-                let mut conn = match get_conn(context) {
-                    Ok(c) => c,
+                let awarded = get_conn(context)
+                    .and_then(|mut conn| Self::award(&mut conn, user_id, &definition.badge_key));
+                match awarded {
+                    Ok(true) => NotificationSvc::notify(
+                        context,
+                        &NotificationEvent::BadgeEarned {
+                            user_id,
+                            badge_type: definition.badge_key,
+                            badge_name: definition.name,
+                        },
+                    ),
+                    Ok(false) => {}
                     Err(e) => {
-                        tracing::warn!("Badge award failed for {:?}: {:?}", badge_type, e);
-                        continue;
+                        tracing::warn!("Badge award failed for {}: {:?}", definition.badge_key, e)
                     }
-                };
-                if let Err(e) = Self::award(&mut conn, user_id, &badge_type) {
-                    tracing::warn!("Badge award failed for {:?}: {:?}", badge_type, e);
                 }
             }
         }
     }
 
-    fn is_earned(context: &GraphQLContext, user_id: i32, badge_type: &BadgeType) -> Result<bool> {
-        match badge_type {
-            BadgeType::FirstChore => Self::check_first_chore(context, user_id),
-            BadgeType::TenDollarsEarned => Self::check_earnings(context, user_id, 1000),
-            BadgeType::FiftyDollarsEarned => Self::check_earnings(context, user_id, 5000),
-            BadgeType::PerfectWeek => Self::check_perfect_week(context, user_id),
-            BadgeType::FiveDayStreak => Self::check_five_day_streak(context, user_id),
+    /// Progress towards every active badge, in display order. Badges that were earned
+    /// stay earned even if the measure has since dropped below the threshold.
+    pub fn progress(context: &GraphQLContext, user_id: i32) -> Result<Vec<BadgeProgress>> {
+        use crate::schema::user_badges;
+        let earned: HashMap<String, NaiveDateTime> = user_badges::table
+            .filter(user_badges::user_id.eq(user_id))
+            .select((user_badges::badge_type, user_badges::earned_at))
+            .load::<(String, NaiveDateTime)>(&mut get_conn(context)?)
+            .context("Could not load earned badges")?
+            .into_iter()
+            .collect();
+
+        let mut measures = Measures::new(context, user_id);
+        Self::list_definitions(context, false)?
+            .into_iter()
+            .map(|definition| {
+                let current = measures.of(&definition)?;
+                let target = definition.threshold;
+                let earned_at = earned.get(&definition.badge_key).copied();
+                Ok(BadgeProgress {
+                    rule_type: BadgeRuleType::from(&definition.rule_type),
+                    chore_id: definition.chore_id,
+                    current: i32::try_from(current).unwrap_or(i32::MAX),
+                    target,
+                    fraction: (current as f64 / f64::from(target)).min(1.0),
+                    earned: earned_at.is_some(),
+                    earned_at,
+                    badge_key: definition.badge_key,
+                    name: definition.name,
+                    description: definition.description,
+                })
+            })
+            .collect()
+    }
+
+    pub fn get_definition(context: &GraphQLContext, uuid: &str) -> Result<BadgeDefinition> {
+        badge_definitions::table
+            .filter(badge_definitions::uuid.eq(uuid))
+            .select(BadgeDefinition::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find badge definition")
+    }
+
+    pub fn get_definition_by_key(
+        context: &GraphQLContext,
+        badge_key: &str,
+    ) -> Result<Option<BadgeDefinition>> {
+        badge_definitions::table
+            .filter(badge_definitions::badge_key.eq(badge_key))
+            .select(BadgeDefinition::as_select())
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not load badge definition")
+    }
+
+    /// Badge definitions in display order.
+    pub fn list_definitions(
+        context: &GraphQLContext,
+        include_inactive: bool,
+    ) -> Result<Vec<BadgeDefinition>> {
+        let mut query = badge_definitions::table.into_boxed();
+        if !include_inactive {
+            query = query.filter(badge_definitions::active.eq(true));
         }
+        query
+            .select(BadgeDefinition::as_select())
+            .order_by((
+                badge_definitions::position.asc(),
+                badge_definitions::id.asc(),
+            ))
+            .load(&mut get_conn(context)?)
+            .context("Could not load badge definitions")
     }
 
-    fn award(conn: &mut SqliteConnection, user_id: i32, badge_type: &BadgeType) -> Result<()> {
+    pub fn create_definition(
+        context: &GraphQLContext,
+        definition: &BadgeDefinition,
+    ) -> Result<BadgeDefinition> {
+        Self::validate(definition)?;
+        diesel::insert_into(badge_definitions::table)
+            .values(definition)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create badge definition")?;
+
+        Self::get_definition(context, &definition.uuid)
+    }
+
+    /// Updates everything but the key, which earned badges refer to.
+    pub fn update_definition(
+        context: &GraphQLContext,
+        definition: &BadgeDefinition,
+    ) -> Result<BadgeDefinition> {
+        Self::validate(definition)?;
+        let current = Self::get_definition(context, &definition.uuid)?;
+        if current.badge_key != definition.badge_key {
+            bail!("A badge's key cannot be changed");
+        }
+
+        diesel::update(badge_definitions::table)
+            .filter(badge_definitions::uuid.eq(&definition.uuid))
+            .set((
+                badge_definitions::name.eq(&definition.name),
+                badge_definitions::description.eq(&definition.description),
+                badge_definitions::rule_type.eq(&definition.rule_type),
+                badge_definitions::threshold.eq(definition.threshold),
+                badge_definitions::chore_id.eq(definition.chore_id),
+                badge_definitions::active.eq(definition.active),
+                badge_definitions::position.eq(definition.position),
+                badge_definitions::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update badge definition")?;
+
+        Self::get_definition(context, &definition.uuid)
+    }
+
+    /// Deletes a definition. Badges already earned under it are kept.
+    pub fn delete_definition(context: &GraphQLContext, uuid: &str) -> Result<()> {
+        diesel::delete(badge_definitions::table)
+            .filter(badge_definitions::uuid.eq(uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete badge definition")?;
+
+        Ok(())
+    }
+
+    fn validate(definition: &BadgeDefinition) -> Result<()> {
+        let key = &definition.badge_key;
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            bail!("Badge keys use lowercase letters, digits and underscores");
+        }
+        if definition.name.trim().is_empty() {
+            bail!("Badges need a name");
+        }
+        if definition.threshold < 1 {
+            bail!("Badge thresholds must be at least 1");
+        }
+        if definition.chore_id.is_some()
+            && BadgeRuleType::from(&definition.rule_type) != BadgeRuleType::CompletionCount
+        {
+            bail!("Only completion count badges can be limited to one chore");
+        }
+        Ok(())
+    }
+
+    /// Inserts the badge unless the user already has it. Returns whether it is new.
+    fn award(conn: &mut SqliteConnection, user_id: i32, badge_key: &str) -> Result<bool> {
         use crate::schema::user_badges;
         let now = chrono::Local::now().naive_local();
 
         // Use insert_or_ignore to handle the UNIQUE(user_id, badge_type) constraint idempotently
-        diesel::insert_or_ignore_into(user_badges::table)
+        let inserted = diesel::insert_or_ignore_into(user_badges::table)
             .values((
                 user_badges::user_id.eq(user_id),
-                user_badges::badge_type.eq(badge_type.as_str()),
+                user_badges::badge_type.eq(badge_key),
                 user_badges::earned_at.eq(now),
             ))
             .execute(conn)
             .context("Failed to insert badge")?;
-        Ok(())
+        Ok(inserted > 0)
     }
 
-    fn check_first_chore(context: &GraphQLContext, user_id: i32) -> Result<bool> {
+    /// Approved completions, of `chore_id` only when given.
+    fn completion_count(
+        context: &GraphQLContext,
+        user_id: i32,
+        chore_id: Option<i32>,
+    ) -> Result<i64> {
         use crate::schema::chore_completions;
-        let count: i64 = chore_completions::table
+        let mut query = chore_completions::table
             .filter(chore_completions::user_id.eq(user_id))
             .filter(chore_completions::approved.eq(true))
+            .into_boxed();
+        if let Some(chore_id) = chore_id {
+            query = query.filter(chore_completions::chore_id.eq(chore_id));
+        }
+        query
             .count()
             .get_result(&mut get_conn(context)?)
-            .context("check_first_chore")?;
-        Ok(count >= 1)
+            .context("completion_count")
     }
 
-    fn check_earnings(context: &GraphQLContext, user_id: i32, min_cents: i32) -> Result<bool> {
+    fn earnings_total(context: &GraphQLContext, user_id: i32) -> Result<i64> {
         use crate::schema::chore_completions;
         let total: Option<i64> = chore_completions::table
             .filter(chore_completions::user_id.eq(user_id))
             .filter(chore_completions::approved.eq(true))
             .select(diesel::dsl::sum(chore_completions::amount_cents))
             .first(&mut get_conn(context)?)
-            .context("check_earnings")?;
-        Ok(total.unwrap_or(0) >= min_cents as i64)
+            .context("earnings_total")?;
+        Ok(total.unwrap_or(0))
     }
 
-    fn check_perfect_week(context: &GraphQLContext, user_id: i32) -> Result<bool> {
+    /// Weeks in which every required chore was done.
+    fn perfect_week_count(context: &GraphQLContext, user_id: i32) -> Result<i64> {
         use crate::schema::chore_assignments;
         use crate::schema::chore_completions;
+        use crate::schema::chores;
 
-        // Get all chores assigned to this user
-        let assigned_chore_ids: Vec<i32> = chore_assignments::table
+        // Get all chores assigned to this user, with their schedules
+        let assigned_chores: Vec<(i32, i32)> = chore_assignments::table
+            .inner_join(chores::table)
             .filter(chore_assignments::user_id.eq(user_id))
-            .select(chore_assignments::chore_id)
-            .load::<i32>(&mut get_conn(context)?)
-            .context("check_perfect_week assignments")?;
+            .select((chore_assignments::chore_id, chores::required_days))
+            .load(&mut get_conn(context)?)
+            .context("perfect_week_count assignments")?;
 
-        if assigned_chore_ids.is_empty() {
-            return Ok(false);
+        if assigned_chores.is_empty() {
+            return Ok(0);
         }
 
         // Get all approved completions with chore_id and date for this user
@@ -104,15 +349,15 @@ impl BadgeSvc {
             .filter(chore_completions::approved.eq(true))
             .select((chore_completions::chore_id, chore_completions::completed_date))
             .load(&mut get_conn(context)?)
-            .context("check_perfect_week records")?;
+            .context("perfect_week_count records")?;
 
         if completion_records.is_empty() {
-            return Ok(false);
+            return Ok(0);
         }
 
         // Group completed chore_ids by ISO week
         use chrono::Datelike;
-        use std::collections::{HashMap, HashSet};
+        use std::collections::HashSet;
         let mut week_completions: HashMap<(i32, u32), HashSet<i32>> = HashMap::new();
         for (chore_id, date) in &completion_records {
             let iso_week = date.iso_week();
@@ -122,52 +367,38 @@ impl BadgeSvc {
                 .insert(*chore_id);
         }
 
-        let assigned_set: HashSet<i32> = assigned_chore_ids.into_iter().collect();
-        for completed_in_week in week_completions.values() {
-            if assigned_set.is_subset(completed_in_week) {
-                return Ok(true);
+        // Chores paused for the whole week are not required that week; a week with
+        // nothing left to do does not count as perfect.
+        let schedule = ScheduleSvc::user_schedule(context, user_id)?;
+        let mut perfect = 0;
+        for (&(year, week), completed_in_week) in &week_completions {
+            let Some(monday) = NaiveDate::from_isoywd_opt(year, week, chrono::Weekday::Mon) else {
+                continue;
+            };
+            let sunday = monday + chrono::Duration::days(6);
+            let required: HashSet<i32> = assigned_chores
+                .iter()
+                .filter(|&&(chore_id, required_days)| {
+                    !schedule.is_chore_excused_between(chore_id, required_days, monday, sunday)
+                })
+                .map(|&(chore_id, _)| chore_id)
+                .collect();
+            if !required.is_empty() && required.is_subset(completed_in_week) {
+                perfect += 1;
             }
         }
-        Ok(false)
+        Ok(perfect)
     }
 
-    fn check_five_day_streak(context: &GraphQLContext, user_id: i32) -> Result<bool> {
-        use crate::schema::chore_completions;
-
-        let mut dates: Vec<NaiveDate> = chore_completions::table
-            .filter(chore_completions::user_id.eq(user_id))
-            .filter(chore_completions::approved.eq(true))
-            .select(chore_completions::completed_date)
-            .load(&mut get_conn(context)?)
-            .context("check_five_day_streak")?;
-
-        if dates.is_empty() {
-            return Ok(false);
-        }
-
-        // Deduplicate and sort
-        dates.sort();
-        dates.dedup();
-
-        let mut streak = 1usize;
-        for i in 1..dates.len() {
-            let diff = (dates[i] - dates[i - 1]).num_days();
-            if diff == 1 {
-                streak += 1;
-                if streak >= 5 {
-                    return Ok(true);
-                }
-            } else {
-                streak = 1;
-            }
-        }
-        Ok(false)
+    #[cfg(test)]
+    fn check_perfect_week(context: &GraphQLContext, user_id: i32) -> Result<bool> {
+        Ok(Self::perfect_week_count(context, user_id)? >= 1)
     }
 
-    /// Public test wrapper for check_five_day_streak.
+    /// Public test wrapper for the five-day streak rule.
     #[cfg(test)]
     pub fn check_five_day_streak_pub(context: &GraphQLContext, user_id: i32) -> Result<bool> {
-        Self::check_five_day_streak(context, user_id)
+        Ok(StreakSvc::get(context, user_id)?.longest_streak >= 5)
     }
 }
 
@@ -425,4 +656,444 @@ mod tests {
             .count();
         assert_eq!(perfect_week_count, 1, "Should have perfect_week badge");
     }
+
+    fn create_pause(
+        context: &GraphQLContext,
+        user_id: i32,
+        chore_id: Option<i32>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) {
+        let pause = crate::models::PausePeriodInput {
+            uuid: None,
+            user_id,
+            chore_id,
+            start_date,
+            end_date,
+            reason: Some("Vacation".to_owned()),
+        };
+        crate::svc::PauseSvc::create(context, &pause.into()).unwrap();
+    }
+
+    #[test]
+    fn test_five_day_streak_skips_paused_days() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let chore = create_test_chore(
+            &context,
+            "Test Chore",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let chore_id = chore.id.unwrap();
+        let user_id = user.id.unwrap();
+        let admin_id = admin.id.unwrap();
+
+        // Days 1, 2, 3, then away on 4-6, then 7 and 8
+        for day in [1u32, 2, 3, 7, 8] {
+            let date = NaiveDate::from_ymd_opt(2026, 4, day).unwrap();
+            setup_approved_completion(&context, chore_id, user_id, admin_id, date);
+        }
+        assert!(!BadgeSvc::check_five_day_streak_pub(&context, user_id).unwrap());
+
+        create_pause(
+            &context,
+            user_id,
+            None,
+            NaiveDate::from_ymd_opt(2026, 4, 4).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 4, 6).unwrap(),
+        );
+        assert!(
+            BadgeSvc::check_five_day_streak_pub(&context, user_id).unwrap(),
+            "Paused days should not break the streak"
+        );
+    }
+
+    #[test]
+    fn test_five_day_streak_not_bridged_by_partial_pause() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let user_id = user.id.unwrap();
+        let admin_id = admin.id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        let trash = create_test_chore(
+            &context,
+            "Trash",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        ChoreSvc::assign_user(&context, trash.id.unwrap(), user_id).unwrap();
+
+        for day in [1u32, 2, 3, 5, 6] {
+            let date = NaiveDate::from_ymd_opt(2026, 4, day).unwrap();
+            setup_approved_completion(&context, dishes.id.unwrap(), user_id, admin_id, date);
+        }
+        // Only dishes is paused on the 4th; trash was still due
+        let fourth = NaiveDate::from_ymd_opt(2026, 4, 4).unwrap();
+        create_pause(&context, user_id, dishes.id, fourth, fourth);
+
+        assert!(!BadgeSvc::check_five_day_streak_pub(&context, user_id).unwrap());
+    }
+
+    #[test]
+    fn test_perfect_week_excludes_paused_chores() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let user_id = user.id.unwrap();
+        let admin_id = admin.id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::monday_only(),
+            admin_id,
+        );
+        let lawn = create_test_chore(
+            &context,
+            "Lawn",
+            PaymentType::Weekly,
+            500,
+            day_patterns::mon_wed_fri(),
+            admin_id,
+        );
+        ChoreSvc::assign_user(&context, lawn.id.unwrap(), user_id).unwrap();
+
+        // Week of Monday 2026-04-13: dishes done, lawn not
+        let monday = NaiveDate::from_ymd_opt(2026, 4, 13).unwrap();
+        setup_approved_completion(&context, dishes.id.unwrap(), user_id, admin_id, monday);
+        assert!(!BadgeSvc::check_perfect_week(&context, user_id).unwrap());
+
+        // Lawn paused on only some of its days is still required
+        create_pause(
+            &context,
+            user_id,
+            lawn.id,
+            monday,
+            NaiveDate::from_ymd_opt(2026, 4, 15).unwrap(),
+        );
+        assert!(!BadgeSvc::check_perfect_week(&context, user_id).unwrap());
+
+        // Once every scheduled lawn day is paused it no longer counts
+        create_pause(
+            &context,
+            user_id,
+            lawn.id,
+            NaiveDate::from_ymd_opt(2026, 4, 16).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 4, 19).unwrap(),
+        );
+        assert!(BadgeSvc::check_perfect_week(&context, user_id).unwrap());
+    }
+
+    #[test]
+    fn test_perfect_week_not_awarded_for_fully_paused_week() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let user_id = user.id.unwrap();
+        let admin_id = admin.id.unwrap();
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::monday_only(),
+            admin_id,
+        );
+        ChoreSvc::assign_user(&context, chore.id.unwrap(), user_id).unwrap();
+
+        // A completion logged during a week the user was away entirely
+        let monday = NaiveDate::from_ymd_opt(2026, 4, 13).unwrap();
+        create_pause(
+            &context,
+            user_id,
+            None,
+            monday,
+            NaiveDate::from_ymd_opt(2026, 4, 19).unwrap(),
+        );
+        setup_approved_completion(&context, chore.id.unwrap(), user_id, admin_id, monday);
+
+        assert!(!BadgeSvc::check_perfect_week(&context, user_id).unwrap());
+    }
+
+    #[test]
+    fn test_five_day_streak_skips_household_skip_days() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let chore = create_test_chore(
+            &context,
+            "Test Chore",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let chore_id = chore.id.unwrap();
+        let user_id = user.id.unwrap();
+        let admin_id = admin.id.unwrap();
+
+        for day in [1u32, 2, 3, 5, 6] {
+            let date = NaiveDate::from_ymd_opt(2026, 4, day).unwrap();
+            setup_approved_completion(&context, chore_id, user_id, admin_id, date);
+        }
+        assert!(!BadgeSvc::check_five_day_streak_pub(&context, user_id).unwrap());
+
+        let holiday = crate::models::CalendarDayInput {
+            uuid: None,
+            date: NaiveDate::from_ymd_opt(2026, 4, 4).unwrap(),
+            name: "Family Day".to_owned(),
+            kind: crate::models::CalendarDayKind::Skip,
+            alternate_weekday: None,
+        };
+        crate::svc::CalendarSvc::upsert(&context, &holiday.into()).unwrap();
+        assert!(BadgeSvc::check_five_day_streak_pub(&context, user_id).unwrap());
+    }
+
+    fn definition(badge_key: &str, rule_type: BadgeRuleType, threshold: i32) -> BadgeDefinition {
+        crate::models::BadgeDefinitionInput {
+            uuid: None,
+            badge_key: badge_key.to_owned(),
+            name: badge_key.replace('_', " "),
+            description: None,
+            rule_type,
+            threshold,
+            chore_id: None,
+            active: None,
+            position: Some(10),
+        }
+        .into()
+    }
+
+    fn earned_keys(context: &GraphQLContext, user_id: i32) -> Vec<String> {
+        use crate::schema::user_badges;
+        let mut keys: Vec<String> = user_badges::table
+            .filter(user_badges::user_id.eq(user_id))
+            .select(user_badges::badge_type)
+            .load(&mut get_conn(context).unwrap())
+            .unwrap();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_existing_badges_are_seeded_as_definitions() {
+        let context = create_test_context();
+        let definitions = BadgeSvc::list_definitions(&context, false).unwrap();
+        assert_eq!(
+            definitions
+                .iter()
+                .map(|d| (d.badge_key.as_str(), d.threshold))
+                .collect::<Vec<_>>(),
+            vec![
+                ("first_chore", 1),
+                ("ten_dollars_earned", 1000),
+                ("fifty_dollars_earned", 5000),
+                ("perfect_week", 1),
+                ("five_day_streak", 5),
+            ]
+        );
+        assert_eq!(
+            BadgeRuleType::from(&definitions[4].rule_type),
+            BadgeRuleType::StreakDays
+        );
+    }
+
+    #[test]
+    fn test_custom_definitions_are_evaluated() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Test Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Test User").id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        let trash = create_test_chore(
+            &context,
+            "Trash",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+
+        let mut dish_master = definition("dish_master", BadgeRuleType::CompletionCount, 2);
+        dish_master.chore_id = Some(dishes);
+        BadgeSvc::create_definition(&context, &dish_master).unwrap();
+        BadgeSvc::create_definition(
+            &context,
+            &definition("three_dollars", BadgeRuleType::EarningsTotal, 300),
+        )
+        .unwrap();
+        // Inactive definitions are never awarded
+        let mut retired = definition("retired", BadgeRuleType::CompletionCount, 1);
+        retired.active = false;
+        BadgeSvc::create_definition(&context, &retired).unwrap();
+
+        setup_approved_completion(
+            &context,
+            dishes,
+            user_id,
+            admin_id,
+            NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+        );
+        setup_approved_completion(
+            &context,
+            trash,
+            user_id,
+            admin_id,
+            NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+        );
+        assert_eq!(
+            earned_keys(&context, user_id),
+            vec!["first_chore", "perfect_week"]
+        );
+
+        setup_approved_completion(
+            &context,
+            dishes,
+            user_id,
+            admin_id,
+            NaiveDate::from_ymd_opt(2026, 4, 2).unwrap(),
+        );
+        assert_eq!(
+            earned_keys(&context, user_id),
+            vec![
+                "dish_master",
+                "first_chore",
+                "perfect_week",
+                "three_dollars"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_definition_validation_and_fixed_keys() {
+        let context = create_test_context();
+        assert!(
+            BadgeSvc::create_definition(
+                &context,
+                &definition("Big Spender", BadgeRuleType::EarningsTotal, 100)
+            )
+            .is_err()
+        );
+        assert!(
+            BadgeSvc::create_definition(
+                &context,
+                &definition("zero", BadgeRuleType::StreakDays, 0)
+            )
+            .is_err()
+        );
+        let mut chore_streak = definition("chore_streak", BadgeRuleType::StreakDays, 3);
+        chore_streak.chore_id = Some(1);
+        assert!(BadgeSvc::create_definition(&context, &chore_streak).is_err());
+
+        let mut ten_weeks = BadgeSvc::create_definition(
+            &context,
+            &definition("ten_perfect_weeks", BadgeRuleType::PerfectWeeks, 10),
+        )
+        .unwrap();
+        ten_weeks.threshold = 8;
+        let updated = BadgeSvc::update_definition(&context, &ten_weeks).unwrap();
+        assert_eq!(updated.threshold, 8);
+
+        ten_weeks.badge_key = "eight_perfect_weeks".to_owned();
+        assert!(BadgeSvc::update_definition(&context, &ten_weeks).is_err());
+
+        BadgeSvc::delete_definition(&context, &ten_weeks.uuid).unwrap();
+        assert!(
+            BadgeSvc::get_definition_by_key(&context, "ten_perfect_weeks")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_progress_reports_current_and_target() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Test Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Test User").id.unwrap();
+        let chore_id = create_test_chore(
+            &context,
+            "Test Chore",
+            PaymentType::Daily,
+            725,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+
+        let before = BadgeSvc::progress(&context, user_id).unwrap();
+        assert_eq!(before.len(), 5);
+        assert!(before.iter().all(|p| p.current == 0 && !p.earned));
+
+        for day in [1u32, 2, 3] {
+            setup_approved_completion(
+                &context,
+                chore_id,
+                user_id,
+                admin_id,
+                NaiveDate::from_ymd_opt(2026, 4, day).unwrap(),
+            );
+        }
+        // A pending completion doesn't count yet
+        ChoreCompletionSvc::create(
+            &context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id,
+                user_id,
+                completed_date: NaiveDate::from_ymd_opt(2026, 4, 4).unwrap(),
+            },
+        )
+        .unwrap();
+
+        let progress = BadgeSvc::progress(&context, user_id).unwrap();
+        let of = |key: &str| progress.iter().find(|p| p.badge_key == key).unwrap();
+
+        let first = of("first_chore");
+        assert_eq!((first.current, first.target), (3, 1));
+        assert!(first.earned && first.earned_at.is_some());
+        assert!((first.fraction - 1.0).abs() < f64::EPSILON);
+
+        let ten = of("ten_dollars_earned");
+        assert_eq!((ten.current, ten.target), (2175, 1000));
+        assert!(ten.earned);
+        let fifty = of("fifty_dollars_earned");
+        assert_eq!((fifty.current, fifty.target), (2175, 5000));
+        assert!(!fifty.earned);
+        assert!((fifty.fraction - 0.435).abs() < 1e-9);
+
+        let streak = of("five_day_streak");
+        assert_eq!((streak.current, streak.target), (3, 5));
+        assert_eq!(streak.rule_type, BadgeRuleType::StreakDays);
+        assert!(!streak.earned);
+    }
 }
@@ -0,0 +1,465 @@
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    get_env_typed,
+    models::{BonusChoreClaim, Chore, ClaimStatus},
+    schema::{bonus_chore_claims, chore_completions},
+    svc::{ChoreSvc, NotificationSvc, notification::NotificationEvent},
+};
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use diesel::prelude::*;
+use std::fmt;
+
+/// How long a claim reserves its slot when `BONUS_CLAIM_TTL_MINUTES` is not set.
+const DEFAULT_CLAIM_TTL_MINUTES: i64 = 120;
+
+/// Expected rejections when claiming a bonus chore, surfaced to GraphQL clients with a
+/// stable `code` extension like `CompletionError`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClaimError {
+    LimitReached,
+}
+
+impl ClaimError {
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::LimitReached => "CLAIM_LIMIT_REACHED",
+        }
+    }
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LimitReached => {
+                write!(f, "This bonus chore has already reached its claim limit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClaimError {}
+
+pub struct BonusClaimSvc {}
+
+impl BonusClaimSvc {
+    fn claim_ttl() -> chrono::Duration {
+        chrono::Duration::minutes(get_env_typed::<i64>(
+            "BONUS_CLAIM_TTL_MINUTES",
+            DEFAULT_CLAIM_TTL_MINUTES,
+        ))
+    }
+
+    /// Statuses that occupy one of a bonus chore's `max_claims` slots.
+    fn held_statuses() -> [String; 2] {
+        [ClaimStatus::Active.into(), ClaimStatus::Fulfilled.into()]
+    }
+
+    pub fn get(context: &GraphQLContext, claim_uuid: &str) -> Result<BonusChoreClaim> {
+        bonus_chore_claims::table
+            .filter(bonus_chore_claims::uuid.eq(claim_uuid))
+            .select(BonusChoreClaim::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find bonus chore claim")
+    }
+
+    /// Lists claims, newest first. Expired reservations are released before loading so the
+    /// returned statuses are current; pass `held_only` to skip released and expired claims.
+    pub fn list(
+        context: &GraphQLContext,
+        chore_id: Option<i32>,
+        user_id: Option<i32>,
+        held_only: bool,
+    ) -> Result<Vec<BonusChoreClaim>> {
+        let mut conn = get_conn(context)?;
+        Self::release_expired(&mut conn)?;
+
+        let mut query = bonus_chore_claims::table.into_boxed();
+
+        if let Some(chore_id) = chore_id {
+            query = query.filter(bonus_chore_claims::chore_id.eq(chore_id));
+        }
+
+        if let Some(user_id) = user_id {
+            query = query.filter(bonus_chore_claims::user_id.eq(user_id));
+        }
+
+        if held_only {
+            query = query.filter(bonus_chore_claims::status.eq_any(Self::held_statuses()));
+        }
+
+        query
+            .select(BonusChoreClaim::as_select())
+            .order_by(bonus_chore_claims::claimed_at.desc())
+            .load::<BonusChoreClaim>(&mut conn)
+            .context("Could not load bonus chore claims")
+    }
+
+    /// Number of slots currently taken on a bonus chore (active or fulfilled claims).
+    pub fn held_count(context: &GraphQLContext, chore_id: i32) -> Result<i32> {
+        let mut conn = get_conn(context)?;
+        Self::release_expired(&mut conn)?;
+        let count = Self::count_held(&mut conn, chore_id)?;
+        Ok(i32::try_from(count).unwrap_or(i32::MAX))
+    }
+
+    /// Reserves a slot on a bonus chore for a user. Claiming again while an active claim is
+    /// held returns the existing claim rather than taking a second slot.
+    pub fn claim(context: &GraphQLContext, chore_id: i32, user_id: i32) -> Result<BonusChoreClaim> {
+        let chore = ChoreSvc::get_by_id(context, chore_id)?;
+        if chore.bonus_date.is_none() {
+            return Err(anyhow!("Only bonus chores can be claimed"));
+        }
+        if !chore.active {
+            return Err(anyhow!("This bonus chore is no longer active"));
+        }
+
+        let (claim, newly_claimed) = Self::acquire(context, &chore, user_id)?;
+        if newly_claimed && let Some(claim_id) = claim.id {
+            NotificationSvc::notify(context, &NotificationEvent::BonusChoreClaimed { claim_id });
+        }
+        Ok(claim)
+    }
+
+    /// Gives up an active claim so its slot becomes available to someone else.
+    pub fn release(context: &GraphQLContext, claim_uuid: &str) -> Result<BonusChoreClaim> {
+        let updated = diesel::update(bonus_chore_claims::table)
+            .filter(bonus_chore_claims::uuid.eq(claim_uuid))
+            .filter(bonus_chore_claims::status.eq(String::from(ClaimStatus::Active)))
+            .set((
+                bonus_chore_claims::status.eq(String::from(ClaimStatus::Released)),
+                bonus_chore_claims::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not release bonus chore claim")?;
+
+        if updated == 0 {
+            return Err(anyhow!("Only active claims can be released"));
+        }
+
+        Self::get(context, claim_uuid)
+    }
+
+    /// Marks every active claim whose reservation window has passed as expired.
+    pub fn release_expired(conn: &mut SqliteConnection) -> Result<usize> {
+        let now = Utc::now().naive_utc();
+        diesel::update(bonus_chore_claims::table)
+            .filter(bonus_chore_claims::status.eq(String::from(ClaimStatus::Active)))
+            .filter(bonus_chore_claims::expires_at.le(now))
+            .set((
+                bonus_chore_claims::status.eq(String::from(ClaimStatus::Expired)),
+                bonus_chore_claims::updated_at.eq(now),
+            ))
+            .execute(conn)
+            .context("Could not release expired bonus chore claims")
+    }
+
+    /// Returns the user's active claim on a bonus chore, taking a new slot if they hold none.
+    /// The flag is `true` when a new slot was reserved by this call.
+    pub(crate) fn acquire(
+        context: &GraphQLContext,
+        chore: &Chore,
+        user_id: i32,
+    ) -> Result<(BonusChoreClaim, bool)> {
+        let chore_id = chore.id.context("chore id missing")?;
+        get_conn(context)?.immediate_transaction(|conn| {
+            Self::release_expired(conn)?;
+            match Self::find_active(conn, chore_id, user_id)? {
+                Some(claim) => Ok((claim, false)),
+                None => Ok((Self::reserve(conn, chore, user_id)?, true)),
+            }
+        })
+    }
+
+    /// Connection-level variant of `acquire` for callers that already hold an immediate
+    /// transaction; the cap check is only race-free when run inside one. Used when a
+    /// completion is submitted so the completion always consumes a claim.
+    pub(crate) fn acquire_in(
+        conn: &mut SqliteConnection,
+        chore: &Chore,
+        user_id: i32,
+    ) -> Result<BonusChoreClaim> {
+        let chore_id = chore.id.context("chore id missing")?;
+        Self::release_expired(conn)?;
+        Self::find_active(conn, chore_id, user_id)?
+            .map_or_else(|| Self::reserve(conn, chore, user_id), Ok)
+    }
+
+    /// Links a claim to the completion that used it, permanently occupying its slot.
+    pub(crate) fn fulfill(
+        conn: &mut SqliteConnection,
+        claim_id: i32,
+        completion_id: i32,
+    ) -> Result<()> {
+        diesel::update(bonus_chore_claims::table)
+            .filter(bonus_chore_claims::id.eq(claim_id))
+            .set((
+                bonus_chore_claims::status.eq(String::from(ClaimStatus::Fulfilled)),
+                bonus_chore_claims::completion_id.eq(completion_id),
+                bonus_chore_claims::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+            .context("Could not fulfill bonus chore claim")?;
+        Ok(())
+    }
+
+    /// Frees the slot held by a completion that is being deleted.
+    pub(crate) fn release_for_completion(
+        conn: &mut SqliteConnection,
+        completion_uuid: &str,
+    ) -> Result<()> {
+        let completion_ids = chore_completions::table
+            .filter(chore_completions::uuid.eq(completion_uuid))
+            .select(chore_completions::id);
+
+        diesel::update(bonus_chore_claims::table)
+            .filter(bonus_chore_claims::completion_id.eq_any(completion_ids))
+            .set((
+                bonus_chore_claims::status.eq(String::from(ClaimStatus::Released)),
+                bonus_chore_claims::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+            .context("Could not release claim for deleted completion")?;
+        Ok(())
+    }
+
+    fn count_held(conn: &mut SqliteConnection, chore_id: i32) -> Result<i64> {
+        bonus_chore_claims::table
+            .filter(bonus_chore_claims::chore_id.eq(chore_id))
+            .filter(bonus_chore_claims::status.eq_any(Self::held_statuses()))
+            .count()
+            .get_result(conn)
+            .context("Could not count bonus chore claims")
+    }
+
+    fn find_active(
+        conn: &mut SqliteConnection,
+        chore_id: i32,
+        user_id: i32,
+    ) -> Result<Option<BonusChoreClaim>> {
+        bonus_chore_claims::table
+            .filter(bonus_chore_claims::chore_id.eq(chore_id))
+            .filter(bonus_chore_claims::user_id.eq(user_id))
+            .filter(bonus_chore_claims::status.eq(String::from(ClaimStatus::Active)))
+            .select(BonusChoreClaim::as_select())
+            .first(conn)
+            .optional()
+            .context("Could not look up active bonus chore claim")
+    }
+
+    fn reserve(
+        conn: &mut SqliteConnection,
+        chore: &Chore,
+        user_id: i32,
+    ) -> Result<BonusChoreClaim> {
+        let chore_id = chore.id.context("chore id missing")?;
+
+        if let Some(cap) = chore.max_claims
+            && Self::count_held(conn, chore_id)? >= i64::from(cap)
+        {
+            return Err(ClaimError::LimitReached.into());
+        }
+
+        let now = Utc::now().naive_utc();
+        let claim = BonusChoreClaim {
+            id: None,
+            uuid: crate::uuid_or_generate(None),
+            chore_id,
+            user_id,
+            status: ClaimStatus::Active.into(),
+            claimed_at: now,
+            expires_at: now + Self::claim_ttl(),
+            completion_id: None,
+            updated_at: None,
+        };
+
+        diesel::insert_into(bonus_chore_claims::table)
+            .values(&claim)
+            .execute(conn)
+            .context("Could not create bonus chore claim")?;
+
+        bonus_chore_claims::table
+            .filter(bonus_chore_claims::uuid.eq(&claim.uuid))
+            .select(BonusChoreClaim::as_select())
+            .first(conn)
+            .context("Could not find bonus chore claim")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, ChoreInput, PaymentType},
+        svc::ChoreCompletionSvc,
+        test_helpers::test_db::{
+            create_test_admin, create_test_chore, create_test_context, create_test_date,
+            create_test_user, day_patterns,
+        },
+    };
+
+    fn create_bonus_chore(
+        context: &GraphQLContext,
+        admin_id: i32,
+        max_claims: Option<i32>,
+    ) -> Chore {
+        let input = ChoreInput {
+            uuid: None,
+            name: "Wash the car".to_owned(),
+            description: None,
+            payment_type: PaymentType::Daily,
+            amount_cents: 500,
+            required_days: 0,
+            active: Some(true),
+            created_by_admin_id: admin_id,
+            bonus_date: Some(create_test_date(2026, 4, 18)),
+            max_claims,
+            points: None,
+        };
+        ChoreSvc::create(context, &Chore::from(input)).unwrap()
+    }
+
+    fn expire_claim(context: &GraphQLContext, claim_uuid: &str) {
+        let past = Utc::now().naive_utc() - chrono::Duration::minutes(1);
+        diesel::update(bonus_chore_claims::table)
+            .filter(bonus_chore_claims::uuid.eq(claim_uuid))
+            .set(bonus_chore_claims::expires_at.eq(past))
+            .execute(&mut context.pool.get().unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_claim_reserves_slot_and_blocks_others() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user1 = create_test_user(&context, "User 1");
+        let user2 = create_test_user(&context, "User 2");
+        let chore = create_bonus_chore(&context, admin.id.unwrap(), Some(1));
+        let chore_id = chore.id.unwrap();
+
+        let claim = BonusClaimSvc::claim(&context, chore_id, user1.id.unwrap()).unwrap();
+        assert_eq!(claim.status, "active");
+        assert!(claim.expires_at > claim.claimed_at);
+        assert!(!ChoreSvc::can_claim_bonus(&context, chore_id).unwrap());
+
+        let err = BonusClaimSvc::claim(&context, chore_id, user2.id.unwrap()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ClaimError>(),
+            Some(&ClaimError::LimitReached)
+        );
+
+        // The slot is reserved before any work is done, so user2's completion is rejected
+        // up front rather than after the fact.
+        let input = ChoreCompletionInput {
+            uuid: None,
+            chore_id,
+            user_id: user2.id.unwrap(),
+            completed_date: create_test_date(2026, 4, 18),
+        };
+        assert!(ChoreCompletionSvc::create(&context, &input).is_err());
+    }
+
+    #[test]
+    fn test_claim_is_idempotent_per_user() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "User");
+        let chore = create_bonus_chore(&context, admin.id.unwrap(), Some(2));
+        let chore_id = chore.id.unwrap();
+
+        let first = BonusClaimSvc::claim(&context, chore_id, user.id.unwrap()).unwrap();
+        let second = BonusClaimSvc::claim(&context, chore_id, user.id.unwrap()).unwrap();
+        assert_eq!(first.uuid, second.uuid);
+        assert_eq!(BonusClaimSvc::held_count(&context, chore_id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_completion_fulfills_existing_claim() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "User");
+        let chore = create_bonus_chore(&context, admin.id.unwrap(), Some(1));
+        let chore_id = chore.id.unwrap();
+
+        let claim = BonusClaimSvc::claim(&context, chore_id, user.id.unwrap()).unwrap();
+        let input = ChoreCompletionInput {
+            uuid: None,
+            chore_id,
+            user_id: user.id.unwrap(),
+            completed_date: create_test_date(2026, 4, 18),
+        };
+        let completion = ChoreCompletionSvc::create(&context, &input).unwrap();
+
+        let claim = BonusClaimSvc::get(&context, &claim.uuid).unwrap();
+        assert_eq!(claim.status, "fulfilled");
+        assert_eq!(claim.completion_id, completion.id);
+        assert_eq!(BonusClaimSvc::held_count(&context, chore_id).unwrap(), 1);
+
+        // Deleting the completion gives the slot back
+        ChoreCompletionSvc::delete(&context, &completion.uuid).unwrap();
+        let claim = BonusClaimSvc::get(&context, &claim.uuid).unwrap();
+        assert_eq!(claim.status, "released");
+        assert!(ChoreSvc::can_claim_bonus(&context, chore_id).unwrap());
+    }
+
+    #[test]
+    fn test_expired_claims_are_released_automatically() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user1 = create_test_user(&context, "User 1");
+        let user2 = create_test_user(&context, "User 2");
+        let chore = create_bonus_chore(&context, admin.id.unwrap(), Some(1));
+        let chore_id = chore.id.unwrap();
+
+        let stale = BonusClaimSvc::claim(&context, chore_id, user1.id.unwrap()).unwrap();
+        expire_claim(&context, &stale.uuid);
+
+        let fresh = BonusClaimSvc::claim(&context, chore_id, user2.id.unwrap()).unwrap();
+        assert_eq!(fresh.user_id, user2.id.unwrap());
+        assert_eq!(
+            BonusClaimSvc::get(&context, &stale.uuid).unwrap().status,
+            "expired"
+        );
+
+        let held = BonusClaimSvc::list(&context, Some(chore_id), None, true).unwrap();
+        assert_eq!(held.len(), 1);
+        let all = BonusClaimSvc::list(&context, Some(chore_id), None, false).unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_release_frees_slot() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "User");
+        let chore = create_bonus_chore(&context, admin.id.unwrap(), Some(1));
+        let chore_id = chore.id.unwrap();
+
+        let claim = BonusClaimSvc::claim(&context, chore_id, user.id.unwrap()).unwrap();
+        let released = BonusClaimSvc::release(&context, &claim.uuid).unwrap();
+        assert_eq!(released.status, "released");
+        assert!(ChoreSvc::can_claim_bonus(&context, chore_id).unwrap());
+
+        // Releasing twice is rejected
+        assert!(BonusClaimSvc::release(&context, &claim.uuid).is_err());
+    }
+
+    #[test]
+    fn test_regular_chores_cannot_be_claimed() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "User");
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+
+        let result = BonusClaimSvc::claim(&context, chore.id.unwrap(), user.id.unwrap());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{CalendarDay, CalendarDayKind},
+    schema::household_calendar_days,
+    svc::StreakSvc,
+};
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+
+/// Longest single ICS event we expand into calendar days.
+const MAX_EVENT_DAYS: i64 = 366;
+
+/// A VEVENT reduced to what the household calendar needs.
+#[derive(Debug, PartialEq, Eq)]
+struct IcsEvent {
+    uid: Option<String>,
+    summary: String,
+    start: NaiveDate,
+    /// Exclusive, as in RFC 5545 all-day events.
+    end: NaiveDate,
+}
+
+/// Properties collected between BEGIN:VEVENT and END:VEVENT.
+#[derive(Default)]
+struct PartialEvent {
+    uid: Option<String>,
+    summary: Option<String>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+}
+
+pub struct CalendarSvc {}
+
+impl CalendarSvc {
+    pub fn get(context: &GraphQLContext, day_uuid: &str) -> Result<CalendarDay> {
+        household_calendar_days::table
+            .filter(household_calendar_days::uuid.eq(day_uuid))
+            .select(CalendarDay::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find calendar day")
+    }
+
+    /// Calendar days in the inclusive range, in date order. Either bound may be open.
+    pub fn list(
+        context: &GraphQLContext,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<CalendarDay>> {
+        let mut query = household_calendar_days::table.into_boxed();
+
+        if let Some(from) = from {
+            query = query.filter(household_calendar_days::date.ge(from));
+        }
+        if let Some(to) = to {
+            query = query.filter(household_calendar_days::date.le(to));
+        }
+
+        query
+            .select(CalendarDay::as_select())
+            .order_by(household_calendar_days::date.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load calendar days")
+    }
+
+    /// Adds a calendar day, or replaces the entry already on that date.
+    pub fn upsert(context: &GraphQLContext, day: &CalendarDay) -> Result<CalendarDay> {
+        Self::validate(day)?;
+        Self::upsert_in(&mut *get_conn(context)?, day)?;
+        StreakSvc::invalidate(context, None, day.date)?;
+
+        Self::get_by_date(context, day.date)
+    }
+
+    pub fn delete(context: &GraphQLContext, day_uuid: &str) -> Result<()> {
+        let date: Option<NaiveDate> = household_calendar_days::table
+            .filter(household_calendar_days::uuid.eq(day_uuid))
+            .select(household_calendar_days::date)
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not find calendar day")?;
+        diesel::delete(household_calendar_days::table)
+            .filter(household_calendar_days::uuid.eq(day_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete calendar day")?;
+        if let Some(date) = date {
+            StreakSvc::invalidate(context, None, date)?;
+        }
+
+        Ok(())
+    }
+
+    /// Imports every all-day or timed VEVENT in an ICS file as `kind` days, one per date the
+    /// event touches. Re-importing the same file updates the existing days in place.
+    /// Recurrence rules are not expanded; only each event's own dates are used.
+    pub fn import_ics(
+        context: &GraphQLContext,
+        ics: &str,
+        kind: CalendarDayKind,
+        alternate_weekday: Option<i32>,
+    ) -> Result<Vec<CalendarDay>> {
+        let events = parse_ics(ics)?;
+
+        let mut days = Vec::new();
+        for event in events {
+            for date in event.start.iter_days().take_while(|date| *date < event.end) {
+                days.push(CalendarDay {
+                    id: None,
+                    uuid: crate::uuid_or_generate(None),
+                    date,
+                    name: event.summary.clone(),
+                    kind: kind.into(),
+                    alternate_weekday,
+                    source: "ics".to_owned(),
+                    ics_uid: event.uid.clone(),
+                    created_at: None,
+                    updated_at: None,
+                });
+            }
+        }
+        for day in &days {
+            Self::validate(day)?;
+        }
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            for day in &days {
+                Self::upsert_in(conn, day)?;
+            }
+            anyhow::Ok(())
+        })?;
+        if let Some(first) = days.iter().map(|day| day.date).min() {
+            StreakSvc::invalidate(context, None, first)?;
+        }
+
+        let dates: Vec<NaiveDate> = days.iter().map(|day| day.date).collect();
+        household_calendar_days::table
+            .filter(household_calendar_days::date.eq_any(dates))
+            .select(CalendarDay::as_select())
+            .order_by(household_calendar_days::date.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load imported calendar days")
+    }
+
+    fn get_by_date(context: &GraphQLContext, date: NaiveDate) -> Result<CalendarDay> {
+        household_calendar_days::table
+            .filter(household_calendar_days::date.eq(date))
+            .select(CalendarDay::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find calendar day")
+    }
+
+    /// Inserts `day`, or overwrites the row on the same date while keeping its uuid.
+    fn upsert_in(conn: &mut SqliteConnection, day: &CalendarDay) -> Result<()> {
+        diesel::insert_into(household_calendar_days::table)
+            .values(day)
+            .on_conflict(household_calendar_days::date)
+            .do_update()
+            .set((
+                household_calendar_days::name.eq(&day.name),
+                household_calendar_days::kind.eq(&day.kind),
+                household_calendar_days::alternate_weekday.eq(day.alternate_weekday),
+                household_calendar_days::source.eq(&day.source),
+                household_calendar_days::ics_uid.eq(&day.ics_uid),
+                household_calendar_days::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)
+            .context("Could not save calendar day")?;
+
+        Ok(())
+    }
+
+    fn validate(day: &CalendarDay) -> Result<()> {
+        if day.name.trim().is_empty() {
+            bail!("Calendar days need a name");
+        }
+        match (CalendarDayKind::from(&day.kind), day.alternate_weekday) {
+            (CalendarDayKind::Skip, None) => Ok(()),
+            (CalendarDayKind::Skip, Some(_)) => bail!("Skip days cannot have an alternate weekday"),
+            (CalendarDayKind::Alternate, Some(weekday)) if (0..=6).contains(&weekday) => Ok(()),
+            (CalendarDayKind::Alternate, _) => {
+                bail!("Alternate days need a weekday between 0 (Monday) and 6 (Sunday)")
+            }
+        }
+    }
+}
+
+/// Minimal RFC 5545 reader: unfolds lines and collects DTSTART/DTEND/SUMMARY/UID of
+/// each VEVENT.
+fn parse_ics(ics: &str) -> Result<Vec<IcsEvent>> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(raw.to_owned()),
+        }
+    }
+    if !lines
+        .iter()
+        .any(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        bail!("Not an iCalendar file");
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<PartialEvent> = None;
+    for line in &lines {
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") {
+            current = Some(PartialEvent::default());
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VEVENT") {
+            let Some(PartialEvent {
+                uid,
+                summary,
+                start,
+                end,
+            }) = current.take()
+            else {
+                bail!("END:VEVENT without BEGIN:VEVENT");
+            };
+            let start = start.context("Event is missing DTSTART")?;
+            let end = end
+                .filter(|end| *end > start)
+                .unwrap_or(start + chrono::Duration::days(1));
+            if (end - start).num_days() > MAX_EVENT_DAYS {
+                bail!("Event starting {start} spans more than {MAX_EVENT_DAYS} days");
+            }
+            events.push(IcsEvent {
+                uid,
+                summary: summary.unwrap_or_else(|| "Holiday".to_owned()),
+                start,
+                end,
+            });
+            continue;
+        }
+        let Some(event) = current.as_mut() else {
+            continue;
+        };
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let property = name.split(';').next().unwrap_or_default().to_uppercase();
+        match property.as_str() {
+            "UID" => event.uid = Some(value.to_owned()),
+            "SUMMARY" => event.summary = Some(unescape_text(value)),
+            "DTSTART" => event.start = Some(parse_ics_date(value, false)?),
+            "DTEND" => event.end = Some(parse_ics_date(value, true)?),
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
+
+/// Reads the date part of a DATE or DATE-TIME value. A timed end falls on the day it names
+/// unless it is exactly midnight, so `is_end` turns it into the exclusive bound.
+fn parse_ics_date(value: &str, is_end: bool) -> Result<NaiveDate> {
+    let date_part = value
+        .get(..8)
+        .ok_or_else(|| anyhow!("Invalid date '{value}'"))?;
+    let date = NaiveDate::parse_from_str(date_part, "%Y%m%d")
+        .with_context(|| format!("Invalid date '{value}'"))?;
+
+    let time = value.get(9..15);
+    if is_end && time.is_some_and(|time| time != "000000") {
+        return Ok(date + chrono::Duration::days(1));
+    }
+    Ok(date)
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push(' '),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out.trim().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::CalendarDayInput,
+        test_helpers::test_db::{create_test_context, create_test_date},
+    };
+
+    const SCHOOL_ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:winter-break@school\r\n\
+DTSTART;VALUE=DATE:20261223\r\n\
+DTEND;VALUE=DATE:20261226\r\n\
+SUMMARY:Winter\\, \r\n Break\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:teacher-day@school\r\n\
+DTSTART:20261106T080000Z\r\n\
+DTEND:20261106T150000Z\r\n\
+SUMMARY:Teacher Workday\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_ics_events() {
+        let events = parse_ics(SCHOOL_ICS).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                IcsEvent {
+                    uid: Some("winter-break@school".to_owned()),
+                    summary: "Winter, Break".to_owned(),
+                    start: create_test_date(2026, 12, 23),
+                    end: create_test_date(2026, 12, 26),
+                },
+                IcsEvent {
+                    uid: Some("teacher-day@school".to_owned()),
+                    summary: "Teacher Workday".to_owned(),
+                    start: create_test_date(2026, 11, 6),
+                    end: create_test_date(2026, 11, 7),
+                },
+            ]
+        );
+
+        assert!(parse_ics("not a calendar").is_err());
+        assert!(
+            parse_ics("BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:No date\nEND:VEVENT\nEND:VCALENDAR")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_import_ics_is_idempotent() {
+        let context = create_test_context();
+
+        let days =
+            CalendarSvc::import_ics(&context, SCHOOL_ICS, CalendarDayKind::Skip, None).unwrap();
+        assert_eq!(days.len(), 4);
+        assert!(days.iter().all(|day| day.source == "ics"));
+
+        // Importing again as alternate days updates the same rows
+        let again =
+            CalendarSvc::import_ics(&context, SCHOOL_ICS, CalendarDayKind::Alternate, Some(5))
+                .unwrap();
+        assert_eq!(again.len(), 4);
+        assert_eq!(again[0].uuid, days[0].uuid);
+        assert_eq!(
+            CalendarDayKind::from(&again[0].kind),
+            CalendarDayKind::Alternate
+        );
+        assert_eq!(CalendarSvc::list(&context, None, None).unwrap().len(), 4);
+
+        // Alternate days need a weekday
+        assert!(
+            CalendarSvc::import_ics(&context, SCHOOL_ICS, CalendarDayKind::Alternate, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_manual_days_and_range_listing() {
+        let context = create_test_context();
+
+        let trip = CalendarSvc::upsert(
+            &context,
+            &CalendarDayInput {
+                uuid: None,
+                date: create_test_date(2026, 8, 3),
+                name: "Beach trip".to_owned(),
+                kind: CalendarDayKind::Skip,
+                alternate_weekday: None,
+            }
+            .into(),
+        )
+        .unwrap();
+        assert_eq!(trip.source, "manual");
+
+        let invalid = CalendarSvc::upsert(
+            &context,
+            &CalendarDayInput {
+                uuid: None,
+                date: create_test_date(2026, 8, 4),
+                name: "Moving day".to_owned(),
+                kind: CalendarDayKind::Alternate,
+                alternate_weekday: Some(9),
+            }
+            .into(),
+        );
+        assert!(invalid.is_err());
+
+        let listed = CalendarSvc::list(
+            &context,
+            Some(create_test_date(2026, 8, 1)),
+            Some(create_test_date(2026, 8, 31)),
+        )
+        .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(
+            CalendarSvc::list(&context, Some(create_test_date(2026, 9, 1)), None)
+                .unwrap()
+                .is_empty()
+        );
+
+        CalendarSvc::delete(&context, &trip.uuid).unwrap();
+        assert!(CalendarSvc::list(&context, None, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,420 @@
+//! Per-kid iCalendar feeds of the chore schedule.
+//!
+//! Each recurring chore becomes one weekly VEVENT with an RRULE built from its
+//! `required_days`; household skip days and pauses are listed as EXDATEs and alternate
+//! days as RDATEs, for a window around today. Bonus chores are single all-day events.
+//! Calendar clients can't sign in, so feeds are reached through a secret token per kid.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{CalendarFeed, Chore},
+    schema::{calendar_feeds, chore_assignments, chores},
+    svc::{ScheduleSvc, UserSvc, email::format_cents},
+};
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Exceptions and bonus chores are listed from this many days back…
+const PAST_DAYS: i64 = 28;
+/// …to this many days ahead.
+const FUTURE_DAYS: i64 = 365;
+
+const BYDAY: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+pub struct CalendarFeedSvc {}
+
+impl CalendarFeedSvc {
+    pub fn get(context: &GraphQLContext, user_id: i32) -> Result<Option<CalendarFeed>> {
+        calendar_feeds::table
+            .filter(calendar_feeds::user_id.eq(user_id))
+            .select(CalendarFeed::as_select())
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not load calendar feed")
+    }
+
+    /// Issues a new token for the user's feed, replacing (and so revoking) any old one.
+    pub fn rotate(context: &GraphQLContext, user_id: i32) -> Result<CalendarFeed> {
+        let feed = CalendarFeed {
+            id: None,
+            user_id,
+            token: Uuid::new_v4().simple().to_string(),
+            created_at: Some(Utc::now().naive_utc()),
+        };
+        diesel::insert_into(calendar_feeds::table)
+            .values(&feed)
+            .on_conflict(calendar_feeds::user_id)
+            .do_update()
+            .set((
+                calendar_feeds::token.eq(&feed.token),
+                calendar_feeds::created_at.eq(feed.created_at),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not save calendar feed")?;
+
+        Self::get(context, user_id)?.context("Could not find calendar feed")
+    }
+
+    pub fn revoke(context: &GraphQLContext, user_id: i32) -> Result<()> {
+        diesel::delete(calendar_feeds::table)
+            .filter(calendar_feeds::user_id.eq(user_id))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete calendar feed")?;
+
+        Ok(())
+    }
+
+    /// The user a feed token belongs to, if it is current.
+    pub fn user_for_token(context: &GraphQLContext, token: &str) -> Result<Option<i32>> {
+        calendar_feeds::table
+            .filter(calendar_feeds::token.eq(token))
+            .select(calendar_feeds::user_id)
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not look up calendar feed")
+    }
+
+    /// The user's schedule as an iCalendar document.
+    pub fn render(context: &GraphQLContext, user_id: i32) -> Result<String> {
+        Self::render_as_of(
+            context,
+            user_id,
+            Local::now().date_naive(),
+            Utc::now().naive_utc(),
+        )
+    }
+
+    fn render_as_of(
+        context: &GraphQLContext,
+        user_id: i32,
+        today: NaiveDate,
+        now: NaiveDateTime,
+    ) -> Result<String> {
+        let user = UserSvc::get_by_id(context, user_id)?;
+        let schedule = ScheduleSvc::user_schedule(context, user_id)?;
+        let from = today - Duration::days(PAST_DAYS);
+        let to = today + Duration::days(FUTURE_DAYS);
+
+        let assigned: Vec<Chore> = chores::table
+            .inner_join(chore_assignments::table)
+            .filter(chore_assignments::user_id.eq(user_id))
+            .filter(chores::active.eq(true))
+            .select(Chore::as_select())
+            .order_by(chores::name.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load assigned chores")?;
+        // Bonus chores nobody is assigned to are open to every kid
+        let open_bonus: Vec<Chore> = chores::table
+            .filter(chores::active.eq(true))
+            .filter(chores::bonus_date.between(from, to))
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                chore_assignments::table
+                    .filter(chore_assignments::chore_id.nullable().eq(chores::id)),
+            )))
+            .select(Chore::as_select())
+            .order_by(chores::name.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load bonus chores")?;
+
+        let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let mut ics = String::new();
+        line(&mut ics, "BEGIN:VCALENDAR");
+        line(&mut ics, "VERSION:2.0");
+        line(&mut ics, "PRODID:-//Chore Tracker//Chore schedule//EN");
+        line(&mut ics, "CALSCALE:GREGORIAN");
+        line(&mut ics, "METHOD:PUBLISH");
+        line(
+            &mut ics,
+            &format!("X-WR-CALNAME:{}", text(&format!("{}'s chores", user.name))),
+        );
+
+        for chore in assigned.iter().chain(&open_bonus) {
+            let Some(chore_id) = chore.id else {
+                continue;
+            };
+            let summary = format!(
+                "{} ({})",
+                chore.name,
+                format_cents(chore.amount_cents.into())
+            );
+            let uid = format!("{}-{}@chore-tracker", chore.uuid, user.uuid);
+
+            let (start, rule, exdates, rdates) = if let Some(bonus_date) = chore.bonus_date {
+                if bonus_date < from || bonus_date > to {
+                    continue;
+                }
+                (bonus_date, None, Vec::new(), Vec::new())
+            } else {
+                let weekdays = chore.required_days & 0x7f;
+                if weekdays == 0 {
+                    continue;
+                }
+                let Some(start) = from
+                    .iter_days()
+                    .find(|date| ScheduleSvc::is_scheduled(weekdays, *date))
+                else {
+                    continue;
+                };
+                let days: Vec<&str> = (0..7)
+                    .filter(|bit| weekdays & (1 << bit) != 0)
+                    .map(|bit| BYDAY[bit])
+                    .collect();
+                let mut exdates = Vec::new();
+                let mut rdates = Vec::new();
+                for date in from.iter_days().take_while(|date| *date <= to) {
+                    let usual = ScheduleSvc::is_scheduled(weekdays, date);
+                    let due = schedule.is_scheduled(weekdays, date)
+                        && !schedule.is_paused(chore_id, date);
+                    match (usual, due) {
+                        (true, false) => exdates.push(date),
+                        (false, true) => rdates.push(date),
+                        _ => {}
+                    }
+                }
+                let rule = format!("FREQ=WEEKLY;WKST=MO;BYDAY={}", days.join(","));
+                (start, Some(rule), exdates, rdates)
+            };
+
+            line(&mut ics, "BEGIN:VEVENT");
+            line(&mut ics, &format!("UID:{uid}"));
+            line(&mut ics, &format!("DTSTAMP:{stamp}"));
+            line(&mut ics, &format!("DTSTART;VALUE=DATE:{}", date(start)));
+            line(&mut ics, "DURATION:P1D");
+            if let Some(rule) = rule {
+                line(&mut ics, &format!("RRULE:{rule}"));
+            }
+            if !exdates.is_empty() {
+                line(&mut ics, &format!("EXDATE;VALUE=DATE:{}", dates(&exdates)));
+            }
+            if !rdates.is_empty() {
+                line(&mut ics, &format!("RDATE;VALUE=DATE:{}", dates(&rdates)));
+            }
+            line(&mut ics, &format!("SUMMARY:{}", text(&summary)));
+            if let Some(description) = chore.description.as_deref().filter(|d| !d.is_empty()) {
+                line(&mut ics, &format!("DESCRIPTION:{}", text(description)));
+            }
+            let category = if chore.bonus_date.is_some() {
+                "Bonus chores"
+            } else {
+                "Chores"
+            };
+            line(&mut ics, &format!("CATEGORIES:{category}"));
+            line(&mut ics, "TRANSP:TRANSPARENT");
+            line(&mut ics, "END:VEVENT");
+        }
+
+        line(&mut ics, "END:VCALENDAR");
+        Ok(ics)
+    }
+}
+
+fn date(date: NaiveDate) -> String {
+    format!("{:04}{:02}{:02}", date.year(), date.month(), date.day())
+}
+
+fn dates(list: &[NaiveDate]) -> String {
+    list.iter().map(|d| date(*d)).collect::<Vec<_>>().join(",")
+}
+
+/// Escapes an RFC 5545 TEXT value.
+fn text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Appends a content line, folded to 75 octets without splitting a character.
+fn line(ics: &mut String, content: &str) {
+    let mut width = 0;
+    for c in content.chars() {
+        if width + c.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    let _ = write!(ics, "\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{CalendarDayInput, CalendarDayKind, ChoreInput, PausePeriodInput, PaymentType},
+        svc::{CalendarSvc, ChoreSvc, PauseSvc},
+        test_helpers::test_db::{
+            create_test_admin, create_test_chore, create_test_chore_assignment,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    /// Content lines with folding undone.
+    fn unfolded(ics: &str) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for raw in ics.split("\r\n") {
+            match (raw.strip_prefix(' '), lines.last_mut()) {
+                (Some(continuation), Some(last)) => last.push_str(continuation),
+                _ => lines.push(raw.to_owned()),
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_tokens_rotate_and_revoke() {
+        let context = create_test_context();
+        let user_id = create_test_user(&context, "Alice").id.unwrap();
+        assert!(CalendarFeedSvc::get(&context, user_id).unwrap().is_none());
+
+        let first = CalendarFeedSvc::rotate(&context, user_id).unwrap();
+        assert_eq!(first.token.len(), 32);
+        let second = CalendarFeedSvc::rotate(&context, user_id).unwrap();
+        assert_ne!(first.token, second.token);
+        assert_eq!(
+            CalendarFeedSvc::user_for_token(&context, &first.token).unwrap(),
+            None
+        );
+        assert_eq!(
+            CalendarFeedSvc::user_for_token(&context, &second.token).unwrap(),
+            Some(user_id)
+        );
+
+        CalendarFeedSvc::revoke(&context, user_id).unwrap();
+        assert_eq!(
+            CalendarFeedSvc::user_for_token(&context, &second.token).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_feed_expands_schedule_with_exceptions() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let user = create_test_user(&context, "Alice");
+        let (user_id, user_uuid) = (user.id.unwrap(), user.uuid);
+        let dishes = create_test_chore(
+            &context,
+            "Dishes, pots",
+            PaymentType::Daily,
+            150,
+            day_patterns::mon_wed_fri(),
+            admin_id,
+        );
+        create_test_chore_assignment(&context, dishes.id.unwrap(), user_id);
+        let bonus = ChoreSvc::create(
+            &context,
+            &Chore::from(ChoreInput {
+                uuid: None,
+                name: "Wash the car".to_owned(),
+                description: None,
+                payment_type: PaymentType::Daily,
+                amount_cents: 500,
+                required_days: 0,
+                active: Some(true),
+                created_by_admin_id: admin_id,
+                bonus_date: Some(create_test_date(2026, 4, 18)),
+                max_claims: None,
+                points: None,
+            }),
+        )
+        .unwrap();
+
+        // Wednesday the 15th is skipped, Tuesday the 21st stands in for a Monday and
+        // Friday the 24th is paused
+        CalendarSvc::upsert(
+            &context,
+            &CalendarDayInput {
+                uuid: None,
+                date: create_test_date(2026, 4, 15),
+                name: "Trip".to_owned(),
+                kind: CalendarDayKind::Skip,
+                alternate_weekday: None,
+            }
+            .into(),
+        )
+        .unwrap();
+        CalendarSvc::upsert(
+            &context,
+            &CalendarDayInput {
+                uuid: None,
+                date: create_test_date(2026, 4, 21),
+                name: "Swap".to_owned(),
+                kind: CalendarDayKind::Alternate,
+                alternate_weekday: Some(0),
+            }
+            .into(),
+        )
+        .unwrap();
+        PauseSvc::create(
+            &context,
+            &PausePeriodInput {
+                uuid: None,
+                user_id,
+                chore_id: dishes.id,
+                start_date: create_test_date(2026, 4, 24),
+                end_date: create_test_date(2026, 4, 24),
+                reason: None,
+            }
+            .into(),
+        )
+        .unwrap();
+
+        let now = create_test_date(2026, 4, 13).and_hms_opt(8, 0, 0).unwrap();
+        let ics =
+            CalendarFeedSvc::render_as_of(&context, user_id, create_test_date(2026, 4, 13), now)
+                .unwrap();
+        assert!(ics.lines().all(|l| l.len() <= 76));
+        let lines = unfolded(&ics);
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        assert!(lines.contains(&"X-WR-CALNAME:Alice's chores".to_owned()));
+        assert_eq!(lines.iter().filter(|l| *l == "BEGIN:VEVENT").count(), 2);
+
+        let has = |expected: &str| lines.iter().any(|l| l == expected);
+        assert!(has(&format!(
+            "UID:{}-{user_uuid}@chore-tracker",
+            dishes.uuid
+        )));
+        // Four weeks back from Monday the 13th
+        assert!(has("DTSTART;VALUE=DATE:20260316"));
+        assert!(has("RRULE:FREQ=WEEKLY;WKST=MO;BYDAY=MO,WE,FR"));
+        assert!(has("EXDATE;VALUE=DATE:20260415,20260424"));
+        assert!(has("RDATE;VALUE=DATE:20260421"));
+        assert!(has("SUMMARY:Dishes\\, pots ($1.50)"));
+        assert!(has("DTSTAMP:20260413T080000Z"));
+
+        assert!(has(&format!(
+            "UID:{}-{user_uuid}@chore-tracker",
+            bonus.uuid
+        )));
+        assert!(has("DTSTART;VALUE=DATE:20260418"));
+        assert!(has("SUMMARY:Wash the car ($5.00)"));
+        assert!(has("CATEGORIES:Bonus chores"));
+    }
+
+    #[test]
+    fn test_long_lines_fold_on_character_boundaries() {
+        let mut ics = String::new();
+        line(&mut ics, &format!("DESCRIPTION:{}", "é".repeat(60)));
+        let physical: Vec<&str> = ics.split("\r\n").collect();
+        assert!(physical.iter().all(|l| l.len() <= 75));
+        assert!(physical[1].starts_with(' '));
+        assert_eq!(unfolded(&ics)[0].chars().count(), 72);
+    }
+}
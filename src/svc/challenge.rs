@@ -0,0 +1,461 @@
+//! Family challenges.
+//!
+//! A challenge is a target the participants reach together, measured over their approved
+//! completions dated within the challenge: how many there were, or the cents or points
+//! they earned. The first time progress meets the target the challenge is marked
+//! completed and a `ChallengeCompleted` event goes out.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{Challenge, ChallengeMetric, ChallengeStatus, User},
+    schema::{challenge_participants, challenges, chore_completions, users},
+    svc::{NotificationSvc, notification::NotificationEvent},
+};
+use anyhow::{Context, Result, bail};
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use juniper::GraphQLObject;
+
+/// What one participant added to a challenge.
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(context = GraphQLContext)]
+pub struct ChallengeContribution {
+    pub user: User,
+    pub value: i32,
+}
+
+pub struct ChallengeSvc {}
+
+impl ChallengeSvc {
+    pub fn get(context: &GraphQLContext, challenge_uuid: &str) -> Result<Challenge> {
+        challenges::table
+            .filter(challenges::uuid.eq(challenge_uuid))
+            .select(Challenge::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find challenge")
+    }
+
+    pub fn get_by_id(context: &GraphQLContext, challenge_id: i32) -> Result<Challenge> {
+        challenges::table
+            .filter(challenges::id.eq(challenge_id))
+            .select(Challenge::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find challenge")
+    }
+
+    /// Challenges newest first, for everyone or only those `user_id` takes part in.
+    pub fn list(context: &GraphQLContext, user_id: Option<i32>) -> Result<Vec<Challenge>> {
+        let mut query = challenges::table.into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(
+                challenges::id.eq_any(
+                    challenge_participants::table
+                        .filter(challenge_participants::user_id.eq(user_id))
+                        .select(challenge_participants::challenge_id.nullable()),
+                ),
+            );
+        }
+        query
+            .select(Challenge::as_select())
+            .order_by((challenges::start_date.desc(), challenges::id.desc()))
+            .load(&mut get_conn(context)?)
+            .context("Could not load challenges")
+    }
+
+    pub fn participants(context: &GraphQLContext, challenge_id: i32) -> Result<Vec<User>> {
+        Self::participants_in(&mut *get_conn(context)?, challenge_id)
+    }
+
+    fn participants_in(conn: &mut SqliteConnection, challenge_id: i32) -> Result<Vec<User>> {
+        users::table
+            .inner_join(challenge_participants::table)
+            .filter(challenge_participants::challenge_id.eq(challenge_id))
+            .select(User::as_select())
+            .order_by(users::name.asc())
+            .load(conn)
+            .context("Could not load challenge participants")
+    }
+
+    pub fn create(
+        context: &GraphQLContext,
+        challenge: &Challenge,
+        user_ids: &[i32],
+    ) -> Result<Challenge> {
+        Self::validate(challenge, user_ids)?;
+        get_conn(context)?.immediate_transaction(|conn| {
+            diesel::insert_into(challenges::table)
+                .values(challenge)
+                .execute(conn)
+                .context("Could not create challenge")?;
+            let challenge_id: Option<i32> = challenges::table
+                .filter(challenges::uuid.eq(&challenge.uuid))
+                .select(challenges::id)
+                .first(conn)
+                .context("Could not find challenge")?;
+            let challenge_id = challenge_id.context("Challenge has no id")?;
+            Self::set_participants_in(conn, challenge_id, user_ids)
+        })?;
+
+        Self::check_completed(context, &Self::get(context, &challenge.uuid)?)?;
+        Self::get(context, &challenge.uuid)
+    }
+
+    /// Updates the challenge and replaces its participants. A completed challenge stays
+    /// completed.
+    pub fn update(
+        context: &GraphQLContext,
+        challenge: &Challenge,
+        user_ids: &[i32],
+    ) -> Result<Challenge> {
+        Self::validate(challenge, user_ids)?;
+        let current = Self::get(context, &challenge.uuid)?;
+        let challenge_id = current.id.context("Challenge has no id")?;
+        get_conn(context)?.immediate_transaction(|conn| {
+            diesel::update(challenges::table)
+                .filter(challenges::id.eq(challenge_id))
+                .set((
+                    challenges::name.eq(&challenge.name),
+                    challenges::description.eq(&challenge.description),
+                    challenges::prize.eq(&challenge.prize),
+                    challenges::metric.eq(&challenge.metric),
+                    challenges::target.eq(challenge.target),
+                    challenges::start_date.eq(challenge.start_date),
+                    challenges::end_date.eq(challenge.end_date),
+                    challenges::chore_id.eq(challenge.chore_id),
+                    challenges::updated_at.eq(Utc::now().naive_utc()),
+                ))
+                .execute(conn)
+                .context("Could not update challenge")?;
+            Self::set_participants_in(conn, challenge_id, user_ids)
+        })?;
+
+        Self::check_completed(context, &Self::get(context, &challenge.uuid)?)?;
+        Self::get(context, &challenge.uuid)
+    }
+
+    pub fn delete(context: &GraphQLContext, challenge_uuid: &str) -> Result<()> {
+        let deleted = diesel::delete(challenges::table)
+            .filter(challenges::uuid.eq(challenge_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete challenge")?;
+        if deleted == 0 {
+            bail!("Could not find challenge");
+        }
+        Ok(())
+    }
+
+    fn validate(challenge: &Challenge, user_ids: &[i32]) -> Result<()> {
+        if challenge.name.trim().is_empty() {
+            bail!("Challenges need a name");
+        }
+        if challenge.target < 1 {
+            bail!("Challenges need a target above zero");
+        }
+        if challenge.end_date < challenge.start_date {
+            bail!("Challenges cannot end before they start");
+        }
+        if user_ids.is_empty() {
+            bail!("Challenges need at least one participant");
+        }
+        Ok(())
+    }
+
+    fn set_participants_in(
+        conn: &mut SqliteConnection,
+        challenge_id: i32,
+        user_ids: &[i32],
+    ) -> Result<()> {
+        diesel::delete(challenge_participants::table)
+            .filter(challenge_participants::challenge_id.eq(challenge_id))
+            .execute(conn)
+            .context("Could not clear challenge participants")?;
+        let mut user_ids = user_ids.to_vec();
+        user_ids.sort_unstable();
+        user_ids.dedup();
+        let rows: Vec<_> = user_ids
+            .into_iter()
+            .map(|user_id| {
+                (
+                    challenge_participants::challenge_id.eq(challenge_id),
+                    challenge_participants::user_id.eq(user_id),
+                )
+            })
+            .collect();
+        diesel::insert_into(challenge_participants::table)
+            .values(&rows)
+            .execute(conn)
+            .context("Could not save challenge participants")?;
+        Ok(())
+    }
+
+    /// Everyone's contributions added up.
+    pub fn progress(context: &GraphQLContext, challenge: &Challenge) -> Result<i32> {
+        Ok(Self::contributions(context, challenge)?
+            .iter()
+            .fold(0, |total, contribution| {
+                total.saturating_add(contribution.value)
+            }))
+    }
+
+    /// What each participant contributed, biggest first; participants who did nothing
+    /// yet are listed with 0.
+    pub fn contributions(
+        context: &GraphQLContext,
+        challenge: &Challenge,
+    ) -> Result<Vec<ChallengeContribution>> {
+        let challenge_id = challenge.id.context("Challenge has no id")?;
+        let mut conn = get_conn(context)?;
+        let participants = Self::participants_in(&mut conn, challenge_id)?;
+        let user_ids: Vec<i32> = participants.iter().filter_map(|user| user.id).collect();
+
+        let mut query = chore_completions::table
+            .filter(chore_completions::approved.eq(true))
+            .filter(chore_completions::user_id.eq_any(&user_ids))
+            .filter(
+                chore_completions::completed_date.between(challenge.start_date, challenge.end_date),
+            )
+            .into_boxed();
+        if let Some(chore_id) = challenge.chore_id {
+            query = query.filter(chore_completions::chore_id.eq(chore_id));
+        }
+        let rows: Vec<(i32, i32, i32)> = query
+            .select((
+                chore_completions::user_id,
+                chore_completions::amount_cents,
+                chore_completions::points,
+            ))
+            .load(&mut conn)
+            .context("Could not load challenge completions")?;
+
+        let metric = ChallengeMetric::from(&challenge.metric);
+        let mut contributions: Vec<ChallengeContribution> = participants
+            .into_iter()
+            .map(|user| {
+                let value = rows
+                    .iter()
+                    .filter(|&&(user_id, _, _)| Some(user_id) == user.id)
+                    .fold(0i32, |total, &(_, amount_cents, points)| {
+                        total.saturating_add(match metric {
+                            ChallengeMetric::Completions => 1,
+                            ChallengeMetric::EarnedCents => amount_cents,
+                            ChallengeMetric::Points => points,
+                        })
+                    });
+                ChallengeContribution { user, value }
+            })
+            .collect();
+        contributions.sort_by_key(|c| std::cmp::Reverse(c.value));
+        Ok(contributions)
+    }
+
+    pub fn status(challenge: &Challenge, today: NaiveDate) -> ChallengeStatus {
+        if challenge.completed_at.is_some() {
+            ChallengeStatus::Completed
+        } else if today < challenge.start_date {
+            ChallengeStatus::Upcoming
+        } else if today > challenge.end_date {
+            ChallengeStatus::Missed
+        } else {
+            ChallengeStatus::Active
+        }
+    }
+
+    /// Checks the open challenges `user_id` takes part in, e.g. after one of their
+    /// completions was approved.
+    pub fn check_for_user(context: &GraphQLContext, user_id: i32) -> Result<()> {
+        for challenge in Self::list(context, Some(user_id))? {
+            if challenge.completed_at.is_none() {
+                Self::check_completed(context, &challenge)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks the challenge completed when progress met the target, raising an event the
+    /// first time.
+    fn check_completed(context: &GraphQLContext, challenge: &Challenge) -> Result<()> {
+        if challenge.completed_at.is_some()
+            || Self::progress(context, challenge)? < challenge.target
+        {
+            return Ok(());
+        }
+        // Only the first caller to mark it raises the event
+        let marked = diesel::update(challenges::table)
+            .filter(challenges::id.eq(challenge.id))
+            .filter(challenges::completed_at.is_null())
+            .set(challenges::completed_at.eq(Utc::now().naive_utc()))
+            .execute(&mut get_conn(context)?)
+            .context("Could not mark challenge as completed")?;
+        if let (1, Some(challenge_id)) = (marked, challenge.id) {
+            NotificationSvc::notify(
+                context,
+                &NotificationEvent::ChallengeCompleted { challenge_id },
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChallengeInput, PaymentType},
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    fn challenge(metric: ChallengeMetric, target: i32) -> Challenge {
+        ChallengeInput {
+            uuid: None,
+            name: "Pizza night".to_owned(),
+            description: None,
+            prize: Some("Pizza".to_owned()),
+            metric,
+            target,
+            start_date: create_test_date(2026, 4, 1),
+            end_date: create_test_date(2026, 4, 30),
+            chore_id: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_challenge_completes_when_participants_reach_the_target() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let alice = create_test_user(&context, "Alice").id.unwrap();
+        let bob = create_test_user(&context, "Bob").id.unwrap();
+        let outsider = create_test_user(&context, "Carol").id.unwrap();
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        let pizza = ChallengeSvc::create(
+            &context,
+            &challenge(ChallengeMetric::Completions, 3),
+            &[alice, bob],
+        )
+        .unwrap();
+        assert!(
+            ChallengeSvc::create(&context, &challenge(ChallengeMetric::Points, 3), &[]).is_err()
+        );
+
+        let complete = |user_id: i32, (month, day): (u32, u32)| {
+            create_approved_test_completion(
+                &context,
+                chore.id.unwrap(),
+                user_id,
+                create_test_date(2026, month, day),
+                admin_id,
+            );
+        };
+        complete(alice, (4, 6));
+        complete(alice, (4, 7));
+        // Outside the date range, and not a participant
+        complete(bob, (5, 1));
+        complete(outsider, (4, 8));
+
+        let pizza = ChallengeSvc::get(&context, &pizza.uuid).unwrap();
+        assert_eq!(ChallengeSvc::progress(&context, &pizza).unwrap(), 2);
+        assert_eq!(
+            ChallengeSvc::status(&pizza, create_test_date(2026, 4, 10)),
+            ChallengeStatus::Active
+        );
+        assert_eq!(
+            ChallengeSvc::status(&pizza, create_test_date(2026, 5, 1)),
+            ChallengeStatus::Missed
+        );
+
+        complete(bob, (4, 9));
+        let pizza = ChallengeSvc::get(&context, &pizza.uuid).unwrap();
+        assert!(pizza.completed_at.is_some());
+        assert_eq!(
+            ChallengeSvc::status(&pizza, create_test_date(2026, 5, 1)),
+            ChallengeStatus::Completed
+        );
+        let contributions = ChallengeSvc::contributions(&context, &pizza).unwrap();
+        assert_eq!(
+            contributions
+                .iter()
+                .map(|c| (c.user.name.as_str(), c.value))
+                .collect::<Vec<_>>(),
+            vec![("Alice", 2), ("Bob", 1)]
+        );
+        assert_eq!(
+            ChallengeSvc::list(&context, Some(outsider)).unwrap().len(),
+            0
+        );
+        assert_eq!(ChallengeSvc::list(&context, Some(bob)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_challenge_metrics_and_chore_filter() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            150,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        let laundry = create_test_chore(
+            &context,
+            "Laundry",
+            PaymentType::Daily,
+            200,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        for chore in [&dishes, &laundry] {
+            create_approved_test_completion(
+                &context,
+                chore.id.unwrap(),
+                user_id,
+                create_test_date(2026, 4, 6),
+                admin_id,
+            );
+        }
+
+        let earned = ChallengeSvc::create(
+            &context,
+            &challenge(ChallengeMetric::EarnedCents, 1000),
+            &[user_id],
+        )
+        .unwrap();
+        assert_eq!(ChallengeSvc::progress(&context, &earned).unwrap(), 350);
+
+        let dishes_only = ChallengeSvc::update(
+            &context,
+            &Challenge {
+                uuid: earned.uuid,
+                chore_id: dishes.id,
+                ..challenge(ChallengeMetric::EarnedCents, 150)
+            },
+            &[user_id, user_id],
+        )
+        .unwrap();
+        assert_eq!(ChallengeSvc::progress(&context, &dishes_only).unwrap(), 150);
+        assert!(dishes_only.completed_at.is_some());
+        assert_eq!(
+            ChallengeSvc::participants(&context, dishes_only.id.unwrap())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
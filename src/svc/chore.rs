@@ -1,11 +1,12 @@
 use crate::{
     context::GraphQLContext,
     db::get_conn,
-    models::{Chore, ChoreAssignment},
-    schema::{chore_assignments, chore_completions, chores, users},
+    models::{Chore, ChoreAssignment, ChoreChecklistItem},
+    schema::{chore_assignments, chore_checklist_items, chores, users},
+    svc::BonusClaimSvc,
 };
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use diesel::prelude::*;
 
 pub struct ChoreSvc {}
@@ -85,12 +86,44 @@ impl ChoreSvc {
         Self::get(context, &chore.uuid)
     }
 
+    /// Saves the chore and, when it is switched on or off, when that happened, so
+    /// completion stats only expect it while it was active.
     pub fn update(context: &GraphQLContext, chore: &Chore) -> Result<Chore> {
-        diesel::update(chores::table)
-            .filter(chores::uuid.eq(&chore.uuid))
-            .set(chore)
-            .execute(&mut get_conn(context)?)
-            .context("Could not update chore")?;
+        get_conn(context)?.transaction(|conn| {
+            let previous: Option<(bool, Option<NaiveDateTime>)> = chores::table
+                .filter(chores::uuid.eq(&chore.uuid))
+                .select((chores::active, chores::activated_at))
+                .first(conn)
+                .optional()
+                .context("Could not load chore")?;
+
+            diesel::update(chores::table)
+                .filter(chores::uuid.eq(&chore.uuid))
+                .set(chore)
+                .execute(conn)
+                .context("Could not update chore")?;
+
+            if let Some((was_active, activated_at)) = previous
+                && was_active != chore.active
+            {
+                let now = Some(Utc::now().naive_utc());
+                let (activated_at, deactivated_at) = if chore.active {
+                    (now, None)
+                } else {
+                    (activated_at, now)
+                };
+                diesel::update(chores::table)
+                    .filter(chores::uuid.eq(&chore.uuid))
+                    .set((
+                        chores::activated_at.eq(activated_at),
+                        chores::deactivated_at.eq(deactivated_at),
+                    ))
+                    .execute(conn)
+                    .context("Could not update chore activity")?;
+            }
+
+            anyhow::Ok(())
+        })?;
 
         Self::get(context, &chore.uuid)
     }
@@ -170,21 +203,68 @@ impl ChoreSvc {
             .context("Could not load bonus chores")
     }
 
+    /// Whether a new claim could be taken on this bonus chore right now. Active and
+    /// fulfilled claims count toward `max_claims`; expired ones are released first.
     pub fn can_claim_bonus(context: &GraphQLContext, chore_id: i32) -> Result<bool> {
         let chore = Self::get_by_id(context, chore_id)?;
 
         match chore.max_claims {
             None => Ok(true), // unlimited
-            Some(cap) => {
-                let count: i64 = chore_completions::table
-                    .filter(chore_completions::chore_id.eq(chore_id))
-                    .count()
-                    .get_result(&mut get_conn(context)?)
-                    .context("Could not count chore completions")?;
-                Ok(count < cap as i64)
-            }
+            Some(cap) => Ok(BonusClaimSvc::held_count(context, chore_id)? < cap),
         }
     }
+
+    pub fn get_checklist(context: &GraphQLContext, chore_id: i32) -> Result<Vec<String>> {
+        chore_checklist_items::table
+            .filter(chore_checklist_items::chore_id.eq(chore_id))
+            .order_by(chore_checklist_items::position.asc())
+            .select(chore_checklist_items::item_text)
+            .load(&mut get_conn(context)?)
+            .context("Could not load chore checklist")
+    }
+
+    pub fn set_checklist(
+        context: &GraphQLContext,
+        chore_id: i32,
+        items: &[String],
+    ) -> Result<Vec<String>> {
+        get_conn(context)?
+            .immediate_transaction(|conn| Self::replace_checklist(conn, chore_id, items))?;
+
+        Self::get_checklist(context, chore_id)
+    }
+
+    /// Replaces the chore's checklist with `items`, keeping their order. Blank entries are dropped.
+    pub(crate) fn replace_checklist(
+        conn: &mut SqliteConnection,
+        chore_id: i32,
+        items: &[String],
+    ) -> Result<()> {
+        diesel::delete(chore_checklist_items::table)
+            .filter(chore_checklist_items::chore_id.eq(chore_id))
+            .execute(conn)
+            .context("Could not clear chore checklist")?;
+
+        let rows: Vec<ChoreChecklistItem> = items
+            .iter()
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .zip(0..)
+            .map(|(item, position)| ChoreChecklistItem {
+                id: None,
+                chore_id,
+                position,
+                item_text: item.to_owned(),
+            })
+            .collect();
+
+        diesel::insert_into(chore_checklist_items::table)
+            .values(&rows)
+            .execute(conn)
+            .context("Could not save chore checklist")?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -244,6 +324,9 @@ mod tests {
             updated_at: chore.updated_at,
             bonus_date: None,
             max_claims: None,
+            points: chore.points,
+            activated_at: chore.activated_at,
+            deactivated_at: chore.deactivated_at,
         };
 
         let result = ChoreSvc::update(&context, &updated_chore).unwrap();
@@ -283,6 +366,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: None,
             max_claims: None,
+            points: None,
         };
         let chore2 = Chore::from(chore2_input);
         let _chore2 = ChoreSvc::create(&context, &chore2).unwrap();
@@ -404,6 +488,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: None,
             max_claims: None,
+            points: None,
         };
         let chore3 = Chore::from(chore3_input);
         let chore3 = ChoreSvc::create(&context, &chore3).unwrap();
@@ -458,6 +543,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(target_date),
             max_claims: None,
+            points: None,
         };
         let bonus_chore_raw = Chore::from(bonus_input);
         let bonus_chore = ChoreSvc::create(&context, &bonus_chore_raw).unwrap();
@@ -484,6 +570,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(other_date),
             max_claims: None,
+            points: None,
         };
         let other_raw = Chore::from(other_input);
         ChoreSvc::create(&context, &other_raw).unwrap();
@@ -509,6 +596,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(NaiveDate::from_ymd_opt(2026, 4, 15).unwrap()),
             max_claims: None,
+            points: None,
         };
         let chore_raw = Chore::from(input);
         let chore = ChoreSvc::create(&context, &chore_raw).unwrap();
@@ -533,6 +621,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(NaiveDate::from_ymd_opt(2026, 4, 15).unwrap()),
             max_claims: Some(2),
+            points: None,
         };
         let chore_raw = Chore::from(input);
         let chore = ChoreSvc::create(&context, &chore_raw).unwrap();
@@ -561,6 +650,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(NaiveDate::from_ymd_opt(2026, 4, 15).unwrap()),
             max_claims: Some(1),
+            points: None,
         };
         let chore_raw = Chore::from(input);
         let chore = ChoreSvc::create(&context, &chore_raw).unwrap();
@@ -597,6 +687,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(target_date),
             max_claims: None,
+            points: None,
         };
         let chore_raw = Chore::from(input);
         ChoreSvc::create(&context, &chore_raw).unwrap();
@@ -639,4 +730,44 @@ mod tests {
         let result = ChoreSvc::unassign_user(&context, chore.id.unwrap(), user.id.unwrap());
         assert!(result.is_ok()); // Should not error even if assignment doesn't exist
     }
+
+    #[test]
+    fn test_chore_checklist_replace_keeps_order() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let chore = create_test_chore(
+            &context,
+            "Clean Room",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let chore_id = chore.id.unwrap();
+
+        assert!(
+            ChoreSvc::get_checklist(&context, chore_id)
+                .unwrap()
+                .is_empty()
+        );
+
+        let items = vec![
+            "Make bed".to_owned(),
+            "  ".to_owned(),
+            "Put away toys".to_owned(),
+        ];
+        let saved = ChoreSvc::set_checklist(&context, chore_id, &items).unwrap();
+        assert_eq!(saved, vec!["Make bed", "Put away toys"]);
+
+        let saved = ChoreSvc::set_checklist(&context, chore_id, &["Vacuum".to_owned()]).unwrap();
+        assert_eq!(saved, vec!["Vacuum"]);
+
+        // Deleting the chore removes its checklist
+        ChoreSvc::delete(&context, &chore.uuid).unwrap();
+        assert!(
+            ChoreSvc::get_checklist(&context, chore_id)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -4,12 +4,62 @@ use crate::{
     db::get_conn,
     models::{ChoreCompletion, ChoreCompletionInput, PaymentType, User},
     schema::{chore_completions, users},
-    svc::{BadgeSvc, ChoreSvc},
+    svc::{
+        BadgeSvc, BonusClaimSvc, ChallengeSvc, ChoreSvc, JarSvc, NotificationSvc, RewardSvc,
+        SavingsGoalSvc, StreakSvc, bonus_claim::ClaimError, notification::NotificationEvent,
+    },
 };
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use chrono::{NaiveDate, Utc};
-use diesel::prelude::*;
+use diesel::{prelude::*, sqlite::Sqlite};
 use juniper::GraphQLInputObject;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// Expected rejections when submitting a completion. Surfaced to GraphQL clients with a
+/// stable `code` extension so the UI can tell them apart from unexpected failures.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CompletionError {
+    ClaimLimitReached,
+    DuplicateCompletion,
+}
+
+impl CompletionError {
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::ClaimLimitReached => "CLAIM_LIMIT_REACHED",
+            Self::DuplicateCompletion => "DUPLICATE_COMPLETION",
+        }
+    }
+}
+
+impl fmt::Display for CompletionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClaimLimitReached => {
+                write!(f, "This bonus chore has already reached its claim limit")
+            }
+            Self::DuplicateCompletion => {
+                write!(
+                    f,
+                    "This chore was already completed by this user on that date"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompletionError {}
+
+/// A full bonus chore rejects the completion, not just the claim it needed.
+fn claim_limit_as_completion_error(err: anyhow::Error) -> anyhow::Error {
+    match err.downcast_ref::<ClaimError>() {
+        Some(ClaimError::LimitReached) => CompletionError::ClaimLimitReached.into(),
+        None => err,
+    }
+}
 
 #[derive(Debug, Copy, Clone, Default, GraphQLInputObject)]
 pub struct ChoreCompletionFilter {
@@ -35,7 +85,7 @@ impl ChoreCompletionSvc {
             .context("Could not find chore completion")
     }
 
-    const MAX_COMPLETION_LIMIT: i32 = 1000;
+    pub(crate) const MAX_COMPLETION_LIMIT: i32 = 1000;
 
     pub fn list(
         context: &GraphQLContext,
@@ -48,6 +98,48 @@ impl ChoreCompletionSvc {
             .into();
         let offset: i64 = filter.offset.unwrap_or_default().into();
 
+        Self::filtered(filter)
+            .select(ChoreCompletion::as_select())
+            .order_by(chore_completions::completed_date.desc())
+            .limit(limit)
+            .offset(offset)
+            .load::<ChoreCompletion>(&mut get_conn(context)?)
+            .context("Could not load chore completions")
+    }
+
+    /// Every completion matching `filter` after the `(completed_date, id)` cursor, oldest
+    /// first. Unlike `list` there is no row cap and `limit`/`offset` in the filter are
+    /// ignored; callers page through by passing the last row's cursor back in.
+    pub fn list_after(
+        context: &GraphQLContext,
+        filter: &ChoreCompletionFilter,
+        after: Option<(NaiveDate, i32)>,
+        page_size: i64,
+    ) -> Result<Vec<ChoreCompletion>> {
+        let mut query = Self::filtered(filter);
+        if let Some((date, id)) = after {
+            query = query.filter(
+                chore_completions::completed_date
+                    .gt(date)
+                    .or(chore_completions::completed_date
+                        .eq(date)
+                        .and(chore_completions::id.gt(id))),
+            );
+        }
+
+        query
+            .select(ChoreCompletion::as_select())
+            .order_by((
+                chore_completions::completed_date.asc(),
+                chore_completions::id.asc(),
+            ))
+            .limit(page_size)
+            .load::<ChoreCompletion>(&mut get_conn(context)?)
+            .context("Could not load chore completions")
+    }
+
+    /// The filter's user, chore, date and status conditions; its paging is left to callers.
+    fn filtered(filter: &ChoreCompletionFilter) -> chore_completions::BoxedQuery<'static, Sqlite> {
         let mut query = chore_completions::table.into_boxed();
 
         // Filter: target user / chore / date range
@@ -81,12 +173,6 @@ impl ChoreCompletionSvc {
         }
 
         query
-            .select(ChoreCompletion::as_select())
-            .order_by(chore_completions::completed_date.desc())
-            .limit(limit)
-            .offset(offset)
-            .load::<ChoreCompletion>(&mut get_conn(context)?)
-            .context("Could not load chore completions")
     }
 
     /// Shared loader for weekly completion queries; pass `user_id = Some(id)` to
@@ -130,7 +216,7 @@ impl ChoreCompletionSvc {
     }
 
     pub fn get_unpaid_totals(context: &GraphQLContext) -> Result<Vec<(User, i32)>> {
-        let results: Vec<(User, Option<i64>)> = users::table
+        let mut results: Vec<(User, Option<i64>)> = users::table
             .left_join(chore_completions::table)
             .filter(
                 // Include users with no completions (LEFT JOIN null case, detected via id),
@@ -149,9 +235,35 @@ impl ChoreCompletionSvc {
             .load(&mut get_conn(context)?)
             .context("Could not load unpaid totals")?;
 
+        // Approved cents rewards come off what is owed until payouts settle them. Whatever
+        // they exceed it by is carried forward, so nobody is owed less than nothing.
+        let redeemed = RewardSvc::unsettled_cents(context)?;
+        let listed: HashSet<i32> = results.iter().filter_map(|(user, _)| user.id).collect();
+        let only_redeemed: Vec<i32> = redeemed
+            .keys()
+            .copied()
+            .filter(|user_id| !listed.contains(user_id))
+            .collect();
+        if !only_redeemed.is_empty() {
+            let users: Vec<User> = users::table
+                .filter(users::id.eq_any(only_redeemed))
+                .select(User::as_select())
+                .load(&mut get_conn(context)?)
+                .context("Could not load users with unsettled rewards")?;
+            results.extend(users.into_iter().map(|user| (user, None)));
+            results.sort_by_key(|(user, _)| user.id);
+        }
+
         let converted_results = results
             .into_iter()
-            .map(|(user, total)| (user, i32::try_from(total.unwrap_or(0)).unwrap_or(i32::MAX)))
+            .map(|(user, total)| {
+                let redeemed = user
+                    .id
+                    .and_then(|id| redeemed.get(&id).copied())
+                    .unwrap_or(0);
+                let total = (total.unwrap_or(0) - redeemed).max(0);
+                (user, i32::try_from(total).unwrap_or(i32::MAX))
+            })
             .collect();
 
         Ok(converted_results)
@@ -163,16 +275,7 @@ impl ChoreCompletionSvc {
     ) -> Result<ChoreCompletion> {
         // Get the chore to calculate the correct payment amount
         let chore = ChoreSvc::get_by_id(context, completion_input.chore_id)?;
-
-        // Guard: if this is a bonus chore with max_claims, check the cap
-        if chore.bonus_date.is_some()
-            && !ChoreSvc::can_claim_bonus(context, completion_input.chore_id)?
-        {
-            return Err(anyhow::anyhow!(
-                "This bonus chore has already reached its claim limit"
-            ));
-        }
-        let payment_type = PaymentType::from(chore.payment_type);
+        let payment_type = PaymentType::from(&chore.payment_type);
 
         // Calculate the appropriate amount based on chore payment type
         let calculated_amount = PaymentType::calculate_completion_amount(
@@ -180,6 +283,11 @@ impl ChoreCompletionSvc {
             chore.amount_cents,
             chore.required_days,
         );
+        let calculated_points = PaymentType::calculate_completion_points(
+            &payment_type,
+            chore.points,
+            chore.required_days,
+        );
 
         // Create the completion with calculated amount
         let completion = ChoreCompletion {
@@ -196,12 +304,54 @@ impl ChoreCompletionSvc {
             paid_out_at: None,
             created_at: None,
             updated_at: None,
+            points: calculated_points,
         };
 
-        diesel::insert_into(chore_completions::table)
-            .values(&completion)
-            .execute(&mut get_conn(context)?)
-            .context("Could not create chore completion")?;
+        // The duplicate check, the bonus claim and the insert share one IMMEDIATE
+        // transaction: SQLite takes the write lock up front, so concurrent submissions
+        // are serialized and cannot both pass the checks.
+        let completion_id = get_conn(context)?.immediate_transaction(|conn| {
+            let existing: i64 = chore_completions::table
+                .filter(chore_completions::user_id.eq(completion.user_id))
+                .filter(chore_completions::chore_id.eq(completion.chore_id))
+                .filter(chore_completions::completed_date.eq(completion.completed_date))
+                .count()
+                .get_result(conn)
+                .context("Could not check for an existing chore completion")?;
+            if existing > 0 {
+                return Err(CompletionError::DuplicateCompletion.into());
+            }
+
+            // A bonus chore completion must consume a claim. Reuse the user's active
+            // claim, or take a free slot now (fails once max_claims slots are held).
+            let claim = if chore.bonus_date.is_some() {
+                let claim = BonusClaimSvc::acquire_in(conn, &chore, completion.user_id)
+                    .map_err(claim_limit_as_completion_error)?;
+                Some(claim)
+            } else {
+                None
+            };
+
+            let completion_id: Option<i32> = diesel::insert_into(chore_completions::table)
+                .values(&completion)
+                .returning(chore_completions::id)
+                .get_result(conn)
+                .context("Could not create chore completion")?;
+
+            if let (Some(claim_id), Some(completion_id)) = (claim.and_then(|c| c.id), completion_id)
+            {
+                BonusClaimSvc::fulfill(conn, claim_id, completion_id)?;
+            }
+
+            Ok::<_, anyhow::Error>(completion_id)
+        })?;
+
+        if let Some(completion_id) = completion_id {
+            NotificationSvc::notify(
+                context,
+                &NotificationEvent::CompletionSubmitted { completion_id },
+            );
+        }
 
         Self::get(context, &completion.uuid)
     }
@@ -211,65 +361,192 @@ impl ChoreCompletionSvc {
         completion_uuid: &str,
         admin_id: i32,
     ) -> Result<ChoreCompletion> {
-        diesel::update(chore_completions::table)
-            .filter(chore_completions::uuid.eq(completion_uuid))
-            .set((
-                chore_completions::approved.eq(true),
-                chore_completions::approved_by_admin_id.eq(admin_id),
-                chore_completions::approved_at.eq(Utc::now().naive_utc()),
-            ))
-            .execute(&mut get_conn(context)?)
-            .context("Could not approve chore completion")?;
-
-        let completion = Self::get(context, completion_uuid)?;
+        let completion = get_conn(context)?.immediate_transaction(|conn| {
+            let approved: bool = chore_completions::table
+                .filter(chore_completions::uuid.eq(completion_uuid))
+                .select(chore_completions::approved)
+                .first(conn)
+                .context("Could not find chore completion")?;
+            // Approving again would move `approved_at` (and with it the statement month)
+            // and repeat every notification
+            if approved {
+                bail!("This completion has already been approved");
+            }
+            diesel::update(chore_completions::table)
+                .filter(chore_completions::uuid.eq(completion_uuid))
+                .set((
+                    chore_completions::approved.eq(true),
+                    chore_completions::approved_by_admin_id.eq(admin_id),
+                    chore_completions::approved_at.eq(Utc::now().naive_utc()),
+                ))
+                .execute(conn)
+                .context("Could not approve chore completion")?;
+            let completion: ChoreCompletion = chore_completions::table
+                .filter(chore_completions::uuid.eq(completion_uuid))
+                .select(ChoreCompletion::as_select())
+                .first(conn)
+                .context("Could not find chore completion")?;
+            JarSvc::credit_completion_in(conn, &completion)?;
+            anyhow::Ok(completion)
+        })?;
+        if let Some(completion_id) = completion.id {
+            NotificationSvc::notify(
+                context,
+                &NotificationEvent::CompletionApproved { completion_id },
+            );
+        }
+        // A late approval can complete a day the streak already counted as missed
+        if let Err(e) =
+            StreakSvc::invalidate(context, Some(completion.user_id), completion.completed_date)
+        {
+            tracing::warn!("Could not rewind streak: {:?}", e);
+        }
         BadgeSvc::check_and_award(context, completion.user_id);
+        if let Err(e) = SavingsGoalSvc::check_reached(context, completion.user_id) {
+            tracing::warn!("Could not check savings goals: {:?}", e);
+        }
+        if let Err(e) = ChallengeSvc::check_for_user(context, completion.user_id) {
+            tracing::warn!("Could not check challenges: {:?}", e);
+        }
         Ok(completion)
     }
 
-    pub fn mark_as_paid(context: &GraphQLContext, user_id: Option<i32>) -> Result<()> {
-        let mut query = diesel::update(chore_completions::table).into_boxed();
-
-        query = query
-            .filter(chore_completions::approved.eq(true))
-            .filter(chore_completions::paid_out.eq(false));
-
-        if let Some(user_id) = user_id {
-            query = query.filter(chore_completions::user_id.eq(user_id));
+    /// Rejects a completion that is still waiting for approval: it is deleted (releasing any
+    /// bonus slot it used) and a rejection event is raised. Approved completions can only be
+    /// deleted.
+    pub fn reject(context: &GraphQLContext, completion_uuid: &str) -> Result<()> {
+        let completion = Self::get(context, completion_uuid)?;
+        if completion.approved {
+            bail!("Only completions waiting for approval can be rejected");
         }
-
-        query
-            .set((
-                chore_completions::paid_out.eq(true),
-                chore_completions::paid_out_at.eq(Utc::now().naive_utc()),
-            ))
-            .execute(&mut get_conn(context)?)
-            .context("Could not mark completions as paid")?;
-
+        Self::delete(context, completion_uuid)?;
+
+        NotificationSvc::notify(
+            context,
+            &NotificationEvent::CompletionRejected {
+                completion_uuid: completion.uuid,
+                user_id: completion.user_id,
+                chore_id: completion.chore_id,
+                completed_date: completion.completed_date,
+            },
+        );
         Ok(())
     }
 
+    pub fn mark_as_paid(context: &GraphQLContext, user_id: Option<i32>) -> Result<()> {
+        Self::pay_out(context, user_id.map(|user_id| vec![user_id]))
+    }
+
     /// Marks all approved, unpaid completions for the given users as paid in a single query.
     pub fn mark_as_paid_batch(context: &GraphQLContext, user_ids: &[i32]) -> Result<()> {
-        diesel::update(chore_completions::table)
-            .filter(chore_completions::approved.eq(true))
-            .filter(chore_completions::paid_out.eq(false))
-            .filter(chore_completions::user_id.eq_any(user_ids))
-            .set((
-                chore_completions::paid_out.eq(true),
-                chore_completions::paid_out_at.eq(Utc::now().naive_utc()),
-            ))
-            .execute(&mut get_conn(context)?)
-            .context("Could not mark completions as paid")?;
+        Self::pay_out(context, Some(user_ids.to_vec()))
+    }
+
+    /// Marks approved, unpaid completions as paid (for every user when `user_ids` is
+    /// `None`) and raises a payout event per user with what was paid. The payout is booked
+    /// into the user's jars, which can reach savings goals. Approved cents rewards are
+    /// settled by the same payout, as far as it and the spend jar cover them, and netted
+    /// out of it.
+    fn pay_out(context: &GraphQLContext, user_ids: Option<Vec<i32>>) -> Result<()> {
+        let now = Utc::now().naive_utc();
+        let payouts = get_conn(context)?.immediate_transaction(|conn| {
+            let mut totals = chore_completions::table
+                .filter(chore_completions::approved.eq(true))
+                .filter(chore_completions::paid_out.eq(false))
+                .group_by(chore_completions::user_id)
+                .select((
+                    chore_completions::user_id,
+                    diesel::dsl::sum(chore_completions::amount_cents),
+                    diesel::dsl::count(chore_completions::id),
+                ))
+                .into_boxed();
+            let mut update = diesel::update(chore_completions::table)
+                .filter(chore_completions::approved.eq(true))
+                .filter(chore_completions::paid_out.eq(false))
+                .into_boxed();
+            if let Some(user_ids) = &user_ids {
+                totals = totals.filter(chore_completions::user_id.eq_any(user_ids));
+                update = update.filter(chore_completions::user_id.eq_any(user_ids));
+            }
+
+            let payouts: Vec<(i32, Option<i64>, i64)> = totals
+                .load(conn)
+                .context("Could not total unpaid completions")?;
+            update
+                .set((
+                    chore_completions::paid_out.eq(true),
+                    chore_completions::paid_out_at.eq(now),
+                ))
+                .execute(conn)
+                .context("Could not mark completions as paid")?;
+            let mut budgets = HashMap::new();
+            for &(user_id, amount_cents, _) in &payouts {
+                JarSvc::credit_payout_in(conn, user_id, now)?;
+                // Rewards come out of the spend jar, so it bounds what can be settled
+                let spend_cents = i64::from(JarSvc::balances_in(conn, user_id)?.spend_cents);
+                budgets.insert(user_id, amount_cents.unwrap_or(0).min(spend_cents).max(0));
+            }
+            let redeemed = RewardSvc::settle_cents_in(conn, &budgets, now)?;
+
+            payouts
+                .into_iter()
+                .map(|(user_id, amount_cents, completion_count)| {
+                    let redeemed = redeemed.get(&user_id).copied().unwrap_or(0);
+                    let jars = JarSvc::debit_rewards_in(conn, user_id, redeemed, now)?;
+                    Ok((
+                        user_id,
+                        amount_cents.unwrap_or(0) - redeemed,
+                        completion_count,
+                        jars,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+
+        for (user_id, amount_cents, completion_count, jars) in payouts {
+            NotificationSvc::notify(
+                context,
+                &NotificationEvent::PayoutMade {
+                    user_id,
+                    amount_cents: i32::try_from(amount_cents).unwrap_or(i32::MAX),
+                    completion_count: i32::try_from(completion_count).unwrap_or(i32::MAX),
+                    jars,
+                },
+            );
+            if let Err(e) = SavingsGoalSvc::check_reached(context, user_id) {
+                tracing::warn!("Could not check savings goals: {:?}", e);
+            }
+        }
 
         Ok(())
     }
 
     pub fn delete(context: &GraphQLContext, completion_uuid: &str) -> Result<()> {
-        diesel::delete(chore_completions::table)
-            .filter(chore_completions::uuid.eq(completion_uuid))
-            .execute(&mut get_conn(context)?)
-            .context("Could not delete chore completion")?;
-
+        let completion = Self::get(context, completion_uuid).ok();
+
+        get_conn(context)?.transaction(|conn| {
+            // Give any bonus slot this completion used back to the pool
+            BonusClaimSvc::release_for_completion(conn, completion_uuid)?;
+            // Take back what it put in the jars; its own entries stay as history
+            if let Some(completion_id) = completion.as_ref().and_then(|completion| completion.id) {
+                JarSvc::reverse_completion_in(conn, completion_id)?;
+            }
+
+            diesel::delete(chore_completions::table)
+                .filter(chore_completions::uuid.eq(completion_uuid))
+                .execute(conn)
+                .context("Could not delete chore completion")?;
+
+            anyhow::Ok(())
+        })?;
+
+        // The delete has committed, so a failed rewind must not be reported as a failed delete
+        if let Some(completion) = completion.filter(|completion| completion.approved)
+            && let Err(e) =
+                StreakSvc::invalidate(context, Some(completion.user_id), completion.completed_date)
+        {
+            tracing::warn!("Could not rewind streak: {:?}", e);
+        }
         Ok(())
     }
 }
@@ -719,6 +996,7 @@ mod tests {
             created_by_admin_id: admin.id.unwrap(),
             bonus_date: Some(today),
             max_claims: Some(1),
+            points: None,
         };
         let chore_raw = Chore::from(chore_input);
         let chore = ChoreSvc::create(&context, &chore_raw).unwrap();
@@ -755,6 +1033,183 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_duplicate_completion_is_rejected_with_typed_error() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let chore = create_test_chore(
+            &context,
+            "Make bed",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+
+        let input = ChoreCompletionInput {
+            uuid: None,
+            chore_id: chore.id.unwrap(),
+            user_id: user.id.unwrap(),
+            completed_date: create_test_date(2024, 10, 21),
+        };
+        ChoreCompletionSvc::create(&context, &input).unwrap();
+
+        let err = ChoreCompletionSvc::create(&context, &input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<CompletionError>(),
+            Some(&CompletionError::DuplicateCompletion)
+        );
+
+        // A different date is still allowed
+        let next_day = ChoreCompletionInput {
+            completed_date: create_test_date(2024, 10, 22),
+            ..input
+        };
+        assert!(ChoreCompletionSvc::create(&context, &next_day).is_ok());
+    }
+
+    #[test]
+    fn test_reject_only_pending_completions() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let chore = create_test_chore(
+            &context,
+            "Make bed",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let input = ChoreCompletionInput {
+            uuid: None,
+            chore_id: chore.id.unwrap(),
+            user_id: user.id.unwrap(),
+            completed_date: create_test_date(2024, 10, 21),
+        };
+
+        let pending = ChoreCompletionSvc::create(&context, &input).unwrap();
+        ChoreCompletionSvc::reject(&context, &pending.uuid).unwrap();
+        assert!(ChoreCompletionSvc::get(&context, &pending.uuid).is_err());
+
+        // The date is free again, but an approved completion cannot be rejected
+        let approved = ChoreCompletionSvc::create(&context, &input).unwrap();
+        ChoreCompletionSvc::approve(&context, &approved.uuid, admin.id.unwrap()).unwrap();
+        assert!(ChoreCompletionSvc::reject(&context, &approved.uuid).is_err());
+        assert!(ChoreCompletionSvc::get(&context, &approved.uuid).is_ok());
+    }
+
+    #[test]
+    fn test_approve_only_pending_completions() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let user = create_test_user(&context, "Test User");
+        let chore = create_test_chore(
+            &context,
+            "Make bed",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let input = ChoreCompletionInput {
+            uuid: None,
+            chore_id: chore.id.unwrap(),
+            user_id: user.id.unwrap(),
+            completed_date: create_test_date(2024, 10, 21),
+        };
+
+        let completion = ChoreCompletionSvc::create(&context, &input).unwrap();
+        let approved =
+            ChoreCompletionSvc::approve(&context, &completion.uuid, admin.id.unwrap()).unwrap();
+        assert!(
+            ChoreCompletionSvc::approve(&context, &completion.uuid, admin.id.unwrap()).is_err()
+        );
+
+        // Paid completions keep the approval they were paid under
+        ChoreCompletionSvc::mark_as_paid(&context, user.id).unwrap();
+        assert!(
+            ChoreCompletionSvc::approve(&context, &completion.uuid, admin.id.unwrap()).is_err()
+        );
+        let paid = ChoreCompletionSvc::get(&context, &completion.uuid).unwrap();
+        assert_eq!(paid.approved_at, approved.approved_at);
+        assert!(paid.paid_out);
+    }
+
+    #[test]
+    fn test_concurrent_bonus_submissions_respect_claim_limit() {
+        use crate::db::{build_pool_for_url, run_migrations};
+        use crate::models::{Chore, ChoreInput};
+
+        // A file-backed pool lets several connections race for the same slot, which the
+        // single-connection in-memory test pool cannot do.
+        let db_path =
+            std::env::temp_dir().join(format!("chore-tracker-{}.sqlite", uuid::Uuid::now_v7()));
+        let pool = build_pool_for_url(db_path.to_str().unwrap()).unwrap();
+        run_migrations(&mut pool.get().unwrap()).unwrap();
+        let context = GraphQLContext {
+            pool,
+            admin_id: None,
+        };
+
+        let admin = create_test_admin(&context, "Test Admin", "admin@test.com");
+        let chore_input = ChoreInput {
+            uuid: None,
+            name: "Rake leaves".to_owned(),
+            description: None,
+            payment_type: PaymentType::Daily,
+            amount_cents: 500,
+            required_days: 0,
+            active: Some(true),
+            created_by_admin_id: admin.id.unwrap(),
+            bonus_date: Some(create_test_date(2026, 4, 18)),
+            max_claims: Some(1),
+            points: None,
+        };
+        let chore = ChoreSvc::create(&context, &Chore::from(chore_input)).unwrap();
+        let chore_id = chore.id.unwrap();
+        let user_ids: Vec<i32> = (0..6)
+            .map(|i| create_test_user(&context, &format!("Kid {i}")).id.unwrap())
+            .collect();
+
+        // Spawn every submitter before joining any of them so they actually overlap.
+        let mut handles = Vec::new();
+        for user_id in user_ids {
+            let context = context.clone();
+            handles.push(std::thread::spawn(move || {
+                let input = ChoreCompletionInput {
+                    uuid: None,
+                    chore_id,
+                    user_id,
+                    completed_date: create_test_date(2026, 4, 18),
+                };
+                ChoreCompletionSvc::create(&context, &input)
+            }));
+        }
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        for err in results.iter().filter_map(|r| r.as_ref().err()) {
+            assert_eq!(
+                err.downcast_ref::<CompletionError>(),
+                Some(&CompletionError::ClaimLimitReached)
+            );
+        }
+
+        let filter = ChoreCompletionFilter {
+            chore_id: Some(chore_id),
+            ..Default::default()
+        };
+        assert_eq!(
+            ChoreCompletionSvc::list(&context, &filter).unwrap().len(),
+            1
+        );
+
+        drop(context);
+        let _ = std::fs::remove_file(db_path);
+    }
+
     #[test]
     fn test_rounding_to_nearest_quarter() {
         // Test various rounding scenarios
@@ -0,0 +1,510 @@
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{Chore, ChoreAssignment, ChoreTemplate, ChoreTemplateInput, PaymentType},
+    schema::{chore_assignments, chore_templates, chores},
+    svc::ChoreSvc,
+};
+use anyhow::{Context, Result, bail};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Version written to and accepted from exported template libraries.
+const LIBRARY_VERSION: u32 = 1;
+
+/// Shareable representation of a template library. Database ids and uuids are left out so
+/// a library exported from one household can be imported into another.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateLibrary {
+    version: u32,
+    templates: Vec<LibraryTemplate>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LibraryTemplate {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    payment_type: String,
+    amount_cents: i32,
+    required_days: i32,
+    #[serde(default)]
+    checklist: Vec<String>,
+    #[serde(default)]
+    points: i32,
+}
+
+impl From<ChoreTemplate> for LibraryTemplate {
+    fn from(template: ChoreTemplate) -> Self {
+        Self {
+            checklist: template.checklist_items(),
+            name: template.name,
+            description: template.description,
+            payment_type: template.payment_type,
+            amount_cents: template.amount_cents,
+            required_days: template.required_days,
+            points: template.points,
+        }
+    }
+}
+
+impl From<LibraryTemplate> for ChoreTemplateInput {
+    fn from(template: LibraryTemplate) -> Self {
+        Self {
+            uuid: None,
+            name: template.name,
+            description: template.description,
+            payment_type: PaymentType::from(&template.payment_type),
+            amount_cents: template.amount_cents,
+            required_days: template.required_days,
+            checklist: Some(template.checklist),
+            points: Some(template.points),
+        }
+    }
+}
+
+pub struct ChoreTemplateSvc {}
+
+impl ChoreTemplateSvc {
+    pub fn get(context: &GraphQLContext, template_uuid: &str) -> Result<ChoreTemplate> {
+        chore_templates::table
+            .filter(chore_templates::uuid.eq(template_uuid))
+            .select(ChoreTemplate::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find chore template")
+    }
+
+    pub fn list(context: &GraphQLContext, limit: i32, offset: i32) -> Result<Vec<ChoreTemplate>> {
+        chore_templates::table
+            .select(ChoreTemplate::as_select())
+            .order_by(chore_templates::name.asc())
+            .limit(limit.into())
+            .offset(offset.into())
+            .load(&mut get_conn(context)?)
+            .context("Could not load chore templates")
+    }
+
+    pub fn create(context: &GraphQLContext, template: &ChoreTemplate) -> Result<ChoreTemplate> {
+        Self::validate(template)?;
+        diesel::insert_into(chore_templates::table)
+            .values(template)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create chore template")?;
+
+        Self::get(context, &template.uuid)
+    }
+
+    pub fn update(context: &GraphQLContext, template: &ChoreTemplate) -> Result<ChoreTemplate> {
+        Self::validate(template)?;
+        diesel::update(chore_templates::table)
+            .filter(chore_templates::uuid.eq(&template.uuid))
+            .set(template)
+            .execute(&mut get_conn(context)?)
+            .context("Could not update chore template")?;
+
+        Self::get(context, &template.uuid)
+    }
+
+    fn validate(template: &ChoreTemplate) -> Result<()> {
+        if template.name.trim().is_empty() {
+            bail!("Chore template names cannot be empty");
+        }
+        if template.amount_cents < 0 {
+            bail!("Chore template '{}' has a negative amount", template.name);
+        }
+        if template.points < 0 {
+            bail!("Chore template '{}' has negative points", template.name);
+        }
+        if !(0..=0b111_1111).contains(&template.required_days) {
+            bail!(
+                "Chore template '{}' has invalid required days",
+                template.name
+            );
+        }
+        Ok(())
+    }
+
+    pub fn delete(context: &GraphQLContext, template_uuid: &str) -> Result<()> {
+        diesel::delete(chore_templates::table)
+            .filter(chore_templates::uuid.eq(template_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete chore template")?;
+
+        Ok(())
+    }
+
+    /// Creates a new chore from a template, copies its checklist and assigns it to
+    /// `user_ids`, all in one transaction so a failed assignment leaves no half-made chore.
+    pub fn instantiate(
+        context: &GraphQLContext,
+        template_uuid: &str,
+        admin_id: i32,
+        user_ids: &[i32],
+    ) -> Result<Chore> {
+        let template = Self::get(context, template_uuid)?;
+        let checklist = template.checklist_items();
+        let chore = Chore {
+            id: None,
+            uuid: Uuid::now_v7().to_string(),
+            name: template.name,
+            description: template.description,
+            payment_type: template.payment_type,
+            amount_cents: template.amount_cents,
+            required_days: template.required_days,
+            active: true,
+            created_by_admin_id: admin_id,
+            created_at: None,
+            updated_at: None,
+            bonus_date: None,
+            max_claims: None,
+            points: template.points,
+            activated_at: None,
+            deactivated_at: None,
+        };
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            let chore_id: Option<i32> = diesel::insert_into(chores::table)
+                .values(&chore)
+                .returning(chores::id)
+                .get_result(conn)
+                .context("Could not create chore from template")?;
+            let chore_id = chore_id.context("Created chore has no id")?;
+
+            ChoreSvc::replace_checklist(conn, chore_id, &checklist)?;
+
+            let mut user_ids = user_ids.to_vec();
+            user_ids.sort_unstable();
+            user_ids.dedup();
+            let assignments: Vec<ChoreAssignment> = user_ids
+                .into_iter()
+                .map(|user_id| ChoreAssignment {
+                    id: None,
+                    chore_id,
+                    user_id,
+                    created_at: None,
+                })
+                .collect();
+            diesel::insert_into(chore_assignments::table)
+                .values(&assignments)
+                .execute(conn)
+                .context("Could not assign users to chore")?;
+
+            anyhow::Ok(())
+        })?;
+
+        ChoreSvc::get(context, &chore.uuid)
+    }
+
+    /// Serializes every template into a shareable JSON library.
+    pub fn export_library(context: &GraphQLContext) -> Result<String> {
+        let templates = chore_templates::table
+            .select(ChoreTemplate::as_select())
+            .order_by(chore_templates::name.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load chore templates")?;
+
+        let library = TemplateLibrary {
+            version: LIBRARY_VERSION,
+            templates: templates.into_iter().map(LibraryTemplate::from).collect(),
+        };
+
+        serde_json::to_string_pretty(&library).context("Could not serialize chore templates")
+    }
+
+    /// Imports a JSON library produced by [`Self::export_library`]. Templates are matched by
+    /// name: existing ones are overwritten, the rest are created. Names that match more than
+    /// one template are rejected. Nothing is written unless the whole library is valid.
+    pub fn import_library(context: &GraphQLContext, library: &str) -> Result<Vec<ChoreTemplate>> {
+        let library: TemplateLibrary =
+            serde_json::from_str(library).context("Could not parse chore template library")?;
+
+        if library.version != LIBRARY_VERSION {
+            bail!(
+                "Unsupported chore template library version {}",
+                library.version
+            );
+        }
+        let mut templates: Vec<ChoreTemplate> = Vec::with_capacity(library.templates.len());
+        for template in library.templates {
+            // `PaymentType::from` falls back to daily, which would silently change the pay
+            if !matches!(
+                template.payment_type.to_lowercase().as_str(),
+                "daily" | "weekly"
+            ) {
+                bail!(
+                    "Chore template '{}' has unknown payment type '{}'",
+                    template.name,
+                    template.payment_type
+                );
+            }
+            let template = ChoreTemplate::from(ChoreTemplateInput::from(template));
+            Self::validate(&template)?;
+            if templates.iter().any(|other| other.name == template.name) {
+                bail!(
+                    "The library has more than one chore template named '{}'",
+                    template.name
+                );
+            }
+            templates.push(template);
+        }
+
+        let uuids = get_conn(context)?.immediate_transaction(|conn| {
+            let mut uuids = Vec::with_capacity(templates.len());
+
+            for mut template in templates {
+                // Names are not unique; overwriting whichever one comes first would be a guess
+                let existing_uuids: Vec<String> = chore_templates::table
+                    .filter(chore_templates::name.eq(&template.name))
+                    .select(chore_templates::uuid)
+                    .limit(2)
+                    .load(conn)
+                    .context("Could not look up chore template")?;
+                if existing_uuids.len() > 1 {
+                    bail!(
+                        "More than one chore template is named '{}'; rename them before importing",
+                        template.name
+                    );
+                }
+
+                if let Some(existing_uuid) = existing_uuids.into_iter().next() {
+                    template.uuid = existing_uuid;
+                    diesel::update(chore_templates::table)
+                        .filter(chore_templates::uuid.eq(&template.uuid))
+                        .set(&template)
+                        .execute(conn)
+                        .context("Could not update chore template")?;
+                } else {
+                    diesel::insert_into(chore_templates::table)
+                        .values(&template)
+                        .execute(conn)
+                        .context("Could not create chore template")?;
+                }
+                uuids.push(template.uuid);
+            }
+
+            anyhow::Ok(uuids)
+        })?;
+
+        uuids
+            .iter()
+            .map(|template_uuid| Self::get(context, template_uuid))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::test_db::{
+        create_test_admin, create_test_context, create_test_user, day_patterns,
+    };
+
+    fn template_input(name: &str, checklist: &[&str]) -> ChoreTemplateInput {
+        ChoreTemplateInput {
+            uuid: None,
+            name: name.to_owned(),
+            description: Some(format!("{name} description")),
+            payment_type: PaymentType::Daily,
+            amount_cents: 150,
+            required_days: day_patterns::weekdays(),
+            checklist: Some(checklist.iter().map(|&item| item.to_owned()).collect()),
+            points: Some(5),
+        }
+    }
+
+    #[test]
+    fn test_template_crud_operations() {
+        let context = create_test_context();
+
+        let template = ChoreTemplateSvc::create(
+            &context,
+            &template_input("Feed Pets", &["Fill bowl", "Refresh water"]).into(),
+        )
+        .unwrap();
+        assert_eq!(template.name, "Feed Pets");
+        assert_eq!(
+            template.checklist_items(),
+            vec!["Fill bowl", "Refresh water"]
+        );
+
+        let mut updated = template.clone();
+        updated.amount_cents = 200;
+        let updated = ChoreTemplateSvc::update(&context, &updated).unwrap();
+        assert_eq!(updated.amount_cents, 200);
+
+        assert_eq!(ChoreTemplateSvc::list(&context, 100, 0).unwrap().len(), 1);
+
+        ChoreTemplateSvc::delete(&context, &template.uuid).unwrap();
+        assert!(ChoreTemplateSvc::get(&context, &template.uuid).is_err());
+    }
+
+    #[test]
+    fn test_instantiate_creates_assigned_chore_with_checklist() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let alice = create_test_user(&context, "Alice");
+        let bob = create_test_user(&context, "Bob");
+
+        let template = ChoreTemplateSvc::create(
+            &context,
+            &template_input("Tidy Room", &["Make bed", "Put away clothes"]).into(),
+        )
+        .unwrap();
+
+        let alice_id = alice.id.unwrap();
+        let bob_id = bob.id.unwrap();
+        let chore = ChoreTemplateSvc::instantiate(
+            &context,
+            &template.uuid,
+            admin.id.unwrap(),
+            &[alice_id, bob_id, alice_id],
+        )
+        .unwrap();
+
+        assert_eq!(chore.name, "Tidy Room");
+        assert_eq!(chore.amount_cents, 150);
+        assert_eq!(chore.points, 5);
+        assert_eq!(chore.required_days, day_patterns::weekdays());
+        assert!(chore.active);
+        assert_eq!(chore.created_by_admin_id, admin.id.unwrap());
+
+        let chore_id = chore.id.unwrap();
+        assert_eq!(
+            ChoreSvc::get_checklist(&context, chore_id).unwrap(),
+            vec!["Make bed", "Put away clothes"]
+        );
+        let mut assigned: Vec<i32> = ChoreSvc::get_assigned_users(&context, chore_id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|user| user.id)
+            .collect();
+        assigned.sort_unstable();
+        assert_eq!(assigned, vec![alice_id, bob_id]);
+
+        // A second instantiation yields an independent chore
+        let second =
+            ChoreTemplateSvc::instantiate(&context, &template.uuid, admin.id.unwrap(), &[])
+                .unwrap();
+        assert_ne!(second.uuid, chore.uuid);
+        assert!(
+            ChoreSvc::get_assigned_users(&context, second.id.unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_instantiate_rolls_back_on_unknown_user() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let template =
+            ChoreTemplateSvc::create(&context, &template_input("Dishes", &[]).into()).unwrap();
+
+        let result =
+            ChoreTemplateSvc::instantiate(&context, &template.uuid, admin.id.unwrap(), &[9999]);
+        assert!(result.is_err());
+        assert!(
+            ChoreSvc::list(&context, None, false, 100, 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_library_round_trip() {
+        let source = create_test_context();
+        ChoreTemplateSvc::create(
+            &source,
+            &template_input("Water Plants", &["Kitchen", "Porch"]).into(),
+        )
+        .unwrap();
+        let mut weekly: ChoreTemplate = template_input("Mow Lawn", &[]).into();
+        weekly.payment_type = PaymentType::Weekly.into();
+        ChoreTemplateSvc::create(&source, &weekly).unwrap();
+
+        let library = ChoreTemplateSvc::export_library(&source).unwrap();
+
+        let target = create_test_context();
+        let imported = ChoreTemplateSvc::import_library(&target, &library).unwrap();
+        assert_eq!(imported.len(), 2);
+
+        let mow = imported.iter().find(|t| t.name == "Mow Lawn").unwrap();
+        assert_eq!(PaymentType::from(&mow.payment_type), PaymentType::Weekly);
+        let plants = imported.iter().find(|t| t.name == "Water Plants").unwrap();
+        assert_eq!(plants.checklist_items(), vec!["Kitchen", "Porch"]);
+        assert_eq!(plants.points, 5);
+        assert_eq!(
+            plants.description.as_deref(),
+            Some("Water Plants description")
+        );
+
+        // Importing again updates by name instead of duplicating
+        let reimported = ChoreTemplateSvc::import_library(&target, &library).unwrap();
+        assert_eq!(reimported.len(), 2);
+        assert_eq!(ChoreTemplateSvc::list(&target, 100, 0).unwrap().len(), 2);
+        assert_eq!(
+            reimported
+                .iter()
+                .find(|t| t.name == "Water Plants")
+                .unwrap()
+                .uuid,
+            plants.uuid
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_library() {
+        let context = create_test_context();
+
+        assert!(ChoreTemplateSvc::import_library(&context, "not json").is_err());
+        assert!(
+            ChoreTemplateSvc::import_library(&context, r#"{"version":2,"templates":[]}"#).is_err()
+        );
+
+        // One bad entry rejects the whole library
+        let library = r#"{"version":1,"templates":[
+            {"name":"Good","paymentType":"daily","amountCents":100,"requiredDays":1},
+            {"name":"Bad","paymentType":"daily","amountCents":100,"requiredDays":255}
+        ]}"#;
+        assert!(ChoreTemplateSvc::import_library(&context, library).is_err());
+        assert!(ChoreTemplateSvc::list(&context, 100, 0).unwrap().is_empty());
+
+        // Unknown payment types are not imported as daily chores
+        let library = r#"{"version":1,"templates":[
+            {"name":"Rent","paymentType":"monthly","amountCents":100,"requiredDays":1}
+        ]}"#;
+        assert!(ChoreTemplateSvc::import_library(&context, library).is_err());
+        assert!(ChoreTemplateSvc::list(&context, 100, 0).unwrap().is_empty());
+
+        // Names must point at one template, in the library and in the database
+        let library = r#"{"version":1,"templates":[
+            {"name":"Dishes","paymentType":"daily","amountCents":100,"requiredDays":1},
+            {"name":"Dishes","paymentType":"daily","amountCents":200,"requiredDays":1}
+        ]}"#;
+        assert!(ChoreTemplateSvc::import_library(&context, library).is_err());
+        for amount_cents in [100, 200] {
+            let mut dishes: ChoreTemplate = template_input("Dishes", &[]).into();
+            dishes.amount_cents = amount_cents;
+            ChoreTemplateSvc::create(&context, &dishes).unwrap();
+        }
+        let library = r#"{"version":1,"templates":[
+            {"name":"Dishes","paymentType":"daily","amountCents":300,"requiredDays":1}
+        ]}"#;
+        assert!(ChoreTemplateSvc::import_library(&context, library).is_err());
+        let mut amounts: Vec<i32> = ChoreTemplateSvc::list(&context, 100, 0)
+            .unwrap()
+            .iter()
+            .map(|template| template.amount_cents)
+            .collect();
+        amounts.sort_unstable();
+        assert_eq!(amounts, vec![100, 200]);
+
+        // Templates made directly are held to the same rules
+        let mut negative: ChoreTemplate = template_input("Negative", &[]).into();
+        negative.amount_cents = -1;
+        assert!(ChoreTemplateSvc::create(&context, &negative).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+//! Per-kid summaries of a date range, sent instead of a notification per event.
+//!
+//! A digest lists, for every kid, the approved completions in the range, the ones still
+//! waiting for approval, the due chores that were never submitted, and what is owed right
+//! now. `DIGEST_FREQUENCY` (`daily`, `weekly` or `off`) together with `DIGEST_HOUR` and
+//! `DIGEST_DAY` controls when the scheduler sends one through the configured channels.
+
+use crate::{
+    context::GraphQLContext,
+    get_env, get_env_typed,
+    models::{ChoreCompletion, User},
+    svc::{
+        ChoreCompletionSvc, ChoreSvc, ScheduleSvc, UserSvc,
+        chore_completion::ChoreCompletionFilter,
+        email::{EmailMessage, escape_html, format_cents},
+    },
+};
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate, Weekday};
+use juniper::GraphQLObject;
+use std::collections::{HashMap, HashSet};
+
+/// How often the scheduler sends a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFrequency {
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Reads `DIGEST_FREQUENCY`; `None` when digests are switched off.
+    pub fn from_env() -> Option<Self> {
+        match get_env("DIGEST_FREQUENCY", "weekly")
+            .to_lowercase()
+            .as_str()
+        {
+            "daily" => Some(Self::Daily),
+            "weekly" => Some(Self::Weekly),
+            "off" | "none" | "" => None,
+            other => {
+                tracing::warn!("Unknown DIGEST_FREQUENCY '{other}'; digests are off");
+                None
+            }
+        }
+    }
+
+    pub fn hour() -> u32 {
+        get_env_typed::<u32>("DIGEST_HOUR", 19)
+    }
+
+    pub fn weekday() -> Weekday {
+        get_env_typed::<Weekday>("DIGEST_DAY", Weekday::Sun)
+    }
+
+    /// The range a digest sent on `today` covers, ending with `today`.
+    pub fn period(self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            Self::Daily => (today, today),
+            Self::Weekly => (today - Duration::days(6), today),
+        }
+    }
+}
+
+/// One chore occurrence in a digest.
+#[derive(Debug, Clone, PartialEq, Eq, GraphQLObject)]
+pub struct DigestEntry {
+    pub chore_id: i32,
+    pub chore_name: String,
+    pub date: NaiveDate,
+    /// What the completion earns; null for missed chores.
+    pub amount_cents: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GraphQLObject)]
+pub struct KidDigest {
+    pub user_id: i32,
+    pub name: String,
+    /// Approved completions in the range.
+    pub done: Vec<DigestEntry>,
+    /// Completions in the range waiting for approval.
+    pub pending: Vec<DigestEntry>,
+    /// Due, unpaused chores in the range with no completion at all. Days after today are
+    /// not counted.
+    pub missed: Vec<DigestEntry>,
+    /// Approved, unpaid earnings, regardless of the range.
+    pub owed_cents: i32,
+}
+
+impl KidDigest {
+    pub const fn is_empty(&self) -> bool {
+        self.done.is_empty()
+            && self.pending.is_empty()
+            && self.missed.is_empty()
+            && self.owed_cents == 0
+    }
+
+    /// One line such as `Alice: 3 done, 1 pending, 1 missed, $4.50 owed`.
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{}: {} done, {} pending, {} missed, {} owed",
+            self.name,
+            self.done.len(),
+            self.pending.len(),
+            self.missed.len(),
+            format_cents(self.owed_cents.into())
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GraphQLObject)]
+pub struct Digest {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub kids: Vec<KidDigest>,
+    pub total_owed_cents: i32,
+    pub subject: String,
+    /// Plain-text rendering.
+    pub text: String,
+    /// HTML rendering, a fragment suitable for an email body.
+    pub html: String,
+}
+
+impl Digest {
+    pub fn email(&self) -> EmailMessage {
+        EmailMessage {
+            subject: self.subject.clone(),
+            text: self.text.clone(),
+            html: self.html.clone(),
+        }
+    }
+}
+
+pub struct DigestSvc {}
+
+impl DigestSvc {
+    pub fn build(context: &GraphQLContext, from: NaiveDate, to: NaiveDate) -> Result<Digest> {
+        Self::build_as_of(context, from, to, Local::now().date_naive())
+    }
+
+    fn build_as_of(
+        context: &GraphQLContext,
+        from: NaiveDate,
+        to: NaiveDate,
+        today: NaiveDate,
+    ) -> Result<Digest> {
+        ScheduleSvc::check_range(from, to)?;
+
+        let completions = Self::completions(context, from, to)?;
+        let owed: HashMap<i32, i32> = ChoreCompletionSvc::get_unpaid_totals(context)?
+            .into_iter()
+            .filter_map(|(user, cents)| user.id.map(|id| (id, cents)))
+            .collect();
+        let mut chore_names: HashMap<i32, String> = HashMap::new();
+        let mut chore_name = |chore_id: i32| -> Result<String> {
+            if let Some(name) = chore_names.get(&chore_id) {
+                return Ok(name.clone());
+            }
+            let name = ChoreSvc::get_by_id(context, chore_id)?.name;
+            chore_names.insert(chore_id, name.clone());
+            Ok(name)
+        };
+
+        let mut kids = Vec::new();
+        for user in UserSvc::list(context, i32::MAX, 0)? {
+            let User {
+                id: Some(user_id),
+                name,
+                ..
+            } = user
+            else {
+                continue;
+            };
+
+            let mut done = Vec::new();
+            let mut pending = Vec::new();
+            let mut submitted = HashSet::new();
+            for completion in completions.iter().filter(|c| c.user_id == user_id) {
+                submitted.insert((completion.chore_id, completion.completed_date));
+                let entry = DigestEntry {
+                    chore_id: completion.chore_id,
+                    chore_name: chore_name(completion.chore_id)?,
+                    date: completion.completed_date,
+                    amount_cents: Some(completion.amount_cents),
+                };
+                if completion.approved {
+                    done.push(entry);
+                } else {
+                    pending.push(entry);
+                }
+            }
+
+            let schedule = ScheduleSvc::user_schedule(context, user_id)?;
+            let mut missed = Vec::new();
+            for date in from.iter_days().take_while(|date| *date <= to.min(today)) {
+                for chore_id in schedule.due_on(date) {
+                    if !submitted.contains(&(chore_id, date)) {
+                        missed.push(DigestEntry {
+                            chore_id,
+                            chore_name: chore_name(chore_id)?,
+                            date,
+                            amount_cents: None,
+                        });
+                    }
+                }
+            }
+
+            for entries in [&mut done, &mut pending, &mut missed] {
+                entries.sort_by(|a, b| (a.date, &a.chore_name).cmp(&(b.date, &b.chore_name)));
+            }
+            kids.push(KidDigest {
+                user_id,
+                name,
+                done,
+                pending,
+                missed,
+                owed_cents: owed.get(&user_id).copied().unwrap_or_default(),
+            });
+        }
+
+        let total_owed_cents = kids.iter().map(|kid| kid.owed_cents).sum();
+        let subject = if from == to {
+            format!("Chore digest for {from}")
+        } else {
+            format!("Chore digest for {from} to {to}")
+        };
+        let mut digest = Digest {
+            from,
+            to,
+            kids,
+            total_owed_cents,
+            subject,
+            text: String::new(),
+            html: String::new(),
+        };
+        digest.text = Self::render_text(&digest);
+        digest.html = Self::render_html(&digest);
+        Ok(digest)
+    }
+
+    /// Every completion in the range, paging through `ChoreCompletionSvc::list_after` so rows
+    /// sharing a date are neither skipped nor repeated between pages.
+    fn completions(
+        context: &GraphQLContext,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ChoreCompletion>> {
+        let filter = ChoreCompletionFilter {
+            date_from: Some(from),
+            date_to: Some(to),
+            ..Default::default()
+        };
+        let page_size = ChoreCompletionSvc::MAX_COMPLETION_LIMIT.into();
+        let mut completions: Vec<ChoreCompletion> = Vec::new();
+        loop {
+            let after = completions
+                .last()
+                .and_then(|last| last.id.map(|id| (last.completed_date, id)));
+            let page = ChoreCompletionSvc::list_after(context, &filter, after, page_size)?;
+            let last_page = page.len() < usize::try_from(page_size)?;
+            completions.extend(page);
+            if last_page {
+                return Ok(completions);
+            }
+        }
+    }
+
+    fn render_text(digest: &Digest) -> String {
+        let entry_line = |entry: &DigestEntry| {
+            format!(
+                "    - {} ({}{})",
+                entry.chore_name,
+                entry.date,
+                amount_suffix(entry)
+            )
+        };
+
+        let mut sections = Vec::new();
+        for kid in &digest.kids {
+            let mut lines = vec![kid.name.clone()];
+            if kid.is_empty() {
+                lines.push("  Nothing to report.".to_owned());
+            }
+            for (label, entries) in [
+                ("Done", &kid.done),
+                ("Waiting for approval", &kid.pending),
+                ("Missed", &kid.missed),
+            ] {
+                if !entries.is_empty() {
+                    lines.push(format!("  {label} ({}):", entries.len()));
+                    lines.extend(entries.iter().map(entry_line));
+                }
+            }
+            if kid.owed_cents > 0 {
+                lines.push(format!("  Owed: {}", format_cents(kid.owed_cents.into())));
+            }
+            sections.push(lines.join("\n"));
+        }
+        if sections.is_empty() {
+            sections.push("No kids yet.".to_owned());
+        }
+        sections.push(format!(
+            "Total owed: {}",
+            format_cents(digest.total_owed_cents.into())
+        ));
+
+        format!("{}\n\n{}", digest.subject, sections.join("\n\n"))
+    }
+
+    fn render_html(digest: &Digest) -> String {
+        let entry_item = |entry: &DigestEntry| {
+            format!(
+                "<li>{} ({}{})</li>",
+                escape_html(&entry.chore_name),
+                entry.date,
+                amount_suffix(entry)
+            )
+        };
+
+        let mut html = format!("<h2>{}</h2>", escape_html(&digest.subject));
+        if digest.kids.is_empty() {
+            html.push_str("<p>No kids yet.</p>");
+        }
+        for kid in &digest.kids {
+            html.push_str(&format!("<h3>{}</h3>", escape_html(&kid.name)));
+            if kid.is_empty() {
+                html.push_str("<p>Nothing to report.</p>");
+            }
+            for (label, entries) in [
+                ("Done", &kid.done),
+                ("Waiting for approval", &kid.pending),
+                ("Missed", &kid.missed),
+            ] {
+                if !entries.is_empty() {
+                    let items: String = entries.iter().map(entry_item).collect();
+                    html.push_str(&format!(
+                        "<p>{label} ({}):</p><ul>{items}</ul>",
+                        entries.len()
+                    ));
+                }
+            }
+            if kid.owed_cents > 0 {
+                html.push_str(&format!(
+                    "<p>Owed: <strong>{}</strong></p>",
+                    format_cents(kid.owed_cents.into())
+                ));
+            }
+        }
+        html.push_str(&format!(
+            "<p>Total owed: <strong>{}</strong></p>",
+            format_cents(digest.total_owed_cents.into())
+        ));
+        html
+    }
+}
+
+/// `, $1.00` for completions, nothing for missed chores.
+fn amount_suffix(entry: &DigestEntry) -> String {
+    entry.amount_cents.map_or_else(String::new, |cents| {
+        format!(", {}", format_cents(cents.into()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, PaymentType},
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    #[test]
+    fn test_digest_sorts_done_pending_missed_and_owed() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let admin_id = admin.id.unwrap();
+        let alice = create_test_user(&context, "Alice");
+        let alice_id = alice.id.unwrap();
+        create_test_user(&context, "Bob");
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        let dishes_id = dishes.id.unwrap();
+        ChoreSvc::assign_user(&context, dishes_id, alice_id).unwrap();
+
+        // Monday approved, Tuesday pending, Wednesday missed, Thursday is in the future
+        let monday = create_test_date(2026, 4, 6);
+        create_approved_test_completion(&context, dishes_id, alice_id, monday, admin_id);
+        ChoreCompletionSvc::create(
+            &context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id: dishes_id,
+                user_id: alice_id,
+                completed_date: monday + Duration::days(1),
+            },
+        )
+        .unwrap();
+
+        let digest = DigestSvc::build_as_of(
+            &context,
+            monday,
+            monday + Duration::days(3),
+            monday + Duration::days(2),
+        )
+        .unwrap();
+
+        assert_eq!(digest.kids.len(), 2);
+        let alice = &digest.kids[0];
+        assert_eq!(alice.name, "Alice");
+        assert_eq!(alice.done.len(), 1);
+        assert_eq!(alice.done[0].amount_cents, Some(100));
+        assert_eq!(alice.pending.len(), 1);
+        assert_eq!(alice.pending[0].date, monday + Duration::days(1));
+        assert_eq!(
+            alice.missed,
+            vec![DigestEntry {
+                chore_id: dishes_id,
+                chore_name: "Dishes".to_owned(),
+                date: monday + Duration::days(2),
+                amount_cents: None,
+            }]
+        );
+        assert_eq!(alice.owed_cents, 100);
+        assert!(digest.kids[1].is_empty());
+        assert_eq!(digest.total_owed_cents, 100);
+        assert_eq!(digest.subject, "Chore digest for 2026-04-06 to 2026-04-09");
+
+        assert!(
+            digest
+                .text
+                .contains("  Missed (1):\n    - Dishes (2026-04-08)")
+        );
+        assert!(digest.text.contains("Bob\n  Nothing to report."));
+        assert!(digest.text.ends_with("Total owed: $1.00"));
+        assert!(
+            digest
+                .html
+                .contains("<p>Done (1):</p><ul><li>Dishes (2026-04-06, $1.00)</li></ul>")
+        );
+    }
+
+    #[test]
+    fn test_digest_rejects_inverted_and_overlong_ranges() {
+        let context = create_test_context();
+        let day = create_test_date(2026, 4, 6);
+        assert!(DigestSvc::build(&context, day, day - Duration::days(1)).is_err());
+        assert!(DigestSvc::build(&context, day, day + Duration::days(730)).is_ok());
+        assert!(DigestSvc::build(&context, day, day + Duration::days(731)).is_err());
+    }
+
+    #[test]
+    fn test_weekly_period_ends_today() {
+        let today = create_test_date(2026, 4, 12);
+        assert_eq!(
+            DigestFrequency::Weekly.period(today),
+            (create_test_date(2026, 4, 6), today)
+        );
+        assert_eq!(DigestFrequency::Daily.period(today), (today, today));
+    }
+}
@@ -0,0 +1,423 @@
+//! SMTP email channel.
+//!
+//! Disabled unless `SMTP_HOST` is set; the other settings are `SMTP_PORT`, `SMTP_TLS`
+//! (`none`, `starttls` or `tls`), `SMTP_USERNAME`, `SMTP_PASSWORD` and `SMTP_FROM`.
+//! `SMTP_TLS=none` is meant for local sinks such as MailHog or Mailpit.
+
+use crate::{get_env, get_env_typed, models::User};
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use lettre::{
+    Message, SmtpTransport, Transport,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+};
+use std::{str::FromStr, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Plain SMTP, no encryption.
+    None,
+    /// Upgrade a plain connection with STARTTLS (usually port 587).
+    StartTls,
+    /// TLS from the first byte (usually port 465).
+    Tls,
+}
+
+impl FromStr for SmtpTls {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "none" | "off" | "plain" => Ok(Self::None),
+            "starttls" => Ok(Self::StartTls),
+            "tls" | "ssl" | "smtps" => Ok(Self::Tls),
+            other => bail!("Unknown SMTP_TLS mode '{other}'"),
+        }
+    }
+}
+
+impl SmtpTls {
+    const fn default_port(self) -> u16 {
+        match self {
+            Self::None => 25,
+            Self::StartTls => 587,
+            Self::Tls => 465,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+}
+
+impl EmailConfig {
+    /// Reads SMTP settings from the environment, or `None` when email is not configured.
+    pub fn from_env() -> Option<Self> {
+        let host = get_env("SMTP_HOST", "");
+        if host.is_empty() {
+            return None;
+        }
+        let tls = get_env("SMTP_TLS", "starttls").parse().unwrap_or_else(|e| {
+            tracing::warn!("{e}; falling back to STARTTLS");
+            SmtpTls::StartTls
+        });
+        let non_empty = |key: &str| Some(get_env(key, "")).filter(|value| !value.is_empty());
+
+        Some(Self {
+            host,
+            port: get_env_typed::<u16>("SMTP_PORT", tls.default_port()),
+            tls,
+            username: non_empty("SMTP_USERNAME"),
+            password: non_empty("SMTP_PASSWORD"),
+            from: get_env("SMTP_FROM", "Chore Tracker <chores@localhost>"),
+        })
+    }
+}
+
+/// A rendered email with plain-text and HTML bodies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+impl EmailMessage {
+    pub fn pending_approval(user_name: &str, chore_name: &str, date: NaiveDate) -> Self {
+        let subject = format!("{user_name} completed \"{chore_name}\"");
+        let text = format!(
+            "{user_name} marked \"{chore_name}\" as done for {date}.\n\n\
+             It is waiting for your approval in Chore Tracker."
+        );
+        let html = format!(
+            "<p><strong>{}</strong> marked <strong>{}</strong> as done for {date}.</p>\
+             <p>It is waiting for your approval in Chore Tracker.</p>",
+            escape_html(user_name),
+            escape_html(chore_name),
+        );
+        Self {
+            subject,
+            text,
+            html,
+        }
+    }
+
+    pub fn bonus_claimed(
+        user_name: &str,
+        chore_name: &str,
+        amount_cents: i32,
+        remaining: Option<i32>,
+    ) -> Self {
+        let amount = format_cents(amount_cents.into());
+        let remaining = remaining.map_or_else(String::new, |left| format!(" {left} slot(s) left."));
+        let subject = format!("{user_name} claimed the bonus chore \"{chore_name}\"");
+        let text = format!("{user_name} claimed \"{chore_name}\" ({amount}).{remaining}");
+        let html = format!(
+            "<p><strong>{}</strong> claimed the bonus chore <strong>{}</strong> ({amount}).{}</p>",
+            escape_html(user_name),
+            escape_html(chore_name),
+            escape_html(&remaining),
+        );
+        Self {
+            subject,
+            text,
+            html,
+        }
+    }
+
+    /// Weekly payout summary built from the unpaid totals; kids with nothing owed are left out.
+    pub fn weekly_summary(unpaid_totals: &[(User, i32)]) -> Self {
+        let owed: Vec<&(User, i32)> = unpaid_totals
+            .iter()
+            .filter(|(_, cents)| *cents > 0)
+            .collect();
+        let total: i64 = owed.iter().map(|(_, cents)| i64::from(*cents)).sum();
+
+        let subject = format!("Weekly payout summary: {} owed", format_cents(total));
+        let (text, html) = if owed.is_empty() {
+            let line = "Nothing is owed this week.".to_owned();
+            (line.clone(), format!("<p>{line}</p>"))
+        } else {
+            let text_lines: Vec<String> = owed
+                .iter()
+                .map(|(user, cents)| format!("- {}: {}", user.name, format_cents((*cents).into())))
+                .collect();
+            let html_rows: String = owed
+                .iter()
+                .map(|(user, cents)| {
+                    format!(
+                        "<tr><td>{}</td><td style=\"text-align:right\">{}</td></tr>",
+                        escape_html(&user.name),
+                        format_cents((*cents).into())
+                    )
+                })
+                .collect();
+            (
+                format!(
+                    "Approved, unpaid earnings:\n\n{}\n\nTotal: {}",
+                    text_lines.join("\n"),
+                    format_cents(total)
+                ),
+                format!(
+                    "<p>Approved, unpaid earnings:</p><table>{html_rows}\
+                     <tr><th>Total</th><th style=\"text-align:right\">{}</th></tr></table>",
+                    format_cents(total)
+                ),
+            )
+        };
+        Self {
+            subject,
+            text,
+            html,
+        }
+    }
+
+    pub fn test_message() -> Self {
+        let line = "Email notifications from Chore Tracker are working.".to_owned();
+        Self {
+            subject: "Chore Tracker test email".to_owned(),
+            html: format!("<p>{line}</p>"),
+            text: line,
+        }
+    }
+}
+
+pub struct EmailSvc {}
+
+impl EmailSvc {
+    /// Sends `message` to each address separately so recipients don't see each other.
+    pub fn send(config: &EmailConfig, recipients: &[String], message: &EmailMessage) -> Result<()> {
+        let mailer = Self::transport(config)?;
+        let from: Mailbox = config.from.parse().context("Invalid SMTP_FROM address")?;
+
+        for recipient in recipients {
+            let to: Mailbox = recipient
+                .parse()
+                .with_context(|| format!("Invalid recipient address '{recipient}'"))?;
+            let email = Message::builder()
+                .from(from.clone())
+                .to(to)
+                .subject(&message.subject)
+                .multipart(MultiPart::alternative_plain_html(
+                    message.text.clone(),
+                    message.html.clone(),
+                ))
+                .context("Could not build email")?;
+            mailer
+                .send(&email)
+                .with_context(|| format!("Could not send email to {recipient}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn transport(config: &EmailConfig) -> Result<SmtpTransport> {
+        let builder = match config.tls {
+            SmtpTls::None => SmtpTransport::builder_dangerous(&config.host),
+            SmtpTls::StartTls => SmtpTransport::starttls_relay(&config.host)
+                .context("Could not set up STARTTLS for SMTP")?,
+            SmtpTls::Tls => {
+                SmtpTransport::relay(&config.host).context("Could not set up TLS for SMTP")?
+            }
+        };
+        let builder = builder
+            .port(config.port)
+            .timeout(Some(Duration::from_secs(15)));
+        let builder = match (&config.username, &config.password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username.clone(), password.clone()))
+            }
+            _ => builder,
+        };
+
+        Ok(builder.build())
+    }
+}
+
+pub(crate) fn format_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.abs();
+    format!("{sign}${}.{:02}", cents / 100, cents % 100)
+}
+
+pub(crate) fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// A throwaway SMTP server that accepts every message and records the raw DATA, so the
+/// real transport can be exercised without a mail server.
+#[cfg(test)]
+pub(crate) mod sink {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    pub struct SmtpSink {
+        pub port: u16,
+        pub messages: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl SmtpSink {
+        pub fn start() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let messages = Arc::new(Mutex::new(Vec::new()));
+            let store = Arc::clone(&messages);
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let _ = stream.write_all(b"220 sink ESMTP\r\n");
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            break;
+                        }
+                        let command = line.trim_end().to_uppercase();
+                        if command.starts_with("EHLO") || command.starts_with("HELO") {
+                            let _ = stream.write_all(b"250 sink\r\n");
+                        } else if command == "DATA" {
+                            let _ = stream.write_all(b"354 go ahead\r\n");
+                            let mut data = String::new();
+                            loop {
+                                line.clear();
+                                if reader.read_line(&mut line).unwrap_or(0) == 0 || line == ".\r\n"
+                                {
+                                    break;
+                                }
+                                data.push_str(&line);
+                            }
+                            store.lock().unwrap().push(data);
+                            let _ = stream.write_all(b"250 queued\r\n");
+                        } else if command == "QUIT" {
+                            let _ = stream.write_all(b"221 bye\r\n");
+                            break;
+                        } else {
+                            let _ = stream.write_all(b"250 ok\r\n");
+                        }
+                    }
+                }
+            });
+
+            Self { port, messages }
+        }
+
+        pub fn config(&self) -> super::EmailConfig {
+            super::EmailConfig {
+                host: "127.0.0.1".to_owned(),
+                port: self.port,
+                tls: super::SmtpTls::None,
+                username: None,
+                password: None,
+                from: "Chore Tracker <chores@example.com>".to_owned(),
+            }
+        }
+
+        pub fn messages(&self) -> Vec<String> {
+            self.messages.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(name: &str) -> User {
+        User {
+            id: Some(1),
+            uuid: "u".to_owned(),
+            name: name.to_owned(),
+            image_path: None,
+            created_at: None,
+            updated_at: None,
+            image_id: None,
+        }
+    }
+
+    #[test]
+    fn test_tls_modes_parse() {
+        assert_eq!("none".parse::<SmtpTls>().unwrap(), SmtpTls::None);
+        assert_eq!("STARTTLS".parse::<SmtpTls>().unwrap(), SmtpTls::StartTls);
+        assert_eq!("tls".parse::<SmtpTls>().unwrap(), SmtpTls::Tls);
+        assert!("sometimes".parse::<SmtpTls>().is_err());
+    }
+
+    #[test]
+    fn test_templates_escape_html() {
+        let message = EmailMessage::pending_approval(
+            "<Kid>",
+            "Dishes & Pans",
+            NaiveDate::from_ymd_opt(2026, 4, 6).unwrap(),
+        );
+        assert_eq!(message.subject, "<Kid> completed \"Dishes & Pans\"");
+        assert!(message.html.contains("&lt;Kid&gt;"));
+        assert!(message.html.contains("Dishes &amp; Pans"));
+        assert!(message.text.contains("2026-04-06"));
+    }
+
+    #[test]
+    fn test_weekly_summary_skips_kids_owed_nothing() {
+        let totals = vec![(user("Alice"), 1250), (user("Bob"), 0), (user("Cara"), 75)];
+        let message = EmailMessage::weekly_summary(&totals);
+
+        assert_eq!(message.subject, "Weekly payout summary: $13.25 owed");
+        assert!(message.text.contains("- Alice: $12.50"));
+        assert!(message.text.contains("- Cara: $0.75"));
+        assert!(!message.text.contains("Bob"));
+
+        let empty = EmailMessage::weekly_summary(&[(user("Bob"), 0)]);
+        assert!(empty.text.contains("Nothing is owed"));
+    }
+
+    #[test]
+    fn test_send_through_local_sink() {
+        let sink = sink::SmtpSink::start();
+        let recipients = vec![
+            "parent@example.com".to_owned(),
+            "other@example.com".to_owned(),
+        ];
+
+        EmailSvc::send(&sink.config(), &recipients, &EmailMessage::test_message()).unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].contains("To: parent@example.com"));
+        assert!(messages[0].contains("Subject: Chore Tracker test email"));
+        assert!(messages[1].contains("To: other@example.com"));
+    }
+
+    #[test]
+    fn test_send_rejects_bad_recipient() {
+        let sink = sink::SmtpSink::start();
+        let result = EmailSvc::send(
+            &sink.config(),
+            &["not an address".to_owned()],
+            &EmailMessage::test_message(),
+        );
+        assert!(result.is_err());
+    }
+}
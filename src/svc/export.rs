@@ -0,0 +1,317 @@
+//! Completion exports for allowance reconciliation.
+//!
+//! Rows are loaded a page at a time with `ChoreCompletionSvc::list_after` and handed to a
+//! sink as formatted chunks, so an export of the whole history never sits in memory.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::ChoreCompletion,
+    schema::{chores, users},
+    svc::{ChoreCompletionSvc, chore_completion::ChoreCompletionFilter},
+};
+use anyhow::{Context, Result};
+use diesel::prelude::*;
+use serde_json::json;
+use std::collections::HashMap;
+
+const PAGE_SIZE: i64 = 500;
+
+const CSV_HEADER: &str = "uuid,completed_date,user_id,user_name,chore_id,chore_name,\
+                          amount_cents,approved,approved_at,approved_by_admin_id,paid_out,\
+                          paid_out_at,created_at\r\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// A completion with the names a spreadsheet needs.
+#[derive(Debug)]
+pub struct ExportRow {
+    pub completion: ChoreCompletion,
+    pub user_name: String,
+    pub chore_name: String,
+}
+
+impl ExportRow {
+    fn csv(&self) -> String {
+        let c = &self.completion;
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let fields = [
+            c.uuid.clone(),
+            c.completed_date.to_string(),
+            c.user_id.to_string(),
+            csv_field(&self.user_name),
+            c.chore_id.to_string(),
+            csv_field(&self.chore_name),
+            c.amount_cents.to_string(),
+            c.approved.to_string(),
+            optional(c.approved_at.map(|at| at.to_string())),
+            optional(c.approved_by_admin_id.map(|id| id.to_string())),
+            c.paid_out.to_string(),
+            optional(c.paid_out_at.map(|at| at.to_string())),
+            optional(c.created_at.map(|at| at.to_string())),
+        ];
+        format!("{}\r\n", fields.join(","))
+    }
+
+    fn json(&self) -> String {
+        let c = &self.completion;
+        json!({
+            "uuid": c.uuid,
+            "completedDate": c.completed_date,
+            "user": { "id": c.user_id, "name": self.user_name },
+            "chore": { "id": c.chore_id, "name": self.chore_name },
+            "amountCents": c.amount_cents,
+            "approved": c.approved,
+            "approvedAt": c.approved_at,
+            "approvedByAdminId": c.approved_by_admin_id,
+            "paidOut": c.paid_out,
+            "paidOutAt": c.paid_out_at,
+            "createdAt": c.created_at,
+        })
+        .to_string()
+    }
+}
+
+/// Quotes a CSV field when needed, and defuses values a spreadsheet would run as a
+/// formula.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_owned()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+pub struct ExportSvc {}
+
+impl ExportSvc {
+    /// Writes every completion matching `filter` to `sink`, oldest first, one chunk per
+    /// page. Stops early when `sink` returns `false` (e.g. the client went away).
+    pub fn write(
+        context: &GraphQLContext,
+        filter: &ChoreCompletionFilter,
+        format: ExportFormat,
+        sink: impl FnMut(String) -> bool,
+    ) -> Result<()> {
+        Self::write_pages(context, filter, format, PAGE_SIZE, sink)
+    }
+
+    fn write_pages(
+        context: &GraphQLContext,
+        filter: &ChoreCompletionFilter,
+        format: ExportFormat,
+        page_size: i64,
+        mut sink: impl FnMut(String) -> bool,
+    ) -> Result<()> {
+        let opening = match format {
+            ExportFormat::Csv => CSV_HEADER,
+            ExportFormat::Json => "[",
+        };
+        if !sink(opening.to_owned()) {
+            return Ok(());
+        }
+
+        let mut after = None;
+        let mut first = true;
+        loop {
+            let page = Self::page(context, filter, after, page_size)?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = last
+                .completion
+                .id
+                .map(|id| (last.completion.completed_date, id));
+
+            let mut chunk = String::new();
+            for row in &page {
+                match format {
+                    ExportFormat::Csv => chunk.push_str(&row.csv()),
+                    ExportFormat::Json => {
+                        if !first {
+                            chunk.push(',');
+                        }
+                        chunk.push_str(&row.json());
+                    }
+                }
+                first = false;
+            }
+            if !sink(chunk) || page.len() < usize::try_from(page_size)? || after.is_none() {
+                break;
+            }
+        }
+
+        if format == ExportFormat::Json {
+            sink("]".to_owned());
+        }
+        Ok(())
+    }
+
+    fn page(
+        context: &GraphQLContext,
+        filter: &ChoreCompletionFilter,
+        after: Option<(chrono::NaiveDate, i32)>,
+        page_size: i64,
+    ) -> Result<Vec<ExportRow>> {
+        let completions = ChoreCompletionSvc::list_after(context, filter, after, page_size)?;
+
+        let user_ids: Vec<i32> = completions.iter().map(|c| c.user_id).collect();
+        let user_names: HashMap<i32, String> = users::table
+            .filter(users::id.eq_any(user_ids))
+            .select((users::id.assume_not_null(), users::name))
+            .load::<(i32, String)>(&mut get_conn(context)?)
+            .context("Could not load user names")?
+            .into_iter()
+            .collect();
+        let chore_ids: Vec<i32> = completions.iter().map(|c| c.chore_id).collect();
+        let chore_names: HashMap<i32, String> = chores::table
+            .filter(chores::id.eq_any(chore_ids))
+            .select((chores::id.assume_not_null(), chores::name))
+            .load::<(i32, String)>(&mut get_conn(context)?)
+            .context("Could not load chore names")?
+            .into_iter()
+            .collect();
+
+        Ok(completions
+            .into_iter()
+            .map(|completion| ExportRow {
+                user_name: user_names
+                    .get(&completion.user_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                chore_name: chore_names
+                    .get(&completion.chore_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                completion,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, PaymentType},
+        test_helpers::test_db::{
+            create_test_admin, create_test_chore, create_test_context, create_test_date,
+            create_test_user, day_patterns,
+        },
+    };
+
+    fn export(
+        context: &GraphQLContext,
+        filter: &ChoreCompletionFilter,
+        format: ExportFormat,
+    ) -> (String, usize) {
+        let mut chunks = Vec::new();
+        ExportSvc::write_pages(context, filter, format, 2, |chunk| {
+            chunks.push(chunk);
+            true
+        })
+        .unwrap();
+        (chunks.concat(), chunks.len())
+    }
+
+    #[test]
+    fn test_csv_field_quotes_and_defuses_formulas() {
+        assert_eq!(csv_field("Dishes"), "Dishes");
+        assert_eq!(csv_field("Rake, bag"), "\"Rake, bag\"");
+        assert_eq!(csv_field("Say \"hi\""), "\"Say \"\"hi\"\"\"");
+        assert_eq!(csv_field("=SUM(A1)"), "'=SUM(A1)");
+    }
+
+    #[test]
+    fn test_export_pages_past_the_list_cap_in_date_order() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Alice").id.unwrap();
+        let chore_id = create_test_chore(
+            &context,
+            "Dishes, pots",
+            PaymentType::Daily,
+            150,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        // Inserted newest first so the export has to sort
+        for day in (6..=10).rev() {
+            ChoreCompletionSvc::create(
+                &context,
+                &ChoreCompletionInput {
+                    uuid: None,
+                    chore_id,
+                    user_id,
+                    completed_date: create_test_date(2026, 4, day),
+                },
+            )
+            .unwrap();
+        }
+
+        let filter = ChoreCompletionFilter {
+            user_id: Some(user_id),
+            date_from: Some(create_test_date(2026, 4, 7)),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let (csv, chunks) = export(&context, &filter, ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5, "header plus four rows, ignoring the limit");
+        assert!(lines[0].starts_with("uuid,completed_date,user_id,user_name"));
+        assert!(lines[1].contains(",2026-04-07,"));
+        assert!(lines[1].contains(",Alice,"));
+        assert!(lines[1].contains(",\"Dishes, pots\",150,false,"));
+        assert!(lines[4].contains(",2026-04-10,"));
+        assert_eq!(chunks, 3, "header and two pages");
+
+        let (json, _) = export(&context, &filter, ExportFormat::Json);
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0]["completedDate"], "2026-04-07");
+        assert_eq!(rows[0]["chore"]["name"], "Dishes, pots");
+
+        let empty = ChoreCompletionFilter {
+            paid_only: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(export(&context, &empty, ExportFormat::Json).0, "[]");
+    }
+}
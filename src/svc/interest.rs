@@ -0,0 +1,465 @@
+//! Interest on savings.
+//!
+//! A household rate, or a kid's own, is applied to the save jar once per week or month.
+//! Each period worked out is recorded in `interest_credits` with the balance, rate and
+//! rounding it used, and the interest itself goes into the save jar as an `interest`
+//! entry, so it compounds with the next period. Each period earns on the save jar as it
+//! stood when the period ended, even when it is credited late.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{
+        InterestCredit, InterestPeriod, InterestRate, InterestRounding, Jar, JarEntryKind,
+        PaymentType,
+    },
+    schema::{interest_credits, interest_rates, jar_entries},
+};
+use anyhow::{Context, Result, bail};
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub struct InterestSvc {}
+
+impl InterestSvc {
+    /// Rate in effect for `user_id`: their own when they have one, else the household
+    /// rate. `None` looks up the household rate only.
+    pub fn rate(context: &GraphQLContext, user_id: Option<i32>) -> Result<Option<InterestRate>> {
+        let mut conn = get_conn(context)?;
+        if let Some(user_id) = user_id {
+            let own = interest_rates::table
+                .filter(interest_rates::user_id.eq(user_id))
+                .select(InterestRate::as_select())
+                .first(&mut conn)
+                .optional()
+                .context("Could not load interest rate")?;
+            if own.is_some() {
+                return Ok(own);
+            }
+        }
+
+        interest_rates::table
+            .filter(interest_rates::user_id.is_null())
+            .select(InterestRate::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Could not load interest rate")
+    }
+
+    /// Sets the rate of `rate.user_id`, or the household rate. A rate of 0 turns interest
+    /// off for that user even when the household has a rate.
+    pub fn set_rate(context: &GraphQLContext, rate: &InterestRate) -> Result<InterestRate> {
+        if !(0..=10_000).contains(&rate.rate_bps) {
+            bail!("Interest rates must be between 0 and 10000 basis points");
+        }
+
+        let now = Utc::now().naive_utc();
+        get_conn(context)?.immediate_transaction(|conn| {
+            let mut existing = diesel::update(interest_rates::table).into_boxed();
+            existing = match rate.user_id {
+                Some(user_id) => existing.filter(interest_rates::user_id.eq(user_id)),
+                None => existing.filter(interest_rates::user_id.is_null()),
+            };
+            // Keep `created_at`: interest only counts periods the rate was around for
+            let updated = existing
+                .set((
+                    interest_rates::rate_bps.eq(rate.rate_bps),
+                    interest_rates::period.eq(&rate.period),
+                    interest_rates::rounding.eq(&rate.rounding),
+                    interest_rates::updated_at.eq(now),
+                ))
+                .execute(conn)
+                .context("Could not update interest rate")?;
+            if updated == 0 {
+                diesel::insert_into(interest_rates::table)
+                    .values(&InterestRate {
+                        id: None,
+                        created_at: Some(now),
+                        updated_at: Some(now),
+                        ..rate.clone()
+                    })
+                    .execute(conn)
+                    .context("Could not save interest rate")?;
+            }
+            anyhow::Ok(())
+        })?;
+
+        Self::rate(context, rate.user_id)?.context("Could not find interest rate")
+    }
+
+    /// Removes the rate of `user_id`, putting them back on the household rate, or the
+    /// household rate itself.
+    pub fn clear_rate(context: &GraphQLContext, user_id: Option<i32>) -> Result<()> {
+        let mut rates = diesel::delete(interest_rates::table).into_boxed();
+        rates = match user_id {
+            Some(user_id) => rates.filter(interest_rates::user_id.eq(user_id)),
+            None => rates.filter(interest_rates::user_id.is_null()),
+        };
+        rates
+            .execute(&mut get_conn(context)?)
+            .context("Could not clear interest rate")?;
+        Ok(())
+    }
+
+    /// The user's interest history, newest period first.
+    pub fn credits(context: &GraphQLContext, user_id: i32) -> Result<Vec<InterestCredit>> {
+        interest_credits::table
+            .filter(interest_credits::user_id.eq(user_id))
+            .select(InterestCredit::as_select())
+            .order_by(interest_credits::period_start.desc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load interest credits")
+    }
+
+    /// Interest on `balance_cents` at `rate_bps`, rounded.
+    pub fn interest_cents(balance_cents: i32, rate_bps: i32, rounding: InterestRounding) -> i32 {
+        let interest = f64::from(balance_cents) * f64::from(rate_bps) / 10_000.0;
+        match rounding {
+            InterestRounding::NearestQuarter => PaymentType::round_to_nearest_quarter(interest),
+            InterestRounding::ExactCent => interest.round() as i32,
+        }
+    }
+
+    /// First and last day of the period that `date` falls in.
+    fn period_of(period: InterestPeriod, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match period {
+            InterestPeriod::Weekly => {
+                let start = date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+                (start, start + Duration::days(6))
+            }
+            InterestPeriod::Monthly => {
+                let start = date.with_day(1).unwrap_or(date);
+                let end = start
+                    .checked_add_months(Months::new(1))
+                    .map_or(start, |next| next - Duration::days(1));
+                (start, end)
+            }
+        }
+    }
+
+    /// First and last day of the latest period that ended before `today`.
+    fn last_period(period: InterestPeriod, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let (start, _) = Self::period_of(period, today);
+        Self::period_of(period, start - Duration::days(1))
+    }
+
+    /// The save jar as it stood when `period_end` was over.
+    fn save_cents_at_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        period_end: NaiveDate,
+    ) -> Result<i32> {
+        let cents: Option<i64> = jar_entries::table
+            .filter(jar_entries::user_id.eq(user_id))
+            .filter(jar_entries::jar.eq(String::from(Jar::Save)))
+            .filter(jar_entries::created_at.lt(Self::closed_at(period_end)))
+            .select(diesel::dsl::sum(jar_entries::amount_cents))
+            .first(conn)
+            .context("Could not total save jar")?;
+        Ok(i32::try_from(cents.unwrap_or(0)).unwrap_or(i32::MAX))
+    }
+
+    /// Start of the day after `period_end`.
+    fn closed_at(period_end: NaiveDate) -> NaiveDateTime {
+        (period_end + Duration::days(1)).and_time(NaiveTime::MIN)
+    }
+
+    /// Credits interest for every period that ended before `today` and was not credited
+    /// yet, oldest first, each on the save jar as it stood at the end of that period.
+    /// Periods missed while the server was down are made up; periods that ended before
+    /// the rate was set are not.
+    pub fn credit_due(
+        context: &GraphQLContext,
+        user_id: i32,
+        today: NaiveDate,
+    ) -> Result<Vec<InterestCredit>> {
+        let Some(rate) = Self::rate(context, Some(user_id))? else {
+            return Ok(Vec::new());
+        };
+        if rate.rate_bps == 0 {
+            return Ok(Vec::new());
+        }
+        let period = InterestPeriod::from(&rate.period);
+        let (last_start, _) = Self::last_period(period, today);
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            let last_credited: Option<NaiveDate> = interest_credits::table
+                .filter(interest_credits::user_id.eq(user_id))
+                .select(diesel::dsl::max(interest_credits::period_end))
+                .first(conn)
+                .context("Could not load interest credits")?;
+            let set_on = rate.created_at.map_or(last_start, |at| at.date());
+            let first = last_credited.map_or(set_on, |end| (end + Duration::days(1)).max(set_on));
+
+            let rounding = InterestRounding::from(&rate.rounding);
+            let mut credits = Vec::new();
+            let (mut period_start, mut period_end) = Self::period_of(period, first);
+            while period_start <= last_start {
+                let credited: bool = diesel::select(diesel::dsl::exists(
+                    interest_credits::table
+                        .filter(interest_credits::user_id.eq(user_id))
+                        .filter(interest_credits::period_start.eq(period_start)),
+                ))
+                .get_result(conn)
+                .context("Could not check interest credits")?;
+                let balance_cents = Self::save_cents_at_in(conn, user_id, period_end)?;
+                if !credited && balance_cents > 0 {
+                    credits.push(Self::credit_in(
+                        conn,
+                        user_id,
+                        &rate,
+                        rounding,
+                        (period_start, period_end),
+                        balance_cents,
+                    )?);
+                }
+                (period_start, period_end) =
+                    Self::period_of(period, period_end + Duration::days(1));
+            }
+            Ok(credits)
+        })
+    }
+
+    /// Records interest on `balance_cents` for one period and puts it in the save jar.
+    fn credit_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        rate: &InterestRate,
+        rounding: InterestRounding,
+        (period_start, period_end): (NaiveDate, NaiveDate),
+        balance_cents: i32,
+    ) -> Result<InterestCredit> {
+        let amount_cents = Self::interest_cents(balance_cents, rate.rate_bps, rounding);
+        let jar_entry_id = if amount_cents > 0 {
+            diesel::insert_into(jar_entries::table)
+                .values((
+                    jar_entries::uuid.eq(Uuid::now_v7().to_string()),
+                    jar_entries::user_id.eq(user_id),
+                    jar_entries::jar.eq(String::from(Jar::Save)),
+                    jar_entries::amount_cents.eq(amount_cents),
+                    jar_entries::kind.eq(String::from(JarEntryKind::Interest)),
+                    jar_entries::note.eq(format!(
+                        "Interest for {} to {}",
+                        period_start.format("%b %-d"),
+                        period_end.format("%b %-d, %Y")
+                    )),
+                    // Dated when its period closed, so it compounds from the next period
+                    // even when it is made up later
+                    jar_entries::created_at.eq(Self::closed_at(period_end)),
+                ))
+                .returning(jar_entries::id)
+                .get_result::<Option<i32>>(conn)
+                .context("Could not save interest")?
+        } else {
+            None
+        };
+
+        let credit = InterestCredit {
+            id: None,
+            uuid: Uuid::now_v7().to_string(),
+            user_id,
+            period_start,
+            period_end,
+            balance_cents,
+            rate_bps: rate.rate_bps,
+            rounding: rounding.into(),
+            amount_cents,
+            jar_entry_id,
+            created_at: Some(Utc::now().naive_utc()),
+        };
+        diesel::insert_into(interest_credits::table)
+            .values(&credit)
+            .execute(conn)
+            .context("Could not record interest credit")?;
+        Ok(credit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        svc::JarSvc,
+        test_helpers::test_db::{create_test_context, create_test_date, create_test_user},
+    };
+
+    fn rate(user_id: Option<i32>, rate_bps: i32, rounding: InterestRounding) -> InterestRate {
+        InterestRate {
+            id: None,
+            user_id,
+            rate_bps,
+            period: InterestPeriod::Monthly.into(),
+            rounding: rounding.into(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_interest_rounding_and_periods() {
+        assert_eq!(
+            InterestSvc::interest_cents(1234, 250, InterestRounding::ExactCent),
+            31
+        );
+        assert_eq!(
+            InterestSvc::interest_cents(1234, 250, InterestRounding::NearestQuarter),
+            25
+        );
+        assert_eq!(
+            InterestSvc::interest_cents(400, 250, InterestRounding::NearestQuarter),
+            0
+        );
+
+        // Wednesday
+        let today = create_test_date(2026, 4, 8);
+        assert_eq!(
+            InterestSvc::last_period(InterestPeriod::Weekly, today),
+            (create_test_date(2026, 3, 30), create_test_date(2026, 4, 5))
+        );
+        assert_eq!(
+            InterestSvc::last_period(InterestPeriod::Monthly, today),
+            (create_test_date(2026, 3, 1), create_test_date(2026, 3, 31))
+        );
+    }
+
+    #[test]
+    fn test_interest_is_credited_once_per_period_and_compounds() {
+        let context = create_test_context();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let other_id = create_test_user(&context, "Other").id.unwrap();
+        diesel::insert_into(jar_entries::table)
+            .values((
+                jar_entries::uuid.eq(Uuid::now_v7().to_string()),
+                jar_entries::user_id.eq(user_id),
+                jar_entries::jar.eq(String::from(Jar::Save)),
+                jar_entries::amount_cents.eq(10_000),
+                jar_entries::kind.eq(String::from(JarEntryKind::Transfer)),
+            ))
+            .execute(&mut get_conn(&context).unwrap())
+            .unwrap();
+
+        InterestSvc::set_rate(&context, &rate(None, 100, InterestRounding::ExactCent)).unwrap();
+        InterestSvc::set_rate(
+            &context,
+            &rate(Some(other_id), 0, InterestRounding::ExactCent),
+        )
+        .unwrap();
+        assert_eq!(
+            InterestSvc::rate(&context, Some(user_id))
+                .unwrap()
+                .unwrap()
+                .user_id,
+            None
+        );
+
+        // The rate was just set, so only months ending from now on earn interest
+        let next_month = Utc::now().date_naive() + Duration::days(40);
+        let first = InterestSvc::credit_due(&context, user_id, next_month).unwrap();
+        assert_eq!(
+            first
+                .iter()
+                .map(|credit| (credit.balance_cents, credit.amount_cents))
+                .collect::<Vec<_>>(),
+            vec![(10_000, 100)]
+        );
+        assert!(
+            InterestSvc::credit_due(&context, user_id, next_month)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            InterestSvc::credit_due(&context, other_id, next_month)
+                .unwrap()
+                .is_empty()
+        );
+
+        let month_after = next_month + Duration::days(31);
+        let second = InterestSvc::credit_due(&context, user_id, month_after).unwrap();
+        assert_eq!(
+            second
+                .iter()
+                .map(|credit| (credit.balance_cents, credit.amount_cents))
+                .collect::<Vec<_>>(),
+            vec![(10_100, 101)]
+        );
+        assert_eq!(
+            JarSvc::balances(&context, user_id).unwrap().save_cents,
+            10_201
+        );
+        assert_eq!(InterestSvc::credits(&context, user_id).unwrap().len(), 2);
+
+        InterestSvc::clear_rate(&context, Some(other_id)).unwrap();
+        assert_eq!(
+            InterestSvc::rate(&context, Some(other_id))
+                .unwrap()
+                .unwrap()
+                .rate_bps,
+            100
+        );
+    }
+
+    #[test]
+    fn test_catch_up_uses_the_balance_at_each_period_end() {
+        let context = create_test_context();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let deposit = |cents: i32, on: NaiveDate| {
+            diesel::insert_into(jar_entries::table)
+                .values((
+                    jar_entries::uuid.eq(Uuid::now_v7().to_string()),
+                    jar_entries::user_id.eq(user_id),
+                    jar_entries::jar.eq(String::from(Jar::Save)),
+                    jar_entries::amount_cents.eq(cents),
+                    jar_entries::kind.eq(String::from(JarEntryKind::Transfer)),
+                    jar_entries::created_at.eq(on.and_time(NaiveTime::MIN)),
+                ))
+                .execute(&mut get_conn(&context).unwrap())
+                .unwrap();
+        };
+        deposit(10_000, create_test_date(2026, 1, 10));
+        deposit(5_000, create_test_date(2026, 2, 20));
+        // After the last period, so it earns nothing yet
+        deposit(1_000, create_test_date(2026, 4, 2));
+
+        InterestSvc::set_rate(
+            &context,
+            &rate(Some(user_id), 100, InterestRounding::ExactCent),
+        )
+        .unwrap();
+        diesel::update(interest_rates::table)
+            .set(
+                interest_rates::created_at
+                    .eq(create_test_date(2026, 1, 15).and_time(NaiveTime::MIN)),
+            )
+            .execute(&mut get_conn(&context).unwrap())
+            .unwrap();
+
+        // The job was down from January until April
+        let credits =
+            InterestSvc::credit_due(&context, user_id, create_test_date(2026, 4, 5)).unwrap();
+        assert_eq!(
+            credits
+                .iter()
+                .map(|credit| (
+                    credit.period_start,
+                    credit.balance_cents,
+                    credit.amount_cents
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (create_test_date(2026, 1, 1), 10_000, 100),
+                (create_test_date(2026, 2, 1), 15_100, 151),
+                (create_test_date(2026, 3, 1), 15_251, 153),
+            ]
+        );
+        assert_eq!(
+            JarSvc::balances(&context, user_id).unwrap().save_cents,
+            16_404
+        );
+        assert!(
+            InterestSvc::credit_due(&context, user_id, create_test_date(2026, 4, 30))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,647 @@
+//! Spend, save and give jars.
+//!
+//! Each kid's earnings are split between three jars by their `JarSplit`, either as each
+//! completion is approved or when it is paid out. Jars are a ledger (`jar_entries`):
+//! cents rewards come out of the spend jar when a payout settles them, and admins can
+//! move money between jars. Kids without a split keep everything in the spend jar.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{ChoreCompletion, Jar, JarEntry, JarEntryKind, JarSplit, JarSplitTiming},
+    schema::{chore_completions, jar_entries, jar_splits},
+    svc::SavingsGoalSvc,
+};
+use anyhow::{Context, Result, bail};
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use juniper::GraphQLObject;
+use uuid::Uuid;
+
+/// Cents per jar: a kid's balances, or what one payout put in each jar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, GraphQLObject)]
+pub struct JarAmounts {
+    pub spend_cents: i32,
+    pub save_cents: i32,
+    pub give_cents: i32,
+}
+
+impl JarAmounts {
+    /// Splits `amount_cents` by the percentages of `split`; rounding leftovers stay in
+    /// the spend jar.
+    fn split(amount_cents: i32, split: &JarSplit) -> Self {
+        let share = |percent: i32| {
+            i32::try_from(i64::from(amount_cents) * i64::from(percent) / 100).unwrap_or(0)
+        };
+        let save_cents = share(split.save_percent);
+        let give_cents = share(split.give_percent);
+        Self {
+            spend_cents: amount_cents - save_cents - give_cents,
+            save_cents,
+            give_cents,
+        }
+    }
+
+    fn get(&self, jar: Jar) -> i32 {
+        match jar {
+            Jar::Spend => self.spend_cents,
+            Jar::Save => self.save_cents,
+            Jar::Give => self.give_cents,
+        }
+    }
+
+    fn add(&mut self, jar: Jar, amount_cents: i32) {
+        let cents = match jar {
+            Jar::Spend => &mut self.spend_cents,
+            Jar::Save => &mut self.save_cents,
+            Jar::Give => &mut self.give_cents,
+        };
+        *cents = cents.saturating_add(amount_cents);
+    }
+
+    pub const fn total_cents(&self) -> i32 {
+        self.spend_cents
+            .saturating_add(self.save_cents)
+            .saturating_add(self.give_cents)
+    }
+}
+
+pub struct JarSvc {}
+
+impl JarSvc {
+    /// The user's split, or everything in the spend jar when none was set.
+    pub fn split(context: &GraphQLContext, user_id: i32) -> Result<JarSplit> {
+        Self::split_in(&mut *get_conn(context)?, user_id)
+    }
+
+    fn split_in(conn: &mut SqliteConnection, user_id: i32) -> Result<JarSplit> {
+        let split = jar_splits::table
+            .filter(jar_splits::user_id.eq(user_id))
+            .select(JarSplit::as_select())
+            .first(conn)
+            .optional()
+            .context("Could not load jar split")?;
+
+        Ok(split.unwrap_or_else(|| JarSplit {
+            user_id,
+            spend_percent: 100,
+            save_percent: 0,
+            give_percent: 0,
+            apply_on: JarSplitTiming::Approval.into(),
+            updated_at: None,
+        }))
+    }
+
+    /// Replaces the user's split. It applies to what is earned from now on; jar balances
+    /// are not redistributed.
+    pub fn set_split(context: &GraphQLContext, split: &JarSplit) -> Result<JarSplit> {
+        let percents = [split.spend_percent, split.save_percent, split.give_percent];
+        if percents.iter().any(|percent| !(0..=100).contains(percent)) {
+            bail!("Jar percentages must be between 0 and 100");
+        }
+        if percents.iter().sum::<i32>() != 100 {
+            bail!("Jar percentages must add up to 100");
+        }
+
+        let split = JarSplit {
+            updated_at: Some(Utc::now().naive_utc()),
+            ..split.clone()
+        };
+        diesel::insert_into(jar_splits::table)
+            .values(&split)
+            .on_conflict(jar_splits::user_id)
+            .do_update()
+            .set(&split)
+            .execute(&mut get_conn(context)?)
+            .context("Could not save jar split")?;
+
+        Self::split(context, split.user_id)
+    }
+
+    pub fn balances(context: &GraphQLContext, user_id: i32) -> Result<JarAmounts> {
+        Self::balances_in(&mut *get_conn(context)?, user_id)
+    }
+
+    pub(crate) fn balances_in(conn: &mut SqliteConnection, user_id: i32) -> Result<JarAmounts> {
+        Self::sum_in(conn, user_id, None)
+    }
+
+    /// Totals the user's entries per jar, or only those of the payout at `paid_out_at`.
+    fn sum_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        paid_out_at: Option<NaiveDateTime>,
+    ) -> Result<JarAmounts> {
+        let mut query = jar_entries::table
+            .filter(jar_entries::user_id.eq(user_id))
+            .group_by(jar_entries::jar)
+            .select((
+                jar_entries::jar,
+                diesel::dsl::sum(jar_entries::amount_cents),
+            ))
+            .into_boxed();
+        if let Some(paid_out_at) = paid_out_at {
+            query = query.filter(jar_entries::paid_out_at.eq(paid_out_at));
+        }
+        let totals: Vec<(String, Option<i64>)> =
+            query.load(conn).context("Could not total jars")?;
+
+        let mut amounts = JarAmounts::default();
+        for (jar, cents) in totals {
+            amounts.add(
+                Jar::from(jar),
+                i32::try_from(cents.unwrap_or(0)).unwrap_or(i32::MAX),
+            );
+        }
+        Ok(amounts)
+    }
+
+    /// The user's jar history, newest first.
+    pub fn entries(
+        context: &GraphQLContext,
+        user_id: i32,
+        jar: Option<Jar>,
+    ) -> Result<Vec<JarEntry>> {
+        let mut query = jar_entries::table
+            .filter(jar_entries::user_id.eq(user_id))
+            .into_boxed();
+        if let Some(jar) = jar {
+            query = query.filter(jar_entries::jar.eq(String::from(jar)));
+        }
+        query
+            .select(JarEntry::as_select())
+            .order_by((jar_entries::created_at.desc(), jar_entries::id.desc()))
+            .load(&mut get_conn(context)?)
+            .context("Could not load jar entries")
+    }
+
+    /// Moves `amount_cents` from one of the user's jars to another. A jar cannot give
+    /// more than it holds, and the save jar cannot drop below what is set aside for
+    /// savings goals.
+    pub fn transfer(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: Jar,
+        to: Jar,
+        amount_cents: i32,
+        note: Option<String>,
+        admin_id: i32,
+    ) -> Result<JarAmounts> {
+        if amount_cents <= 0 {
+            bail!("Transfers need an amount above zero");
+        }
+        if from == to {
+            bail!("Transfers need two different jars");
+        }
+
+        let balances = get_conn(context)?.immediate_transaction(|conn| {
+            let balances = Self::balances_in(conn, user_id)?;
+            if balances.get(from) < amount_cents {
+                bail!("The jar does not hold that much");
+            }
+            if from == Jar::Save
+                && i64::from(balances.save_cents - amount_cents)
+                    < SavingsGoalSvc::allocated_to_user_in(conn, user_id)?
+            {
+                bail!("That much of the save jar is set aside for savings goals");
+            }
+            let entry = |jar: Jar, amount_cents: i32| JarEntry {
+                id: None,
+                uuid: Uuid::now_v7().to_string(),
+                user_id,
+                jar: jar.into(),
+                amount_cents,
+                kind: JarEntryKind::Transfer.into(),
+                completion_id: None,
+                paid_out_at: None,
+                note: note.clone(),
+                created_by_admin_id: Some(admin_id),
+                created_at: Some(Utc::now().naive_utc()),
+            };
+            diesel::insert_into(jar_entries::table)
+                .values(&[entry(from, -amount_cents), entry(to, amount_cents)])
+                .execute(conn)
+                .context("Could not save transfer")?;
+            Self::balances_in(conn, user_id)
+        })?;
+
+        if let Err(e) = SavingsGoalSvc::check_reached(context, user_id) {
+            tracing::warn!("Could not check savings goals: {:?}", e);
+        }
+        Ok(balances)
+    }
+
+    /// Splits an approved completion into the user's jars, unless they split at payout or
+    /// it was already split.
+    pub(crate) fn credit_completion_in(
+        conn: &mut SqliteConnection,
+        completion: &ChoreCompletion,
+    ) -> Result<()> {
+        let split = Self::split_in(conn, completion.user_id)?;
+        if JarSplitTiming::from(&split.apply_on) != JarSplitTiming::Approval {
+            return Ok(());
+        }
+        let Some(completion_id) = completion.id else {
+            return Ok(());
+        };
+        if Self::is_credited_in(conn, completion_id)? {
+            return Ok(());
+        }
+
+        let amounts = JarAmounts::split(completion.amount_cents, &split);
+        Self::insert_in(
+            conn,
+            completion.user_id,
+            amounts,
+            JarEntryKind::Completion,
+            Some(completion_id),
+            None,
+        )
+    }
+
+    fn is_credited_in(conn: &mut SqliteConnection, completion_id: i32) -> Result<bool> {
+        diesel::select(diesel::dsl::exists(
+            jar_entries::table.filter(jar_entries::completion_id.eq(completion_id)),
+        ))
+        .get_result(conn)
+        .context("Could not check jar entries")
+    }
+
+    /// Books a payout made at `paid_out_at` by splitting what was not split on approval.
+    pub(crate) fn credit_payout_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        paid_out_at: NaiveDateTime,
+    ) -> Result<()> {
+        let paid: Vec<(Option<i32>, i32)> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::paid_out_at.eq(paid_out_at))
+            .select((chore_completions::id, chore_completions::amount_cents))
+            .load(conn)
+            .context("Could not load paid completions")?;
+        let paid_ids: Vec<i32> = paid.iter().filter_map(|&(id, _)| id).collect();
+        diesel::update(jar_entries::table)
+            .filter(jar_entries::completion_id.eq_any(&paid_ids))
+            .set(jar_entries::paid_out_at.eq(paid_out_at))
+            .execute(conn)
+            .context("Could not mark jar entries as paid")?;
+
+        let split = Self::split_in(conn, user_id)?;
+        let mut uncredited_cents = 0;
+        for (completion_id, amount_cents) in paid {
+            let Some(completion_id) = completion_id else {
+                continue;
+            };
+            if Self::is_credited_in(conn, completion_id)? {
+                continue;
+            }
+            match JarSplitTiming::from(&split.apply_on) {
+                // Approved before the split was set; book it now with the payout
+                JarSplitTiming::Approval => Self::insert_in(
+                    conn,
+                    user_id,
+                    JarAmounts::split(amount_cents, &split),
+                    JarEntryKind::Completion,
+                    Some(completion_id),
+                    Some(paid_out_at),
+                )?,
+                JarSplitTiming::Payout => uncredited_cents += amount_cents,
+            }
+        }
+        Self::insert_in(
+            conn,
+            user_id,
+            JarAmounts::split(uncredited_cents, &split),
+            JarEntryKind::Payout,
+            None,
+            Some(paid_out_at),
+        )
+    }
+
+    /// Takes `redeemed_cents` of rewards settled by the payout at `paid_out_at` out of the
+    /// spend jar, which cannot give more than it holds. Returns what the payout put in
+    /// each jar.
+    pub(crate) fn debit_rewards_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        redeemed_cents: i64,
+        paid_out_at: NaiveDateTime,
+    ) -> Result<JarAmounts> {
+        let spend_cents = Self::balances_in(conn, user_id)?.spend_cents;
+        if i64::from(spend_cents) < redeemed_cents {
+            bail!("The spend jar does not hold enough to settle these rewards");
+        }
+        Self::insert_in(
+            conn,
+            user_id,
+            JarAmounts {
+                spend_cents: -i32::try_from(redeemed_cents).unwrap_or(i32::MAX),
+                ..JarAmounts::default()
+            },
+            JarEntryKind::Reward,
+            None,
+            Some(paid_out_at),
+        )?;
+
+        Self::sum_in(conn, user_id, Some(paid_out_at))
+    }
+
+    /// Books entries that cancel out what a completion put in the jars, before the
+    /// completion is deleted.
+    pub(crate) fn reverse_completion_in(
+        conn: &mut SqliteConnection,
+        completion_id: i32,
+    ) -> Result<()> {
+        let credited: Vec<JarEntry> = jar_entries::table
+            .filter(jar_entries::completion_id.eq(completion_id))
+            .select(JarEntry::as_select())
+            .load(conn)
+            .context("Could not load jar entries")?;
+        let Some(user_id) = credited.first().map(|entry| entry.user_id) else {
+            return Ok(());
+        };
+
+        let mut amounts = JarAmounts::default();
+        for entry in &credited {
+            amounts.add(Jar::from(&entry.jar), -entry.amount_cents);
+        }
+        Self::insert_in(conn, user_id, amounts, JarEntryKind::Completion, None, None)
+    }
+
+    /// Inserts one entry per jar with a non-zero amount.
+    fn insert_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        amounts: JarAmounts,
+        kind: JarEntryKind,
+        completion_id: Option<i32>,
+        paid_out_at: Option<NaiveDateTime>,
+    ) -> Result<()> {
+        let entries: Vec<JarEntry> = [Jar::Spend, Jar::Save, Jar::Give]
+            .into_iter()
+            .filter(|&jar| amounts.get(jar) != 0)
+            .map(|jar| JarEntry {
+                id: None,
+                uuid: Uuid::now_v7().to_string(),
+                user_id,
+                jar: jar.into(),
+                amount_cents: amounts.get(jar),
+                kind: kind.into(),
+                completion_id,
+                paid_out_at,
+                note: None,
+                created_by_admin_id: None,
+                created_at: Some(Utc::now().naive_utc()),
+            })
+            .collect();
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        diesel::insert_into(jar_entries::table)
+            .values(&entries)
+            .execute(conn)
+            .context("Could not save jar entries")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{PaymentType, RewardCurrency, RewardInput},
+        svc::{ChoreCompletionSvc, RewardSvc, chore_completion::ChoreCompletionFilter},
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    /// Approves one completion of a new daily chore worth `cents`.
+    fn earn(context: &GraphQLContext, user_id: i32, admin_id: i32, cents: i32) {
+        let chore = create_test_chore(
+            context,
+            "Dishes",
+            PaymentType::Daily,
+            cents,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        create_approved_test_completion(
+            context,
+            chore.id.unwrap(),
+            user_id,
+            create_test_date(2026, 4, 6),
+            admin_id,
+        );
+    }
+
+    fn split(user_id: i32, percents: [i32; 3], apply_on: JarSplitTiming) -> JarSplit {
+        JarSplit {
+            user_id,
+            spend_percent: percents[0],
+            save_percent: percents[1],
+            give_percent: percents[2],
+            apply_on: apply_on.into(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_split_on_approval_and_rewards_come_out_of_spend() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        JarSvc::set_split(
+            &context,
+            &split(user_id, [50, 30, 20], JarSplitTiming::Approval),
+        )
+        .unwrap();
+
+        earn(&context, user_id, admin_id, 333);
+        assert_eq!(
+            JarSvc::balances(&context, user_id).unwrap(),
+            JarAmounts {
+                spend_cents: 168,
+                save_cents: 99,
+                give_cents: 66,
+            }
+        );
+
+        let reward = RewardSvc::create(
+            &context,
+            &RewardInput {
+                uuid: None,
+                name: "Comic".to_owned(),
+                description: None,
+                currency: RewardCurrency::Cents,
+                cost: 100,
+                active: None,
+                position: None,
+            }
+            .into(),
+        )
+        .unwrap();
+        let redemption = RewardSvc::request(&context, &reward.uuid, user_id).unwrap();
+        RewardSvc::approve(&context, &redemption.uuid, admin_id).unwrap();
+        ChoreCompletionSvc::mark_as_paid(&context, Some(user_id)).unwrap();
+
+        let jars = JarSvc::balances(&context, user_id).unwrap();
+        assert_eq!(
+            (jars.spend_cents, jars.save_cents, jars.give_cents),
+            (68, 99, 66)
+        );
+        assert_eq!(jars.total_cents(), 233);
+        let entries = JarSvc::entries(&context, user_id, None).unwrap();
+        assert!(entries.iter().all(|entry| entry.paid_out_at.is_some()));
+        assert_eq!(
+            entries
+                .iter()
+                .filter(|entry| JarEntryKind::from(&entry.kind) == JarEntryKind::Reward)
+                .map(|entry| entry.amount_cents)
+                .collect::<Vec<_>>(),
+            vec![-100]
+        );
+    }
+
+    #[test]
+    fn test_rewards_never_overdraw_the_spend_jar() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        JarSvc::set_split(
+            &context,
+            &split(user_id, [20, 60, 20], JarSplitTiming::Payout),
+        )
+        .unwrap();
+
+        let reward = RewardSvc::create(
+            &context,
+            &RewardInput {
+                uuid: None,
+                name: "Comic".to_owned(),
+                description: None,
+                currency: RewardCurrency::Cents,
+                cost: 100,
+                active: None,
+                position: None,
+            }
+            .into(),
+        )
+        .unwrap();
+        earn(&context, user_id, admin_id, 300);
+        let redemption = RewardSvc::request(&context, &reward.uuid, user_id).unwrap();
+        RewardSvc::approve(&context, &redemption.uuid, admin_id).unwrap();
+
+        // The payout covers the reward, but only 60 of it reaches the spend jar
+        ChoreCompletionSvc::mark_as_paid(&context, Some(user_id)).unwrap();
+        let jars = JarSvc::balances(&context, user_id).unwrap();
+        assert_eq!(
+            (jars.spend_cents, jars.save_cents, jars.give_cents),
+            (0, 180, 60)
+        );
+
+        // The rest is settled by the next payout
+        earn(&context, user_id, admin_id, 300);
+        ChoreCompletionSvc::mark_as_paid(&context, Some(user_id)).unwrap();
+        let jars = JarSvc::balances(&context, user_id).unwrap();
+        assert_eq!(
+            (jars.spend_cents, jars.save_cents, jars.give_cents),
+            (20, 360, 120)
+        );
+        assert!(
+            RewardSvc::get_redemption(&context, &redemption.uuid)
+                .unwrap()
+                .paid_out_at
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_split_on_payout_and_transfers() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        assert!(
+            JarSvc::set_split(
+                &context,
+                &split(user_id, [60, 50, 0], JarSplitTiming::Payout)
+            )
+            .is_err()
+        );
+        JarSvc::set_split(
+            &context,
+            &split(user_id, [60, 40, 0], JarSplitTiming::Payout),
+        )
+        .unwrap();
+
+        earn(&context, user_id, admin_id, 250);
+        assert_eq!(
+            JarSvc::balances(&context, user_id).unwrap(),
+            JarAmounts::default()
+        );
+        ChoreCompletionSvc::mark_as_paid(&context, Some(user_id)).unwrap();
+        let jars = JarSvc::balances(&context, user_id).unwrap();
+        assert_eq!(
+            (jars.spend_cents, jars.save_cents, jars.give_cents),
+            (150, 100, 0)
+        );
+
+        let jars =
+            JarSvc::transfer(&context, user_id, Jar::Save, Jar::Give, 30, None, admin_id).unwrap();
+        assert_eq!(
+            (jars.spend_cents, jars.save_cents, jars.give_cents),
+            (150, 70, 30)
+        );
+        assert!(
+            JarSvc::transfer(&context, user_id, Jar::Save, Jar::Spend, 71, None, admin_id).is_err()
+        );
+        assert!(
+            JarSvc::transfer(&context, user_id, Jar::Give, Jar::Give, 10, None, admin_id).is_err()
+        );
+    }
+
+    #[test]
+    fn test_deleting_a_completion_keeps_its_jar_history() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        JarSvc::set_split(
+            &context,
+            &split(user_id, [50, 50, 0], JarSplitTiming::Approval),
+        )
+        .unwrap();
+
+        earn(&context, user_id, admin_id, 200);
+        earn(&context, user_id, admin_id, 100);
+        let mut completions =
+            ChoreCompletionSvc::list(&context, &ChoreCompletionFilter::default()).unwrap();
+        completions.sort_by_key(|completion| -completion.amount_cents);
+        ChoreCompletionSvc::delete(&context, &completions[0].uuid).unwrap();
+
+        let jars = JarSvc::balances(&context, user_id).unwrap();
+        assert_eq!(
+            (jars.spend_cents, jars.save_cents, jars.give_cents),
+            (50, 50, 0)
+        );
+        let entries = JarSvc::entries(&context, user_id, None).unwrap();
+        assert_eq!(entries.len(), 6);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.amount_cents)
+                .filter(|&cents| cents < 0)
+                .sum::<i32>(),
+            -200
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{context::GraphQLContext, db::get_conn, schema::scheduled_job_runs};
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+pub struct JobSvc {}
+
+impl JobSvc {
+    pub fn last_run(context: &GraphQLContext, name: &str) -> Result<Option<NaiveDateTime>> {
+        scheduled_job_runs::table
+            .filter(scheduled_job_runs::name.eq(name))
+            .select(scheduled_job_runs::last_run_at)
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not load scheduled job run")
+    }
+
+    /// Records `slot` as the latest run of job `name` and reports whether the caller should
+    /// run it now. Only one caller wins each slot, and a job seen for the first time is just
+    /// recorded so a fresh install doesn't fire every job immediately.
+    pub fn claim_run(context: &GraphQLContext, name: &str, slot: NaiveDateTime) -> Result<bool> {
+        get_conn(context)?.immediate_transaction(|conn| {
+            let last_run: Option<NaiveDateTime> = scheduled_job_runs::table
+                .filter(scheduled_job_runs::name.eq(name))
+                .select(scheduled_job_runs::last_run_at)
+                .first(conn)
+                .optional()
+                .context("Could not load scheduled job run")?;
+
+            if last_run.is_some_and(|last_run| last_run >= slot) {
+                return Ok(false);
+            }
+
+            diesel::replace_into(scheduled_job_runs::table)
+                .values((
+                    scheduled_job_runs::name.eq(name),
+                    scheduled_job_runs::last_run_at.eq(slot),
+                ))
+                .execute(conn)
+                .context("Could not record scheduled job run")?;
+
+            Ok(last_run.is_some())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::test_db::{create_test_context, create_test_date};
+
+    #[test]
+    fn test_claim_run_once_per_slot() {
+        let context = create_test_context();
+        let first_slot = create_test_date(2026, 4, 5).and_hms_opt(18, 0, 0).unwrap();
+        let next_slot = create_test_date(2026, 4, 12).and_hms_opt(18, 0, 0).unwrap();
+
+        // First sighting only records the slot
+        assert!(!JobSvc::claim_run(&context, "weekly", first_slot).unwrap());
+        assert_eq!(
+            JobSvc::last_run(&context, "weekly").unwrap(),
+            Some(first_slot)
+        );
+        assert!(!JobSvc::claim_run(&context, "weekly", first_slot).unwrap());
+
+        assert!(JobSvc::claim_run(&context, "weekly", next_slot).unwrap());
+        assert!(!JobSvc::claim_run(&context, "weekly", next_slot).unwrap());
+        assert_eq!(
+            JobSvc::last_run(&context, "weekly").unwrap(),
+            Some(next_slot)
+        );
+        assert_eq!(JobSvc::last_run(&context, "other").unwrap(), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! Sibling leaderboards.
+//!
+//! Kids are ranked over a date range by completion rate, longest perfect-day streak or
+//! points. Raw streaks and points favour whoever has the most (or the priciest) chores, so
+//! a leaderboard can be normalized by each kid's workload: streaks become a share of the
+//! days something was due and points are counted per due chore. Completion rate is a share
+//! of the workload already and is the same either way.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::User,
+    schema::chore_completions,
+    svc::{ScheduleSvc, UserSvc},
+};
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use juniper::{GraphQLEnum, GraphQLObject};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum LeaderboardMetric {
+    CompletionRate,
+    /// Longest run of perfect days within the range.
+    Streak,
+    /// Points from approved completions dated within the range.
+    Points,
+}
+
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(context = GraphQLContext)]
+pub struct LeaderboardEntry {
+    /// 1 for the best score; tied scores share a rank. `None` for kids without a score.
+    pub rank: Option<i32>,
+    pub user: User,
+    /// What the leaderboard is ranked by: `value`, or `value` over the workload when
+    /// normalized. `None` when the kid had nothing due.
+    pub score: Option<f64>,
+    /// The metric before normalization.
+    pub value: f64,
+    /// Recurring chore occurrences due in the range.
+    pub expected: i32,
+    /// Days in the range with at least one chore due.
+    pub due_days: i32,
+}
+
+pub struct LeaderboardSvc {}
+
+impl LeaderboardSvc {
+    /// Every kid not in `exclude_user_ids`, best score first. Kids without a score come
+    /// last, by name.
+    pub fn build(
+        context: &GraphQLContext,
+        metric: LeaderboardMetric,
+        from: NaiveDate,
+        to: NaiveDate,
+        normalize: bool,
+        exclude_user_ids: &[i32],
+    ) -> Result<Vec<LeaderboardEntry>> {
+        ScheduleSvc::check_range(from, to)?;
+
+        let mut entries = Vec::new();
+        for user in UserSvc::list(context, i32::MAX, 0)? {
+            let user_id = user.id.context("User has no id")?;
+            if exclude_user_ids.contains(&user_id) {
+                continue;
+            }
+
+            let stats = ScheduleSvc::completion_stats(context, user_id, from, to)?;
+            let (value, workload) = match metric {
+                LeaderboardMetric::CompletionRate => (stats.rate.unwrap_or(0.0), None),
+                LeaderboardMetric::Streak => {
+                    (f64::from(stats.longest_streak), Some(stats.due_days))
+                }
+                LeaderboardMetric::Points => (
+                    f64::from(Self::points(context, user_id, from, to)?),
+                    Some(stats.expected),
+                ),
+            };
+            let score = match (metric, normalize, workload) {
+                (LeaderboardMetric::CompletionRate, _, _) => stats.rate,
+                (_, true, Some(workload)) => (workload > 0).then(|| value / f64::from(workload)),
+                _ => Some(value),
+            };
+
+            entries.push(LeaderboardEntry {
+                rank: None,
+                user,
+                score,
+                value,
+                expected: stats.expected,
+                due_days: stats.due_days,
+            });
+        }
+
+        entries.sort_by(|a, b| {
+            match (a.score, b.score) {
+                (Some(a_score), Some(b_score)) => b_score.total_cmp(&a_score),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+            .then_with(|| a.user.name.cmp(&b.user.name))
+        });
+
+        let mut previous: Option<(f64, i32)> = None;
+        for (position, entry) in (1..).zip(entries.iter_mut()) {
+            let Some(score) = entry.score else {
+                break;
+            };
+            let rank = match previous {
+                Some((previous_score, previous_rank)) if previous_score == score => previous_rank,
+                _ => position,
+            };
+            entry.rank = Some(rank);
+            previous = Some((score, rank));
+        }
+
+        Ok(entries)
+    }
+
+    /// Points from the user's approved completions dated within `from..=to`.
+    fn points(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<i32> {
+        let points: Option<i64> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::approved.eq(true))
+            .filter(chore_completions::completed_date.between(from, to))
+            .select(diesel::dsl::sum(chore_completions::points))
+            .first(&mut get_conn(context)?)
+            .context("Could not total points")?;
+
+        Ok(i32::try_from(points.unwrap_or(0)).unwrap_or(i32::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::PaymentType,
+        schema::chores,
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_chore_assignment_on, create_test_context, create_test_date,
+            create_test_user, day_patterns,
+        },
+    };
+
+    fn chore(context: &GraphQLContext, name: &str, admin_id: i32, user_ids: &[i32]) -> i32 {
+        let chore_id = create_test_chore(
+            context,
+            name,
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        diesel::update(chores::table.filter(chores::id.eq(chore_id)))
+            .set(chores::points.eq(10))
+            .execute(&mut get_conn(context).unwrap())
+            .unwrap();
+        for &user_id in user_ids {
+            create_test_chore_assignment_on(
+                context,
+                chore_id,
+                user_id,
+                create_test_date(2026, 4, 1),
+            );
+        }
+        chore_id
+    }
+
+    fn ranking(entries: &[LeaderboardEntry]) -> Vec<(&str, Option<i32>, Option<f64>)> {
+        entries
+            .iter()
+            .map(|entry| (entry.user.name.as_str(), entry.rank, entry.score))
+            .collect()
+    }
+
+    #[test]
+    fn test_points_leaderboard_normalizes_by_workload() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let older = create_test_user(&context, "Older").id.unwrap();
+        let younger = create_test_user(&context, "Younger").id.unwrap();
+        create_test_user(&context, "Guest");
+        let chores: Vec<i32> = ["Dishes", "Laundry", "Trash"]
+            .into_iter()
+            .map(|name| chore(&context, name, admin_id, &[older]))
+            .collect();
+        let beds = chore(&context, "Beds", admin_id, &[younger]);
+
+        // Mon 6 to Sun 12: the older kid does all three chores on four days, the younger
+        // kid their one chore every day
+        for day in 6..=9 {
+            for &chore_id in &chores {
+                create_approved_test_completion(
+                    &context,
+                    chore_id,
+                    older,
+                    create_test_date(2026, 4, day),
+                    admin_id,
+                );
+            }
+        }
+        for day in 6..=12 {
+            create_approved_test_completion(
+                &context,
+                beds,
+                younger,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        }
+
+        let from = create_test_date(2026, 4, 6);
+        let to = create_test_date(2026, 4, 12);
+        let raw = LeaderboardSvc::build(&context, LeaderboardMetric::Points, from, to, false, &[])
+            .unwrap();
+        assert_eq!(
+            ranking(&raw),
+            vec![
+                ("Older", Some(1), Some(120.0)),
+                ("Younger", Some(2), Some(70.0)),
+                ("Guest", Some(3), Some(0.0)),
+            ]
+        );
+
+        // 120 points over 21 due chores against 70 over 7; nothing was due for the guest
+        let normalized =
+            LeaderboardSvc::build(&context, LeaderboardMetric::Points, from, to, true, &[])
+                .unwrap();
+        assert_eq!(
+            ranking(&normalized),
+            vec![
+                ("Younger", Some(1), Some(10.0)),
+                ("Older", Some(2), Some(120.0 / 21.0)),
+                ("Guest", None, None),
+            ]
+        );
+        assert_eq!((normalized[1].value, normalized[1].expected), (120.0, 21));
+    }
+
+    #[test]
+    fn test_rate_and_streak_leaderboards_share_tied_ranks() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let ann = create_test_user(&context, "Ann").id.unwrap();
+        let ben = create_test_user(&context, "Ben").id.unwrap();
+        let cal = create_test_user(&context, "Cal").id.unwrap();
+        let dishes = chore(&context, "Dishes", admin_id, &[ann, ben, cal]);
+
+        // Ann and Ben miss Thursday 9, Cal misses Monday 6 and Tuesday 7
+        for day in [6, 7, 8, 10, 11, 12] {
+            create_approved_test_completion(
+                &context,
+                dishes,
+                ann,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+            create_approved_test_completion(
+                &context,
+                dishes,
+                ben,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        }
+        for day in 8..=12 {
+            create_approved_test_completion(
+                &context,
+                dishes,
+                cal,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        }
+
+        let from = create_test_date(2026, 4, 6);
+        let to = create_test_date(2026, 4, 12);
+        let rate = LeaderboardSvc::build(
+            &context,
+            LeaderboardMetric::CompletionRate,
+            from,
+            to,
+            true,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            ranking(&rate),
+            vec![
+                ("Ann", Some(1), Some(6.0 / 7.0)),
+                ("Ben", Some(1), Some(6.0 / 7.0)),
+                ("Cal", Some(3), Some(5.0 / 7.0)),
+            ]
+        );
+
+        let streak =
+            LeaderboardSvc::build(&context, LeaderboardMetric::Streak, from, to, true, &[ben])
+                .unwrap();
+        assert_eq!(
+            ranking(&streak),
+            vec![
+                ("Cal", Some(1), Some(5.0 / 7.0)),
+                ("Ann", Some(2), Some(3.0 / 7.0))
+            ]
+        );
+        assert_eq!((streak[0].value, streak[0].due_days), (5.0, 7));
+    }
+    #[test]
+    fn test_leaderboard_range_is_bounded() {
+        let context = create_test_context();
+        create_test_user(&context, "Kid");
+        let from = create_test_date(2026, 1, 1);
+        for metric in [
+            LeaderboardMetric::CompletionRate,
+            LeaderboardMetric::Streak,
+            LeaderboardMetric::Points,
+        ] {
+            let build = |to| LeaderboardSvc::build(&context, metric, from, to, true, &[]);
+            assert!(build(create_test_date(2028, 1, 1)).is_ok());
+            assert!(build(create_test_date(2028, 1, 2)).is_err());
+            assert!(build(create_test_date(2025, 12, 31)).is_err());
+        }
+    }
+}
@@ -1,15 +1,63 @@
 pub mod admin;
+pub mod analytics;
 pub mod badge;
+pub mod bonus_claim;
+pub mod calendar;
+pub mod calendar_feed;
+pub mod challenge;
 pub mod chore;
 pub mod chore_completion;
 pub mod chore_completion_note;
+pub mod chore_template;
+pub mod digest;
+pub mod email;
+pub mod export;
+pub mod interest;
+pub mod jar;
+pub mod job;
+pub mod leaderboard;
+pub mod notification;
+pub mod pause;
+pub mod points;
+pub mod push;
+pub mod reward;
+pub mod savings_goal;
+pub mod schedule;
+pub mod statement;
+pub mod streak;
 pub mod user;
 pub mod user_image;
+pub mod web_push;
+pub mod webhook;
 
 pub use admin::AdminSvc;
+pub use analytics::AnalyticsSvc;
 pub use badge::BadgeSvc;
+pub use bonus_claim::BonusClaimSvc;
+pub use calendar::CalendarSvc;
+pub use calendar_feed::CalendarFeedSvc;
+pub use challenge::ChallengeSvc;
 pub use chore::ChoreSvc;
 pub use chore_completion::ChoreCompletionSvc;
 pub use chore_completion_note::ChoreCompletionNoteSvc;
+pub use chore_template::ChoreTemplateSvc;
+pub use digest::DigestSvc;
+pub use email::EmailSvc;
+pub use export::ExportSvc;
+pub use interest::InterestSvc;
+pub use jar::JarSvc;
+pub use job::JobSvc;
+pub use leaderboard::LeaderboardSvc;
+pub use notification::NotificationSvc;
+pub use pause::PauseSvc;
+pub use points::PointsSvc;
+pub use push::PushSvc;
+pub use reward::RewardSvc;
+pub use savings_goal::SavingsGoalSvc;
+pub use schedule::ScheduleSvc;
+pub use statement::StatementSvc;
+pub use streak::StreakSvc;
 pub use user::UserSvc;
 pub use user_image::UserImageSvc;
+pub use web_push::WebPushSvc;
+pub use webhook::WebhookSvc;
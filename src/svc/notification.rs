@@ -0,0 +1,392 @@
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{
+        AdminNotificationPrefs, AdminNotificationPrefsInput, BonusChoreClaim, ChoreCompletion,
+    },
+    schema::{admins, bonus_chore_claims, chore_completions},
+    svc::{
+        BonusClaimSvc, ChoreCompletionSvc, ChoreSvc, DigestSvc, PushSvc, UserSvc, WebPushSvc,
+        WebhookSvc,
+        email::{EmailConfig, EmailMessage, EmailSvc},
+        jar::JarAmounts,
+    },
+};
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+
+/// Something that happened in the app that admins (or their automations) may want to hear
+/// about. Channels decide who receives it and how it is rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// A kid submitted a completion that now waits for approval.
+    CompletionSubmitted { completion_id: i32 },
+    /// An admin approved a completion.
+    CompletionApproved { completion_id: i32 },
+    /// An admin rejected a pending completion. The row is gone, so the event carries what
+    /// is left of it.
+    CompletionRejected {
+        completion_uuid: String,
+        user_id: i32,
+        chore_id: i32,
+        completed_date: NaiveDate,
+    },
+    /// Approved completions of a user were marked as paid.
+    PayoutMade {
+        user_id: i32,
+        amount_cents: i32,
+        completion_count: i32,
+        /// What the payout put in each jar.
+        jars: JarAmounts,
+    },
+    /// A user earned a badge for the first time.
+    BadgeEarned {
+        user_id: i32,
+        badge_type: String,
+        badge_name: String,
+    },
+    /// A kid reserved a slot on a bonus chore.
+    BonusChoreClaimed { claim_id: i32 },
+    /// Scheduled summary of approved, unpaid earnings.
+    WeeklyPayoutSummary,
+    /// Scheduled daily nudge about the chores a kid still has to do on `date`.
+    ChoresDueToday { user_id: i32, date: NaiveDate },
+    /// Scheduled per-kid summary of `from..=to` (see `DigestSvc`).
+    Digest { from: NaiveDate, to: NaiveDate },
+    /// A kid's savings goal reached its target for the first time.
+    SavingsGoalReached { goal_id: i32, user_id: i32 },
+    /// A family challenge met its target for the first time.
+    ChallengeCompleted { challenge_id: i32 },
+}
+
+pub struct NotificationSvc {}
+
+impl NotificationSvc {
+    /// Delivers `event` on every configured channel. Like badge awards, this is non-fatal:
+    /// failures are logged and never fail the action that raised the event.
+    pub fn notify(context: &GraphQLContext, event: &NotificationEvent) {
+        if let Err(e) = Self::email(context, event) {
+            tracing::warn!("Email notification failed for {:?}: {:?}", event, e);
+        }
+        if let Err(e) = WebhookSvc::enqueue(context, event) {
+            tracing::warn!("Webhook notification failed for {:?}: {:?}", event, e);
+        }
+        if let Err(e) = PushSvc::notify(context, event) {
+            tracing::warn!("Push notification failed for {:?}: {:?}", event, e);
+        }
+        if let Err(e) = WebPushSvc::notify(context, event) {
+            tracing::warn!("Web push notification failed for {:?}: {:?}", event, e);
+        }
+    }
+
+    pub fn preferences(context: &GraphQLContext, admin_id: i32) -> Result<AdminNotificationPrefs> {
+        admins::table
+            .filter(admins::id.eq(admin_id))
+            .select(AdminNotificationPrefs::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not load notification preferences")
+    }
+
+    pub fn update_preferences(
+        context: &GraphQLContext,
+        admin_id: i32,
+        prefs: &AdminNotificationPrefsInput,
+    ) -> Result<AdminNotificationPrefs> {
+        let unchanged = prefs.notify_pending_approval.is_none()
+            && prefs.notify_bonus_claimed.is_none()
+            && prefs.notify_weekly_summary.is_none()
+            && prefs.notify_digest.is_none();
+        if !unchanged {
+            diesel::update(admins::table)
+                .filter(admins::id.eq(admin_id))
+                .set(prefs)
+                .execute(&mut get_conn(context)?)
+                .context("Could not update notification preferences")?;
+        }
+
+        Self::preferences(context, admin_id)
+    }
+
+    /// Sends a test email to the given admin right away, surfacing any SMTP error.
+    pub fn send_test_email(context: &GraphQLContext, admin_id: i32) -> Result<()> {
+        let Some(config) = EmailConfig::from_env() else {
+            bail!("Email is not configured (set SMTP_HOST)");
+        };
+        let prefs = Self::preferences(context, admin_id)?;
+
+        EmailSvc::send(&config, &[prefs.email], &EmailMessage::test_message())
+    }
+
+    fn email(context: &GraphQLContext, event: &NotificationEvent) -> Result<()> {
+        let Some(config) = EmailConfig::from_env() else {
+            return Ok(());
+        };
+        Self::email_with(context, event, config)
+    }
+
+    fn email_with(
+        context: &GraphQLContext,
+        event: &NotificationEvent,
+        config: EmailConfig,
+    ) -> Result<()> {
+        let recipients = Self::email_recipients(context, event)?;
+        if recipients.is_empty() {
+            return Ok(());
+        }
+        let message = Self::render_email(context, event)?;
+
+        // SMTP is blocking; keep it off the async workers when running inside the server
+        let send = move || {
+            if let Err(e) = EmailSvc::send(&config, &recipients, &message) {
+                tracing::warn!("Could not send notification email: {:?}", e);
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(send);
+            }
+            Err(_) => send(),
+        }
+
+        Ok(())
+    }
+
+    /// Addresses of admins who opted in to this kind of event.
+    fn email_recipients(
+        context: &GraphQLContext,
+        event: &NotificationEvent,
+    ) -> Result<Vec<String>> {
+        let query = admins::table.select(admins::email).into_boxed();
+        let query = match event {
+            NotificationEvent::CompletionSubmitted { .. } => {
+                query.filter(admins::notify_pending_approval.eq(true))
+            }
+            NotificationEvent::BonusChoreClaimed { .. } => {
+                query.filter(admins::notify_bonus_claimed.eq(true))
+            }
+            NotificationEvent::WeeklyPayoutSummary => {
+                query.filter(admins::notify_weekly_summary.eq(true))
+            }
+            NotificationEvent::Digest { .. } => query.filter(admins::notify_digest.eq(true)),
+            // No email opt-in for these yet; other channels pick them up
+            NotificationEvent::CompletionApproved { .. }
+            | NotificationEvent::CompletionRejected { .. }
+            | NotificationEvent::PayoutMade { .. }
+            | NotificationEvent::BadgeEarned { .. }
+            | NotificationEvent::ChoresDueToday { .. }
+            | NotificationEvent::SavingsGoalReached { .. }
+            | NotificationEvent::ChallengeCompleted { .. } => return Ok(Vec::new()),
+        };
+
+        query
+            .load(&mut get_conn(context)?)
+            .context("Could not load notification recipients")
+    }
+
+    fn render_email(context: &GraphQLContext, event: &NotificationEvent) -> Result<EmailMessage> {
+        match *event {
+            NotificationEvent::CompletionSubmitted { completion_id } => {
+                let completion: ChoreCompletion = chore_completions::table
+                    .filter(chore_completions::id.eq(completion_id))
+                    .select(ChoreCompletion::as_select())
+                    .first(&mut get_conn(context)?)
+                    .context("Could not find chore completion")?;
+                let user = UserSvc::get_by_id(context, completion.user_id)?;
+                let chore = ChoreSvc::get_by_id(context, completion.chore_id)?;
+
+                Ok(EmailMessage::pending_approval(
+                    &user.name,
+                    &chore.name,
+                    completion.completed_date,
+                ))
+            }
+            NotificationEvent::BonusChoreClaimed { claim_id } => {
+                let claim: BonusChoreClaim = bonus_chore_claims::table
+                    .filter(bonus_chore_claims::id.eq(claim_id))
+                    .select(BonusChoreClaim::as_select())
+                    .first(&mut get_conn(context)?)
+                    .context("Could not find bonus chore claim")?;
+                let user = UserSvc::get_by_id(context, claim.user_id)?;
+                let chore = ChoreSvc::get_by_id(context, claim.chore_id)?;
+                let remaining = chore
+                    .max_claims
+                    .map(|cap| {
+                        BonusClaimSvc::held_count(context, claim.chore_id)
+                            .map(|held| (cap - held).max(0))
+                    })
+                    .transpose()?;
+
+                Ok(EmailMessage::bonus_claimed(
+                    &user.name,
+                    &chore.name,
+                    chore.amount_cents,
+                    remaining,
+                ))
+            }
+            NotificationEvent::WeeklyPayoutSummary => Ok(EmailMessage::weekly_summary(
+                &ChoreCompletionSvc::get_unpaid_totals(context)?,
+            )),
+            NotificationEvent::Digest { from, to } => {
+                Ok(DigestSvc::build(context, from, to)?.email())
+            }
+            _ => bail!("No email template for {:?}", event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, PaymentType},
+        svc::email::sink::SmtpSink,
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    #[test]
+    fn test_preferences_default_off_and_update_partially() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let admin_id = admin.id.unwrap();
+
+        let prefs = NotificationSvc::preferences(&context, admin_id).unwrap();
+        assert!(!prefs.notify_pending_approval);
+        assert!(!prefs.notify_bonus_claimed);
+        assert!(!prefs.notify_weekly_summary);
+
+        let prefs = NotificationSvc::update_preferences(
+            &context,
+            admin_id,
+            &AdminNotificationPrefsInput {
+                notify_pending_approval: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(prefs.notify_pending_approval);
+        assert!(!prefs.notify_weekly_summary);
+
+        let prefs = NotificationSvc::update_preferences(
+            &context,
+            admin_id,
+            &AdminNotificationPrefsInput {
+                notify_weekly_summary: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(prefs.notify_pending_approval, "Unset fields are left alone");
+        assert!(prefs.notify_weekly_summary);
+    }
+
+    #[test]
+    fn test_recipients_follow_opt_ins() {
+        let context = create_test_context();
+        let opted_in = create_test_admin(&context, "Parent", "parent@test.com");
+        create_test_admin(&context, "Other", "other@test.com");
+        NotificationSvc::update_preferences(
+            &context,
+            opted_in.id.unwrap(),
+            &AdminNotificationPrefsInput {
+                notify_bonus_claimed: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let event = NotificationEvent::BonusChoreClaimed { claim_id: 1 };
+        assert_eq!(
+            NotificationSvc::email_recipients(&context, &event).unwrap(),
+            vec!["parent@test.com"]
+        );
+        assert!(
+            NotificationSvc::email_recipients(&context, &NotificationEvent::WeeklyPayoutSummary)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_render_pending_approval_email() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Alice");
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let completion = ChoreCompletionSvc::create(
+            &context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id: chore.id.unwrap(),
+                user_id: user.id.unwrap(),
+                completed_date: create_test_date(2026, 4, 6),
+            },
+        )
+        .unwrap();
+
+        let message = NotificationSvc::render_email(
+            &context,
+            &NotificationEvent::CompletionSubmitted {
+                completion_id: completion.id.unwrap(),
+            },
+        )
+        .unwrap();
+        assert_eq!(message.subject, "Alice completed \"Dishes\"");
+        assert!(message.text.contains("2026-04-06"));
+    }
+
+    #[test]
+    fn test_weekly_summary_reaches_opted_in_admins_through_smtp() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        create_test_admin(&context, "Other", "other@test.com");
+        NotificationSvc::update_preferences(
+            &context,
+            admin.id.unwrap(),
+            &AdminNotificationPrefsInput {
+                notify_weekly_summary: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let user = create_test_user(&context, "Alice");
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            250,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        create_approved_test_completion(
+            &context,
+            chore.id.unwrap(),
+            user.id.unwrap(),
+            create_test_date(2026, 4, 6),
+            admin.id.unwrap(),
+        );
+
+        let sink = SmtpSink::start();
+        NotificationSvc::email_with(
+            &context,
+            &NotificationEvent::WeeklyPayoutSummary,
+            sink.config(),
+        )
+        .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("To: parent@test.com"));
+        assert!(messages[0].contains("Alice: $2.50"));
+    }
+}
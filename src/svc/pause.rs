@@ -0,0 +1,212 @@
+use crate::{
+    context::GraphQLContext, db::get_conn, models::PausePeriod, schema::pause_periods,
+    svc::StreakSvc,
+};
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+
+pub struct PauseSvc {}
+
+impl PauseSvc {
+    pub fn get(context: &GraphQLContext, pause_uuid: &str) -> Result<PausePeriod> {
+        pause_periods::table
+            .filter(pause_periods::uuid.eq(pause_uuid))
+            .select(PausePeriod::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find pause period")
+    }
+
+    /// Lists pauses, earliest first. `active_on` keeps only pauses covering that date.
+    pub fn list(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        active_on: Option<NaiveDate>,
+    ) -> Result<Vec<PausePeriod>> {
+        let mut query = pause_periods::table.into_boxed();
+
+        if let Some(user_id) = user_id {
+            query = query.filter(pause_periods::user_id.eq(user_id));
+        }
+        if let Some(date) = active_on {
+            query = query
+                .filter(pause_periods::start_date.le(date))
+                .filter(pause_periods::end_date.ge(date));
+        }
+
+        query
+            .select(PausePeriod::as_select())
+            .order_by((pause_periods::start_date.asc(), pause_periods::id.asc()))
+            .load(&mut get_conn(context)?)
+            .context("Could not load pause periods")
+    }
+
+    /// Pauses for `user_id` that overlap the inclusive range `from..=to`.
+    pub fn overlapping(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<PausePeriod>> {
+        pause_periods::table
+            .filter(pause_periods::user_id.eq(user_id))
+            .filter(pause_periods::start_date.le(to))
+            .filter(pause_periods::end_date.ge(from))
+            .select(PausePeriod::as_select())
+            .load(&mut get_conn(context)?)
+            .context("Could not load pause periods")
+    }
+
+    pub fn create(context: &GraphQLContext, pause: &PausePeriod) -> Result<PausePeriod> {
+        Self::validate(pause)?;
+
+        diesel::insert_into(pause_periods::table)
+            .values(pause)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create pause period")?;
+        StreakSvc::invalidate(context, Some(pause.user_id), pause.start_date)?;
+
+        Self::get(context, &pause.uuid)
+    }
+
+    pub fn update(context: &GraphQLContext, pause: &PausePeriod) -> Result<PausePeriod> {
+        Self::validate(pause)?;
+        let current = Self::get(context, &pause.uuid)?;
+
+        diesel::update(pause_periods::table)
+            .filter(pause_periods::uuid.eq(&pause.uuid))
+            .set((
+                pause_periods::chore_id.eq(pause.chore_id),
+                pause_periods::start_date.eq(pause.start_date),
+                pause_periods::end_date.eq(pause.end_date),
+                pause_periods::reason.eq(&pause.reason),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update pause period")?;
+        StreakSvc::invalidate(
+            context,
+            Some(current.user_id),
+            current.start_date.min(pause.start_date),
+        )?;
+
+        Self::get(context, &pause.uuid)
+    }
+
+    pub fn delete(context: &GraphQLContext, pause_uuid: &str) -> Result<()> {
+        let current = Self::get(context, pause_uuid).ok();
+        diesel::delete(pause_periods::table)
+            .filter(pause_periods::uuid.eq(pause_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete pause period")?;
+        if let Some(pause) = current {
+            StreakSvc::invalidate(context, Some(pause.user_id), pause.start_date)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(pause: &PausePeriod) -> Result<()> {
+        if pause.end_date < pause.start_date {
+            bail!("A pause cannot end before it starts");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{PausePeriodInput, PaymentType},
+        test_helpers::test_db::{
+            create_test_admin, create_test_chore, create_test_context, create_test_date,
+            create_test_user, day_patterns,
+        },
+    };
+
+    #[test]
+    fn test_pause_crud_and_filters() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Kid");
+        let chore = create_test_chore(
+            &context,
+            "Feed Fish",
+            PaymentType::Daily,
+            50,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let user_id = user.id.unwrap();
+
+        let vacation = PauseSvc::create(
+            &context,
+            &PausePeriodInput {
+                uuid: None,
+                user_id,
+                chore_id: None,
+                start_date: create_test_date(2026, 7, 1),
+                end_date: create_test_date(2026, 7, 7),
+                reason: Some("Camp".to_owned()),
+            }
+            .into(),
+        )
+        .unwrap();
+        let mut sick = PausePeriod::from(PausePeriodInput {
+            uuid: None,
+            user_id,
+            chore_id: chore.id,
+            start_date: create_test_date(2026, 7, 20),
+            end_date: create_test_date(2026, 7, 20),
+            reason: None,
+        });
+        sick = PauseSvc::create(&context, &sick).unwrap();
+
+        assert_eq!(
+            PauseSvc::list(&context, Some(user_id), None).unwrap().len(),
+            2
+        );
+        let active = PauseSvc::list(&context, None, Some(create_test_date(2026, 7, 3))).unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].uuid, vacation.uuid);
+
+        let overlapping = PauseSvc::overlapping(
+            &context,
+            user_id,
+            create_test_date(2026, 7, 6),
+            create_test_date(2026, 7, 31),
+        )
+        .unwrap();
+        assert_eq!(overlapping.len(), 2);
+
+        sick.end_date = create_test_date(2026, 7, 22);
+        let sick = PauseSvc::update(&context, &sick).unwrap();
+        assert_eq!(sick.end_date, create_test_date(2026, 7, 22));
+
+        PauseSvc::delete(&context, &vacation.uuid).unwrap();
+        assert_eq!(
+            PauseSvc::list(&context, Some(user_id), None).unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_pause_rejects_reversed_dates() {
+        let context = create_test_context();
+        let user = create_test_user(&context, "Kid");
+
+        let result = PauseSvc::create(
+            &context,
+            &PausePeriodInput {
+                uuid: None,
+                user_id: user.id.unwrap(),
+                chore_id: None,
+                start_date: create_test_date(2026, 7, 7),
+                end_date: create_test_date(2026, 7, 1),
+                reason: None,
+            }
+            .into(),
+        );
+        assert!(result.is_err());
+    }
+}
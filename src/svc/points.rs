@@ -0,0 +1,282 @@
+//! Points, XP and levels.
+//!
+//! Points are earned like money: chores award them, completions snapshot them, and they
+//! count once the completion is approved. XP is everything ever earned and only grows;
+//! the balance is what is left to spend on rewards (see `RewardSvc`). Levels come from
+//! XP thresholds, which a user can override for themselves.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{LevelThreshold, RedemptionStatus, RewardCurrency},
+    schema::{chore_completions, level_thresholds, reward_redemptions},
+};
+use anyhow::{Context, Result, bail};
+use diesel::prelude::*;
+use juniper::GraphQLObject;
+
+/// A user's level, with the XP bounds of the level for progress bars.
+#[derive(Debug, Clone, PartialEq, Eq, GraphQLObject)]
+pub struct UserLevel {
+    pub level: i32,
+    pub xp: i32,
+    /// XP at which the current level was reached.
+    pub level_xp: i32,
+    /// XP needed for the next level; `None` at the top level.
+    pub next_level_xp: Option<i32>,
+}
+
+impl UserLevel {
+    /// Places `xp` between `thresholds`, which must be ordered by level.
+    fn from_xp(xp: i32, thresholds: &[LevelThreshold]) -> Self {
+        let reached = thresholds
+            .iter()
+            .take_while(|threshold| threshold.xp <= xp)
+            .last();
+        Self {
+            level: reached.map_or(1, |threshold| threshold.level),
+            xp,
+            level_xp: reached.map_or(0, |threshold| threshold.xp),
+            next_level_xp: thresholds
+                .iter()
+                .find(|threshold| threshold.xp > xp)
+                .map(|threshold| threshold.xp),
+        }
+    }
+}
+
+pub struct PointsSvc {}
+
+impl PointsSvc {
+    /// Points from approved completions, ever.
+    pub fn xp(context: &GraphQLContext, user_id: i32) -> Result<i32> {
+        Self::xp_in(&mut *get_conn(context)?, user_id)
+    }
+
+    /// Points available to spend: XP less approved point redemptions.
+    pub fn balance(context: &GraphQLContext, user_id: i32) -> Result<i32> {
+        Self::balance_in(&mut *get_conn(context)?, user_id)
+    }
+
+    fn xp_in(conn: &mut SqliteConnection, user_id: i32) -> Result<i32> {
+        let xp: Option<i64> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::approved.eq(true))
+            .select(diesel::dsl::sum(chore_completions::points))
+            .first(conn)
+            .context("Could not total points")?;
+
+        Ok(i32::try_from(xp.unwrap_or(0)).unwrap_or(i32::MAX))
+    }
+
+    pub(crate) fn balance_in(conn: &mut SqliteConnection, user_id: i32) -> Result<i32> {
+        let spent: Option<i64> = reward_redemptions::table
+            .filter(reward_redemptions::user_id.eq(user_id))
+            .filter(reward_redemptions::currency.eq(String::from(RewardCurrency::Points)))
+            .filter(reward_redemptions::status.eq(String::from(RedemptionStatus::Approved)))
+            .select(diesel::dsl::sum(reward_redemptions::cost))
+            .first(conn)
+            .context("Could not total redeemed points")?;
+
+        let spent = i32::try_from(spent.unwrap_or(0)).unwrap_or(i32::MAX);
+        Ok(Self::xp_in(conn, user_id)?.saturating_sub(spent))
+    }
+
+    pub fn level(context: &GraphQLContext, user_id: i32) -> Result<UserLevel> {
+        let thresholds = Self::thresholds(context, Some(user_id))?;
+        Ok(UserLevel::from_xp(Self::xp(context, user_id)?, &thresholds))
+    }
+
+    /// Thresholds in effect for `user_id`: their own when they have any, else the
+    /// household defaults. `None` lists the defaults.
+    pub fn thresholds(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+    ) -> Result<Vec<LevelThreshold>> {
+        let mut conn = get_conn(context)?;
+        if let Some(user_id) = user_id {
+            let own = level_thresholds::table
+                .filter(level_thresholds::user_id.eq(user_id))
+                .select(LevelThreshold::as_select())
+                .order_by(level_thresholds::level.asc())
+                .load(&mut conn)
+                .context("Could not load level thresholds")?;
+            if !own.is_empty() {
+                return Ok(own);
+            }
+        }
+
+        level_thresholds::table
+            .filter(level_thresholds::user_id.is_null())
+            .select(LevelThreshold::as_select())
+            .order_by(level_thresholds::level.asc())
+            .load(&mut conn)
+            .context("Could not load level thresholds")
+    }
+
+    /// Replaces the thresholds of `user_id` (or the household defaults): `xp[0]` is the XP
+    /// for level 2, `xp[1]` for level 3 and so on. An empty list puts a user back on the
+    /// defaults.
+    pub fn set_thresholds(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        xp: &[i32],
+    ) -> Result<Vec<LevelThreshold>> {
+        if user_id.is_none() && xp.is_empty() {
+            bail!("The household needs at least one level threshold");
+        }
+        if xp.first().is_some_and(|&first| first <= 0) {
+            bail!("Level thresholds must be positive");
+        }
+        if xp.windows(2).any(|pair| pair[0] >= pair[1]) {
+            bail!("Each level must need more XP than the one before");
+        }
+
+        let rows: Vec<LevelThreshold> = (2..)
+            .zip(xp)
+            .map(|(level, &xp)| LevelThreshold {
+                id: None,
+                user_id,
+                level,
+                xp,
+            })
+            .collect();
+        get_conn(context)?.immediate_transaction(|conn| {
+            let mut existing = diesel::delete(level_thresholds::table).into_boxed();
+            existing = match user_id {
+                Some(user_id) => existing.filter(level_thresholds::user_id.eq(user_id)),
+                None => existing.filter(level_thresholds::user_id.is_null()),
+            };
+            existing
+                .execute(conn)
+                .context("Could not clear level thresholds")?;
+            diesel::insert_into(level_thresholds::table)
+                .values(&rows)
+                .execute(conn)
+                .context("Could not save level thresholds")?;
+            anyhow::Ok(())
+        })?;
+
+        Self::thresholds(context, user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, ChoreInput, PaymentType},
+        svc::{ChoreCompletionSvc, ChoreSvc},
+        test_helpers::test_db::{
+            create_test_admin, create_test_context, create_test_date, create_test_user,
+            day_patterns,
+        },
+    };
+
+    #[test]
+    fn test_points_split_and_count_once_approved() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let chore = ChoreSvc::create(
+            &context,
+            &ChoreInput {
+                uuid: None,
+                name: "Feed the cat".to_owned(),
+                description: None,
+                payment_type: PaymentType::Weekly,
+                amount_cents: 0,
+                required_days: day_patterns::mon_wed_fri(),
+                active: Some(true),
+                created_by_admin_id: admin_id,
+                bonus_date: None,
+                max_claims: None,
+                points: Some(30),
+            }
+            .into(),
+        )
+        .unwrap();
+
+        let mut completions = Vec::new();
+        for day in [6, 8, 10] {
+            let completion = ChoreCompletionSvc::create(
+                &context,
+                &ChoreCompletionInput {
+                    uuid: None,
+                    chore_id: chore.id.unwrap(),
+                    user_id,
+                    completed_date: create_test_date(2026, 4, day),
+                },
+            )
+            .unwrap();
+            assert_eq!(completion.points, 10);
+            assert_eq!(completion.amount_cents, 0);
+            completions.push(completion);
+        }
+        assert_eq!(PointsSvc::xp(&context, user_id).unwrap(), 0);
+
+        for completion in &completions[..2] {
+            ChoreCompletionSvc::approve(&context, &completion.uuid, admin_id).unwrap();
+        }
+        assert_eq!(PointsSvc::xp(&context, user_id).unwrap(), 20);
+        assert_eq!(PointsSvc::balance(&context, user_id).unwrap(), 20);
+    }
+
+    #[test]
+    fn test_levels_use_user_thresholds_over_defaults() {
+        let thresholds = |xp: &[i32]| -> Vec<LevelThreshold> {
+            (2..)
+                .zip(xp)
+                .map(|(level, &xp)| LevelThreshold {
+                    id: None,
+                    user_id: None,
+                    level,
+                    xp,
+                })
+                .collect()
+        };
+        let defaults = thresholds(&[100, 250]);
+        assert_eq!(
+            UserLevel::from_xp(0, &defaults),
+            UserLevel {
+                level: 1,
+                xp: 0,
+                level_xp: 0,
+                next_level_xp: Some(100),
+            }
+        );
+        assert_eq!(UserLevel::from_xp(100, &defaults).level, 2);
+        let top = UserLevel::from_xp(300, &defaults);
+        assert_eq!((top.level, top.level_xp, top.next_level_xp), (3, 250, None));
+
+        let context = create_test_context();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        assert_eq!(
+            PointsSvc::thresholds(&context, Some(user_id))
+                .unwrap()
+                .len(),
+            5
+        );
+
+        PointsSvc::set_thresholds(&context, Some(user_id), &[10, 20]).unwrap();
+        let own = PointsSvc::thresholds(&context, Some(user_id)).unwrap();
+        assert_eq!(
+            own.iter().map(|t| (t.level, t.xp)).collect::<Vec<_>>(),
+            vec![(2, 10), (3, 20)]
+        );
+        assert_eq!(PointsSvc::thresholds(&context, None).unwrap().len(), 5);
+
+        assert!(PointsSvc::set_thresholds(&context, Some(user_id), &[20, 20]).is_err());
+        assert!(PointsSvc::set_thresholds(&context, None, &[]).is_err());
+
+        PointsSvc::set_thresholds(&context, Some(user_id), &[]).unwrap();
+        assert_eq!(
+            PointsSvc::thresholds(&context, Some(user_id))
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+}
@@ -0,0 +1,533 @@
+//! Self-hosted push channel for ntfy and Gotify.
+//!
+//! Admin targets are told about submissions waiting for approval and get the scheduled
+//! digest; kid targets get a reminder of the chores still due today and hear about reached
+//! savings goals and completed challenges. ntfy messages are published as JSON to the
+//! server root (`{"topic", "title", "message"}`, with a bearer token when one is set);
+//! Gotify messages go to `/message` with the application token in `X-Gotify-Key`.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{ChoreCompletion, PushProvider, PushTarget},
+    schema::{chore_completions, push_targets},
+    svc::{
+        ChallengeSvc, ChoreSvc, DigestSvc, SavingsGoalSvc, ScheduleSvc, UserSvc, digest::KidDigest,
+        email::format_cents, notification::NotificationEvent,
+    },
+};
+use anyhow::{Context, Result, bail};
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use serde_json::json;
+
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Gotify priorities run 0-10; 5 shows a notification without being intrusive.
+const GOTIFY_PRIORITY: i32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushMessage {
+    pub title: String,
+    pub message: String,
+}
+
+pub struct PushSvc {}
+
+impl PushSvc {
+    pub fn get(context: &GraphQLContext, target_uuid: &str) -> Result<PushTarget> {
+        push_targets::table
+            .filter(push_targets::uuid.eq(target_uuid))
+            .select(PushTarget::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find push target")
+    }
+
+    pub fn list(
+        context: &GraphQLContext,
+        admin_id: Option<i32>,
+        user_id: Option<i32>,
+    ) -> Result<Vec<PushTarget>> {
+        let mut query = push_targets::table.into_boxed();
+
+        if let Some(admin_id) = admin_id {
+            query = query.filter(push_targets::admin_id.eq(admin_id));
+        }
+        if let Some(user_id) = user_id {
+            query = query.filter(push_targets::user_id.eq(user_id));
+        }
+
+        query
+            .select(PushTarget::as_select())
+            .order_by(push_targets::id.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load push targets")
+    }
+
+    pub fn create(context: &GraphQLContext, target: &PushTarget) -> Result<PushTarget> {
+        Self::validate(target)?;
+
+        diesel::insert_into(push_targets::table)
+            .values(target)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create push target")?;
+
+        Self::get(context, &target.uuid)
+    }
+
+    /// Updates the target; an empty token keeps the stored one.
+    pub fn update(context: &GraphQLContext, target: &PushTarget) -> Result<PushTarget> {
+        let current = Self::get(context, &target.uuid)?;
+        let mut target = target.clone();
+        if target.token.as_deref().is_none_or(str::is_empty) {
+            target.token = current.token;
+        }
+        Self::validate(&target)?;
+
+        diesel::update(push_targets::table)
+            .filter(push_targets::uuid.eq(&target.uuid))
+            .set((
+                push_targets::provider.eq(&target.provider),
+                push_targets::server_url.eq(&target.server_url),
+                push_targets::topic.eq(&target.topic),
+                push_targets::token.eq(&target.token),
+                push_targets::admin_id.eq(target.admin_id),
+                push_targets::user_id.eq(target.user_id),
+                push_targets::active.eq(target.active),
+                push_targets::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update push target")?;
+
+        Self::get(context, &target.uuid)
+    }
+
+    pub fn delete(context: &GraphQLContext, target_uuid: &str) -> Result<()> {
+        diesel::delete(push_targets::table)
+            .filter(push_targets::uuid.eq(target_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete push target")?;
+
+        Ok(())
+    }
+
+    /// Sends a test message to one target right away, surfacing any error.
+    pub fn send_test(context: &GraphQLContext, target_uuid: &str) -> Result<()> {
+        let target = Self::get(context, target_uuid)?;
+        Self::send(
+            &Self::client()?,
+            &target,
+            &PushMessage {
+                title: "Chore Tracker".to_owned(),
+                message: "Push notifications are working.".to_owned(),
+            },
+        )
+    }
+
+    /// Pushes `event` to the targets that want it. Sending happens on the blocking pool
+    /// when running inside the server and inline otherwise.
+    pub fn notify(context: &GraphQLContext, event: &NotificationEvent) -> Result<()> {
+        let targets = Self::recipients(context, event)?;
+        if targets.is_empty() {
+            return Ok(());
+        }
+        let Some(message) = Self::render(context, event)? else {
+            return Ok(());
+        };
+
+        let send = move || {
+            let client = match Self::client() {
+                Ok(client) => client,
+                Err(e) => {
+                    tracing::warn!("Could not send push notifications: {:?}", e);
+                    return;
+                }
+            };
+            for target in &targets {
+                if let Err(e) = Self::send(&client, target, &message) {
+                    tracing::warn!("Could not push to target {}: {:?}", target.uuid, e);
+                }
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(send);
+            }
+            Err(_) => send(),
+        }
+
+        Ok(())
+    }
+
+    pub fn send(
+        client: &reqwest::blocking::Client,
+        target: &PushTarget,
+        message: &PushMessage,
+    ) -> Result<()> {
+        let server_url = target.server_url.trim_end_matches('/');
+        let request = match PushProvider::from(&target.provider) {
+            PushProvider::Ntfy => {
+                let request = client.post(server_url).json(&json!({
+                    "topic": target.topic,
+                    "title": message.title,
+                    "message": message.message,
+                }));
+                match &target.token {
+                    Some(token) => request.bearer_auth(token),
+                    None => request,
+                }
+            }
+            PushProvider::Gotify => client
+                .post(format!("{server_url}/message"))
+                .header("X-Gotify-Key", target.token.as_deref().unwrap_or_default())
+                .json(&json!({
+                    "title": message.title,
+                    "message": message.message,
+                    "priority": GOTIFY_PRIORITY,
+                })),
+        };
+
+        let response = request.send().context("Could not reach push server")?;
+        if !response.status().is_success() {
+            bail!("Push server returned {}", response.status());
+        }
+        Ok(())
+    }
+
+    fn recipients(context: &GraphQLContext, event: &NotificationEvent) -> Result<Vec<PushTarget>> {
+        let query = push_targets::table
+            .filter(push_targets::active.eq(true))
+            .select(PushTarget::as_select())
+            .into_boxed();
+        let query = match event {
+            NotificationEvent::CompletionSubmitted { .. } | NotificationEvent::Digest { .. } => {
+                query.filter(push_targets::admin_id.is_not_null())
+            }
+            NotificationEvent::ChoresDueToday { user_id, .. }
+            | NotificationEvent::SavingsGoalReached { user_id, .. } => {
+                query.filter(push_targets::user_id.eq(*user_id))
+            }
+            NotificationEvent::ChallengeCompleted { challenge_id } => {
+                let user_ids: Vec<i32> = ChallengeSvc::participants(context, *challenge_id)?
+                    .into_iter()
+                    .filter_map(|user| user.id)
+                    .collect();
+                query.filter(push_targets::user_id.eq_any(user_ids))
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        query
+            .load(&mut get_conn(context)?)
+            .context("Could not load push targets")
+    }
+
+    /// The message for `event`, or `None` when there is nothing worth sending. Shared with
+    /// Web Push.
+    pub(crate) fn render(
+        context: &GraphQLContext,
+        event: &NotificationEvent,
+    ) -> Result<Option<PushMessage>> {
+        match *event {
+            NotificationEvent::CompletionSubmitted { completion_id } => {
+                let completion = Self::completion(context, completion_id)?;
+                let user = UserSvc::get_by_id(context, completion.user_id)?;
+                let chore = ChoreSvc::get_by_id(context, completion.chore_id)?;
+
+                Ok(Some(PushMessage {
+                    title: format!("{} completed \"{}\"", user.name, chore.name),
+                    message: format!("Waiting for your approval ({})", completion.completed_date),
+                }))
+            }
+            NotificationEvent::CompletionApproved { completion_id } => {
+                let completion = Self::completion(context, completion_id)?;
+                let chore = ChoreSvc::get_by_id(context, completion.chore_id)?;
+
+                Ok(Some(PushMessage {
+                    title: format!("\"{}\" approved", chore.name),
+                    message: format!(
+                        "Nice work! You earned {}.",
+                        format_cents(completion.amount_cents.into())
+                    ),
+                }))
+            }
+            NotificationEvent::ChoresDueToday { user_id, date } => {
+                let remaining = Self::remaining_chores(context, user_id, date)?;
+                if remaining.is_empty() {
+                    return Ok(None);
+                }
+
+                Ok(Some(PushMessage {
+                    title: "Chores due today".to_owned(),
+                    message: format!("Still to do: {}", remaining.join(", ")),
+                }))
+            }
+            NotificationEvent::Digest { from, to } => {
+                let digest = DigestSvc::build(context, from, to)?;
+                let lines: Vec<String> = digest.kids.iter().map(KidDigest::summary_line).collect();
+
+                Ok(Some(PushMessage {
+                    title: digest.subject,
+                    message: lines.join("\n"),
+                }))
+            }
+            NotificationEvent::SavingsGoalReached { goal_id, .. } => {
+                let goal = SavingsGoalSvc::get_by_id(context, goal_id)?;
+
+                Ok(Some(PushMessage {
+                    title: format!("Goal reached: {}", goal.name),
+                    message: format!(
+                        "You saved {}. Time to get it!",
+                        format_cents(goal.target_cents.into())
+                    ),
+                }))
+            }
+            NotificationEvent::ChallengeCompleted { challenge_id } => {
+                let challenge = ChallengeSvc::get_by_id(context, challenge_id)?;
+                let message = challenge.prize.as_ref().map_or_else(
+                    || "You did it together!".to_owned(),
+                    |prize| format!("You did it together! Enjoy: {prize}"),
+                );
+
+                Ok(Some(PushMessage {
+                    title: format!("Challenge complete: {}", challenge.name),
+                    message,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub(crate) fn completion(
+        context: &GraphQLContext,
+        completion_id: i32,
+    ) -> Result<ChoreCompletion> {
+        chore_completions::table
+            .filter(chore_completions::id.eq(completion_id))
+            .select(ChoreCompletion::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find chore completion")
+    }
+
+    /// Names of the chores due for the user on `date` that have no completion yet.
+    fn remaining_chores(
+        context: &GraphQLContext,
+        user_id: i32,
+        date: NaiveDate,
+    ) -> Result<Vec<String>> {
+        let due = ScheduleSvc::due_chores(context, user_id, date)?;
+        let done: Vec<i32> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::completed_date.eq(date))
+            .select(chore_completions::chore_id)
+            .load(&mut get_conn(context)?)
+            .context("Could not load completions")?;
+
+        Ok(due
+            .into_iter()
+            .filter(|chore| chore.id.is_some_and(|id| !done.contains(&id)))
+            .map(|chore| chore.name)
+            .collect())
+    }
+
+    fn client() -> Result<reqwest::blocking::Client> {
+        reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Could not build HTTP client")
+    }
+
+    fn validate(target: &PushTarget) -> Result<()> {
+        let url = reqwest::Url::parse(&target.server_url)
+            .context("Push server URL is not a valid URL")?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Push server URL must use http or https");
+        }
+        if target.admin_id.is_some() == target.user_id.is_some() {
+            bail!("A push target belongs to exactly one admin or kid");
+        }
+        let blank = |value: &Option<String>| value.as_deref().is_none_or(|v| v.trim().is_empty());
+        match PushProvider::from(&target.provider) {
+            PushProvider::Ntfy if blank(&target.topic) => bail!("ntfy targets need a topic"),
+            PushProvider::Gotify if blank(&target.token) => {
+                bail!("Gotify targets need an application token")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, PaymentType, PushTargetInput},
+        svc::{ChoreCompletionSvc, NotificationSvc},
+        test_helpers::{
+            http_sink::HttpSink,
+            test_db::{
+                create_test_admin, create_test_chore, create_test_context, create_test_date,
+                create_test_user, day_patterns,
+            },
+        },
+    };
+    use serde_json::Value;
+
+    fn target_input(provider: PushProvider, server_url: &str) -> PushTargetInput {
+        PushTargetInput {
+            uuid: None,
+            provider,
+            server_url: server_url.to_owned(),
+            topic: None,
+            token: None,
+            admin_id: None,
+            user_id: None,
+            active: None,
+        }
+    }
+
+    #[test]
+    fn test_target_validation_and_token_kept_on_update() {
+        let context = create_test_context();
+        let user = create_test_user(&context, "Kid");
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+
+        // ntfy needs a topic, Gotify a token, and exactly one recipient
+        let mut ntfy = target_input(PushProvider::Ntfy, "https://ntfy.example.com");
+        ntfy.user_id = user.id;
+        assert!(PushSvc::create(&context, &ntfy.clone().into()).is_err());
+        ntfy.topic = Some("kid-chores".to_owned());
+        ntfy.admin_id = admin.id;
+        assert!(PushSvc::create(&context, &ntfy.clone().into()).is_err());
+        ntfy.admin_id = None;
+        PushSvc::create(&context, &ntfy.into()).unwrap();
+
+        let mut gotify = target_input(PushProvider::Gotify, "https://gotify.example.com");
+        gotify.admin_id = admin.id;
+        assert!(PushSvc::create(&context, &gotify.clone().into()).is_err());
+        gotify.token = Some("app-token".to_owned());
+        let created = PushSvc::create(&context, &gotify.clone().into()).unwrap();
+
+        gotify.uuid = Some(created.uuid.clone());
+        gotify.token = None;
+        gotify.active = Some(false);
+        let updated = PushSvc::update(&context, &gotify.into()).unwrap();
+        assert_eq!(updated.token.as_deref(), Some("app-token"));
+        assert!(!updated.active);
+
+        assert_eq!(PushSvc::list(&context, None, user.id).unwrap().len(), 1);
+        assert_eq!(PushSvc::list(&context, admin.id, None).unwrap().len(), 1);
+        PushSvc::delete(&context, &created.uuid).unwrap();
+        assert_eq!(PushSvc::list(&context, None, None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_submission_alert_is_published_to_ntfy() {
+        let context = create_test_context();
+        let sink = HttpSink::start(vec![200]);
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let mut input = target_input(PushProvider::Ntfy, &sink.url);
+        input.topic = Some("parents".to_owned());
+        input.token = Some("tk_secret".to_owned());
+        input.admin_id = admin.id;
+        PushSvc::create(&context, &input.into()).unwrap();
+
+        let user = create_test_user(&context, "Alice");
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        ChoreCompletionSvc::create(
+            &context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id: chore.id.unwrap(),
+                user_id: user.id.unwrap(),
+                completed_date: create_test_date(2026, 4, 6),
+            },
+        )
+        .unwrap();
+
+        let requests = sink.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].request_line, "POST / HTTP/1.1");
+        assert_eq!(
+            requests[0].header("Authorization"),
+            Some("Bearer tk_secret")
+        );
+        let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["topic"], "parents");
+        assert_eq!(body["title"], "Alice completed \"Dishes\"");
+    }
+
+    #[test]
+    fn test_due_today_reminder_lists_remaining_chores_via_gotify() {
+        let context = create_test_context();
+        let sink = HttpSink::start(vec![200]);
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Alice");
+        let user_id = user.id.unwrap();
+        let mut input = target_input(PushProvider::Gotify, &format!("{}/", sink.url));
+        input.token = Some("app-token".to_owned());
+        input.user_id = Some(user_id);
+        PushSvc::create(&context, &input.into()).unwrap();
+
+        let monday = create_test_date(2026, 4, 6);
+        for name in ["Dishes", "Trash", "Feed Fish"] {
+            let chore = create_test_chore(
+                &context,
+                name,
+                PaymentType::Daily,
+                100,
+                day_patterns::every_day(),
+                admin.id.unwrap(),
+            );
+            ChoreSvc::assign_user(&context, chore.id.unwrap(), user_id).unwrap();
+            if name == "Trash" {
+                ChoreCompletionSvc::create(
+                    &context,
+                    &ChoreCompletionInput {
+                        uuid: None,
+                        chore_id: chore.id.unwrap(),
+                        user_id,
+                        completed_date: monday,
+                    },
+                )
+                .unwrap();
+            }
+        }
+
+        NotificationSvc::notify(
+            &context,
+            &NotificationEvent::ChoresDueToday {
+                user_id,
+                date: monday,
+            },
+        );
+
+        let requests = sink.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].request_line, "POST /message HTTP/1.1");
+        assert_eq!(requests[0].header("X-Gotify-Key"), Some("app-token"));
+        let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["message"], "Still to do: Dishes, Feed Fish");
+    }
+
+    #[test]
+    fn test_no_reminder_when_everything_is_done() {
+        let context = create_test_context();
+        let user = create_test_user(&context, "Alice");
+        let message = PushSvc::render(
+            &context,
+            &NotificationEvent::ChoresDueToday {
+                user_id: user.id.unwrap(),
+                date: create_test_date(2026, 4, 6),
+            },
+        )
+        .unwrap();
+        assert_eq!(message, None);
+    }
+}
@@ -0,0 +1,633 @@
+//! Rewards store.
+//!
+//! Admins keep a catalog of rewards priced in points or cents. A kid asks for one, which
+//! leaves a pending redemption until an admin approves or rejects it. Approval takes the
+//! cost off the kid's balance: points off their points balance, cents off what they are
+//! owed. Payouts settle cents rewards, oldest first, as far as they cover them; the rest
+//! carries forward to the next payout.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{RedemptionStatus, Reward, RewardCurrency, RewardRedemption},
+    schema::{chore_completions, reward_redemptions, reward_settlements, rewards},
+    svc::PointsSvc,
+};
+use anyhow::{Context, Result, bail};
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct RewardSvc {}
+
+impl RewardSvc {
+    pub fn get(context: &GraphQLContext, reward_uuid: &str) -> Result<Reward> {
+        rewards::table
+            .filter(rewards::uuid.eq(reward_uuid))
+            .select(Reward::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find reward")
+    }
+
+    /// The catalog in display order.
+    pub fn list(context: &GraphQLContext, include_inactive: bool) -> Result<Vec<Reward>> {
+        let mut query = rewards::table.into_boxed();
+        if !include_inactive {
+            query = query.filter(rewards::active.eq(true));
+        }
+        query
+            .select(Reward::as_select())
+            .order_by((rewards::position.asc(), rewards::id.asc()))
+            .load(&mut get_conn(context)?)
+            .context("Could not load rewards")
+    }
+
+    pub fn create(context: &GraphQLContext, reward: &Reward) -> Result<Reward> {
+        Self::validate(reward)?;
+        diesel::insert_into(rewards::table)
+            .values(reward)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create reward")?;
+
+        Self::get(context, &reward.uuid)
+    }
+
+    /// Price changes only apply to new requests; pending ones keep the price they were
+    /// made at.
+    pub fn update(context: &GraphQLContext, reward: &Reward) -> Result<Reward> {
+        Self::validate(reward)?;
+        diesel::update(rewards::table)
+            .filter(rewards::uuid.eq(&reward.uuid))
+            .set((
+                rewards::name.eq(&reward.name),
+                rewards::description.eq(&reward.description),
+                rewards::currency.eq(&reward.currency),
+                rewards::cost.eq(reward.cost),
+                rewards::active.eq(reward.active),
+                rewards::position.eq(reward.position),
+                rewards::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update reward")?;
+
+        Self::get(context, &reward.uuid)
+    }
+
+    /// Removes the reward from the catalog; redemptions of it stay in the history.
+    pub fn delete(context: &GraphQLContext, reward_uuid: &str) -> Result<()> {
+        diesel::delete(rewards::table)
+            .filter(rewards::uuid.eq(reward_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete reward")?;
+
+        Ok(())
+    }
+
+    fn validate(reward: &Reward) -> Result<()> {
+        if reward.name.trim().is_empty() {
+            bail!("Rewards need a name");
+        }
+        if reward.cost < 1 {
+            bail!("Rewards must cost something");
+        }
+        Ok(())
+    }
+
+    pub fn get_redemption(
+        context: &GraphQLContext,
+        redemption_uuid: &str,
+    ) -> Result<RewardRedemption> {
+        reward_redemptions::table
+            .filter(reward_redemptions::uuid.eq(redemption_uuid))
+            .select(RewardRedemption::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find redemption")
+    }
+
+    /// Redemption history, newest first, optionally for one user and/or status.
+    pub fn redemptions(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        status: Option<RedemptionStatus>,
+    ) -> Result<Vec<RewardRedemption>> {
+        let mut query = reward_redemptions::table.into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(reward_redemptions::user_id.eq(user_id));
+        }
+        if let Some(status) = status {
+            query = query.filter(reward_redemptions::status.eq(String::from(status)));
+        }
+        query
+            .select(RewardRedemption::as_select())
+            .order_by((
+                reward_redemptions::requested_at.desc(),
+                reward_redemptions::id.desc(),
+            ))
+            .load(&mut get_conn(context)?)
+            .context("Could not load redemptions")
+    }
+
+    /// Asks for a reward. The kid must be able to afford it on top of what they already
+    /// have pending in the same currency.
+    pub fn request(
+        context: &GraphQLContext,
+        reward_uuid: &str,
+        user_id: i32,
+    ) -> Result<RewardRedemption> {
+        let reward = Self::get(context, reward_uuid)?;
+        if !reward.active {
+            bail!("This reward is not available");
+        }
+        let currency = RewardCurrency::from(&reward.currency);
+        let redemption = RewardRedemption {
+            id: None,
+            uuid: Uuid::now_v7().to_string(),
+            reward_id: reward.id,
+            user_id,
+            reward_name: reward.name,
+            currency: reward.currency,
+            cost: reward.cost,
+            status: RedemptionStatus::Pending.into(),
+            requested_at: Some(Utc::now().naive_utc()),
+            decided_at: None,
+            decided_by_admin_id: None,
+            decision_note: None,
+            paid_out_at: None,
+        };
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            let pending: Option<i64> = reward_redemptions::table
+                .filter(reward_redemptions::user_id.eq(user_id))
+                .filter(reward_redemptions::currency.eq(&redemption.currency))
+                .filter(reward_redemptions::status.eq(String::from(RedemptionStatus::Pending)))
+                .select(diesel::dsl::sum(reward_redemptions::cost))
+                .first(conn)
+                .context("Could not total pending redemptions")?;
+            let available = Self::balance_in(conn, user_id, currency)? - pending.unwrap_or(0);
+            if available < i64::from(redemption.cost) {
+                bail!(
+                    "Not enough {} for {}",
+                    String::from(currency),
+                    redemption.reward_name
+                );
+            }
+
+            diesel::insert_into(reward_redemptions::table)
+                .values(&redemption)
+                .execute(conn)
+                .context("Could not create redemption")?;
+            anyhow::Ok(())
+        })?;
+
+        Self::get_redemption(context, &redemption.uuid)
+    }
+
+    /// Approves a pending redemption, taking its cost off the kid's balance.
+    pub fn approve(
+        context: &GraphQLContext,
+        redemption_uuid: &str,
+        admin_id: i32,
+    ) -> Result<RewardRedemption> {
+        let redemption = Self::get_redemption(context, redemption_uuid)?;
+        Self::ensure_pending(&redemption)?;
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            let currency = RewardCurrency::from(&redemption.currency);
+            if Self::balance_in(conn, redemption.user_id, currency)? < i64::from(redemption.cost) {
+                bail!(
+                    "Not enough {} left for {}",
+                    String::from(currency),
+                    redemption.reward_name
+                );
+            }
+            Self::decide_in(
+                conn,
+                redemption_uuid,
+                RedemptionStatus::Approved,
+                admin_id,
+                None,
+            )
+        })?;
+
+        Self::get_redemption(context, redemption_uuid)
+    }
+
+    pub fn reject(
+        context: &GraphQLContext,
+        redemption_uuid: &str,
+        admin_id: i32,
+        note: Option<String>,
+    ) -> Result<RewardRedemption> {
+        let redemption = Self::get_redemption(context, redemption_uuid)?;
+        Self::ensure_pending(&redemption)?;
+
+        Self::decide_in(
+            &mut *get_conn(context)?,
+            redemption_uuid,
+            RedemptionStatus::Rejected,
+            admin_id,
+            note,
+        )?;
+
+        Self::get_redemption(context, redemption_uuid)
+    }
+
+    fn ensure_pending(redemption: &RewardRedemption) -> Result<()> {
+        if RedemptionStatus::from(&redemption.status) != RedemptionStatus::Pending {
+            bail!("This redemption has already been decided");
+        }
+        Ok(())
+    }
+
+    fn decide_in(
+        conn: &mut SqliteConnection,
+        redemption_uuid: &str,
+        status: RedemptionStatus,
+        admin_id: i32,
+        note: Option<String>,
+    ) -> Result<()> {
+        let updated = diesel::update(reward_redemptions::table)
+            .filter(reward_redemptions::uuid.eq(redemption_uuid))
+            .filter(reward_redemptions::status.eq(String::from(RedemptionStatus::Pending)))
+            .set((
+                reward_redemptions::status.eq(String::from(status)),
+                reward_redemptions::decided_at.eq(Utc::now().naive_utc()),
+                reward_redemptions::decided_by_admin_id.eq(admin_id),
+                reward_redemptions::decision_note.eq(note),
+            ))
+            .execute(conn)
+            .context("Could not update redemption")?;
+        if updated == 0 {
+            bail!("This redemption has already been decided");
+        }
+        Ok(())
+    }
+
+    /// What the kid can spend in `currency`: their points balance, or the cents they are
+    /// owed less approved rewards not yet settled by a payout.
+    pub fn balance(
+        context: &GraphQLContext,
+        user_id: i32,
+        currency: RewardCurrency,
+    ) -> Result<i64> {
+        Self::balance_in(&mut *get_conn(context)?, user_id, currency)
+    }
+
+    fn balance_in(
+        conn: &mut SqliteConnection,
+        user_id: i32,
+        currency: RewardCurrency,
+    ) -> Result<i64> {
+        if currency == RewardCurrency::Points {
+            return Ok(PointsSvc::balance_in(conn, user_id)?.into());
+        }
+
+        let owed: Option<i64> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::approved.eq(true))
+            .filter(chore_completions::paid_out.eq(false))
+            .select(diesel::dsl::sum(chore_completions::amount_cents))
+            .first(conn)
+            .context("Could not total unpaid completions")?;
+        let redeemed = Self::unsettled_cents_in(conn, Some(&[user_id]))?;
+
+        Ok(owed.unwrap_or(0) - redeemed.get(&user_id).copied().unwrap_or(0))
+    }
+
+    /// Approved cents redemptions not yet settled by a payout, per user.
+    pub fn unsettled_cents(context: &GraphQLContext) -> Result<HashMap<i32, i64>> {
+        Self::unsettled_cents_in(&mut *get_conn(context)?, None)
+    }
+
+    fn unsettled_cents_in(
+        conn: &mut SqliteConnection,
+        user_ids: Option<&[i32]>,
+    ) -> Result<HashMap<i32, i64>> {
+        let mut costs = reward_redemptions::table
+            .filter(reward_redemptions::currency.eq(String::from(RewardCurrency::Cents)))
+            .filter(reward_redemptions::status.eq(String::from(RedemptionStatus::Approved)))
+            .filter(reward_redemptions::paid_out_at.is_null())
+            .group_by(reward_redemptions::user_id)
+            .select((
+                reward_redemptions::user_id,
+                diesel::dsl::sum(reward_redemptions::cost),
+            ))
+            .into_boxed();
+        // What earlier payouts already settled of the redemptions that are still open
+        let mut settled = reward_settlements::table
+            .inner_join(reward_redemptions::table)
+            .filter(reward_redemptions::paid_out_at.is_null())
+            .group_by(reward_settlements::user_id)
+            .select((
+                reward_settlements::user_id,
+                diesel::dsl::sum(reward_settlements::amount_cents),
+            ))
+            .into_boxed();
+        if let Some(user_ids) = user_ids {
+            costs = costs.filter(reward_redemptions::user_id.eq_any(user_ids));
+            settled = settled.filter(reward_settlements::user_id.eq_any(user_ids));
+        }
+
+        let costs: Vec<(i32, Option<i64>)> = costs
+            .load(conn)
+            .context("Could not total unsettled redemptions")?;
+        let settled: HashMap<i32, i64> = settled
+            .load::<(i32, Option<i64>)>(conn)
+            .context("Could not total settled redemptions")?
+            .into_iter()
+            .map(|(user_id, cents)| (user_id, cents.unwrap_or(0)))
+            .collect();
+        Ok(costs
+            .into_iter()
+            .map(|(user_id, cents)| {
+                let settled = settled.get(&user_id).copied().unwrap_or(0);
+                (user_id, cents.unwrap_or(0) - settled)
+            })
+            .collect())
+    }
+
+    /// Settles the unsettled cents redemptions of the users in `budgets`, oldest first, as
+    /// far as each user's budget covers them. A redemption is marked as paid out at
+    /// `paid_out_at` once it is settled in full; the rest carries forward to the next
+    /// payout. Returns what was settled per user, for netting against the payout.
+    pub(crate) fn settle_cents_in(
+        conn: &mut SqliteConnection,
+        budgets: &HashMap<i32, i64>,
+        paid_out_at: NaiveDateTime,
+    ) -> Result<HashMap<i32, i64>> {
+        let user_ids: Vec<i32> = budgets.keys().copied().collect();
+        let unsettled: Vec<(i32, i32, i32)> = reward_redemptions::table
+            .filter(reward_redemptions::user_id.eq_any(&user_ids))
+            .filter(reward_redemptions::currency.eq(String::from(RewardCurrency::Cents)))
+            .filter(reward_redemptions::status.eq(String::from(RedemptionStatus::Approved)))
+            .filter(reward_redemptions::paid_out_at.is_null())
+            .order_by((
+                reward_redemptions::decided_at.asc(),
+                reward_redemptions::id.asc(),
+            ))
+            .select((
+                reward_redemptions::id.assume_not_null(),
+                reward_redemptions::user_id,
+                reward_redemptions::cost,
+            ))
+            .load(conn)
+            .context("Could not load unsettled redemptions")?;
+
+        let mut settled: HashMap<i32, i64> = HashMap::new();
+        for (redemption_id, user_id, cost) in unsettled {
+            let earlier: Option<i64> = reward_settlements::table
+                .filter(reward_settlements::redemption_id.eq(redemption_id))
+                .select(diesel::dsl::sum(reward_settlements::amount_cents))
+                .first(conn)
+                .context("Could not total settled redemption")?;
+            let left = i64::from(cost) - earlier.unwrap_or(0);
+            let budget = budgets.get(&user_id).copied().unwrap_or(0)
+                - settled.get(&user_id).copied().unwrap_or(0);
+            let amount_cents = left.min(budget);
+            if amount_cents <= 0 {
+                continue;
+            }
+
+            diesel::insert_into(reward_settlements::table)
+                .values((
+                    reward_settlements::redemption_id.eq(redemption_id),
+                    reward_settlements::user_id.eq(user_id),
+                    reward_settlements::amount_cents
+                        .eq(i32::try_from(amount_cents).context("Settlement is too large")?),
+                    reward_settlements::paid_out_at.eq(paid_out_at),
+                ))
+                .execute(conn)
+                .context("Could not settle redemption")?;
+            if amount_cents == left {
+                diesel::update(reward_redemptions::table)
+                    .filter(reward_redemptions::id.eq(redemption_id))
+                    .set(reward_redemptions::paid_out_at.eq(paid_out_at))
+                    .execute(conn)
+                    .context("Could not mark redemption as paid out")?;
+            }
+            *settled.entry(user_id).or_default() += amount_cents;
+        }
+
+        Ok(settled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreInput, PaymentType, RewardInput},
+        svc::{
+            ChoreCompletionSvc, ChoreSvc, JarSvc, StatementSvc,
+            chore_completion::ChoreCompletionFilter,
+        },
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_context,
+            create_test_date, create_test_user, day_patterns,
+        },
+    };
+    use chrono::Datelike;
+
+    /// Approves one completion of a new chore worth `cents` and `points`.
+    fn earn(context: &GraphQLContext, user_id: i32, admin_id: i32, cents: i32, points: i32) {
+        let chore = ChoreSvc::create(
+            context,
+            &ChoreInput {
+                uuid: None,
+                name: format!("Chore worth {cents}c/{points}p"),
+                description: None,
+                payment_type: PaymentType::Daily,
+                amount_cents: cents,
+                required_days: day_patterns::every_day(),
+                active: Some(true),
+                created_by_admin_id: admin_id,
+                bonus_date: None,
+                max_claims: None,
+                points: Some(points),
+            }
+            .into(),
+        )
+        .unwrap();
+        create_approved_test_completion(
+            context,
+            chore.id.unwrap(),
+            user_id,
+            create_test_date(2026, 4, 6),
+            admin_id,
+        );
+    }
+
+    fn reward(context: &GraphQLContext, name: &str, currency: RewardCurrency, cost: i32) -> Reward {
+        RewardSvc::create(
+            context,
+            &RewardInput {
+                uuid: None,
+                name: name.to_owned(),
+                description: None,
+                currency,
+                cost,
+                active: None,
+                position: None,
+            }
+            .into(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_point_redemptions_need_approval_and_balance() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        earn(&context, user_id, admin_id, 0, 50);
+        let screen_time = reward(&context, "Screen time", RewardCurrency::Points, 30);
+
+        let first = RewardSvc::request(&context, &screen_time.uuid, user_id).unwrap();
+        assert_eq!(
+            RedemptionStatus::from(&first.status),
+            RedemptionStatus::Pending
+        );
+        assert_eq!(PointsSvc::balance(&context, user_id).unwrap(), 50);
+        // 30 of the 50 points are already spoken for
+        assert!(RewardSvc::request(&context, &screen_time.uuid, user_id).is_err());
+
+        let approved = RewardSvc::approve(&context, &first.uuid, admin_id).unwrap();
+        assert_eq!(
+            RedemptionStatus::from(&approved.status),
+            RedemptionStatus::Approved
+        );
+        assert_eq!(approved.decided_by_admin_id, Some(admin_id));
+        assert_eq!(PointsSvc::balance(&context, user_id).unwrap(), 20);
+        assert_eq!(PointsSvc::xp(&context, user_id).unwrap(), 50);
+        assert!(RewardSvc::reject(&context, &first.uuid, admin_id, None).is_err());
+
+        earn(&context, user_id, admin_id, 0, 10);
+        let second = RewardSvc::request(&context, &screen_time.uuid, user_id).unwrap();
+        let rejected = RewardSvc::reject(
+            &context,
+            &second.uuid,
+            admin_id,
+            Some("Not on a school night".to_owned()),
+        )
+        .unwrap();
+        assert_eq!(
+            rejected.decision_note.as_deref(),
+            Some("Not on a school night")
+        );
+        assert_eq!(PointsSvc::balance(&context, user_id).unwrap(), 30);
+
+        // History survives the reward leaving the catalog
+        RewardSvc::delete(&context, &screen_time.uuid).unwrap();
+        let history = RewardSvc::redemptions(&context, Some(user_id), None).unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().all(|r| r.reward_id.is_none()));
+        assert_eq!(
+            RewardSvc::redemptions(&context, Some(user_id), Some(RedemptionStatus::Approved))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_cent_redemptions_come_off_what_is_owed_and_the_payout() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        earn(&context, user_id, admin_id, 500, 0);
+        let ice_cream = reward(&context, "Ice cream", RewardCurrency::Cents, 300);
+
+        let redemption = RewardSvc::request(&context, &ice_cream.uuid, user_id).unwrap();
+        RewardSvc::approve(&context, &redemption.uuid, admin_id).unwrap();
+        let owed = |context: &GraphQLContext| {
+            ChoreCompletionSvc::get_unpaid_totals(context)
+                .unwrap()
+                .into_iter()
+                .find(|(user, _)| user.id == Some(user_id))
+                .map(|(_, cents)| cents)
+        };
+        assert_eq!(owed(&context), Some(200));
+        assert!(RewardSvc::request(&context, &ice_cream.uuid, user_id).is_err());
+
+        ChoreCompletionSvc::mark_as_paid_batch(&context, &[user_id]).unwrap();
+        let settled = RewardSvc::get_redemption(&context, &redemption.uuid).unwrap();
+        assert!(settled.paid_out_at.is_some());
+        assert!(RewardSvc::unsettled_cents(&context).unwrap().is_empty());
+
+        let today = Utc::now().date_naive();
+        let statement =
+            StatementSvc::build(&context, user_id, today.year(), today.month()).unwrap();
+        assert_eq!(statement.earned_cents, 500);
+        assert_eq!(statement.redeemed_cents, 300);
+        assert_eq!(statement.paid_cents, 200);
+        assert_eq!(statement.closing_balance_cents, 0);
+        assert!(
+            statement
+                .lines
+                .iter()
+                .any(|line| line.description == "Reward: Ice cream" && line.amount_cents == -300)
+        );
+    }
+
+    #[test]
+    fn test_payouts_carry_forward_rewards_they_cannot_cover() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        earn(&context, user_id, admin_id, 200, 0);
+        earn(&context, user_id, admin_id, 100, 0);
+        let ice_cream = reward(&context, "Ice cream", RewardCurrency::Cents, 250);
+        let redemption = RewardSvc::request(&context, &ice_cream.uuid, user_id).unwrap();
+        RewardSvc::approve(&context, &redemption.uuid, admin_id).unwrap();
+        let owed = |context: &GraphQLContext| {
+            ChoreCompletionSvc::get_unpaid_totals(context)
+                .unwrap()
+                .into_iter()
+                .find(|(user, _)| user.id == Some(user_id))
+                .map(|(_, cents)| cents)
+        };
+        assert_eq!(owed(&context), Some(50));
+
+        // Losing the bigger completion leaves less owed than the reward cost
+        let bigger = ChoreCompletionSvc::list(&context, &ChoreCompletionFilter::default())
+            .unwrap()
+            .into_iter()
+            .find(|completion| completion.amount_cents == 200)
+            .unwrap();
+        ChoreCompletionSvc::delete(&context, &bigger.uuid).unwrap();
+        assert_eq!(owed(&context), Some(0));
+
+        // The payout settles the 100 it covers and carries 150 forward
+        ChoreCompletionSvc::mark_as_paid_batch(&context, &[user_id]).unwrap();
+        let open = RewardSvc::get_redemption(&context, &redemption.uuid).unwrap();
+        assert!(open.paid_out_at.is_none());
+        assert_eq!(
+            RewardSvc::unsettled_cents(&context).unwrap().get(&user_id),
+            Some(&150)
+        );
+        assert_eq!(
+            JarSvc::balances(&context, user_id).unwrap().total_cents(),
+            0
+        );
+        // Listed without unpaid completions so the reward is not forgotten
+        assert_eq!(owed(&context), Some(0));
+
+        earn(&context, user_id, admin_id, 200, 0);
+        assert_eq!(owed(&context), Some(50));
+        ChoreCompletionSvc::mark_as_paid_batch(&context, &[user_id]).unwrap();
+        let settled = RewardSvc::get_redemption(&context, &redemption.uuid).unwrap();
+        assert!(settled.paid_out_at.is_some());
+        assert!(RewardSvc::unsettled_cents(&context).unwrap().is_empty());
+        assert_eq!(
+            JarSvc::balances(&context, user_id).unwrap().total_cents(),
+            50
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! Savings goals.
+//!
+//! A goal tracks either the kid's save jar or only the amounts an admin allocated to it
+//! out of that jar. The first time progress meets the target the goal is marked reached
+//! and a `SavingsGoalReached` event goes out; it stays reached even if the jar drops
+//! again, e.g. after a transfer.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{GoalProgressSource, SavingsGoal, SavingsGoalAllocation, UserImageInput},
+    schema::{savings_goal_allocations, savings_goals},
+    svc::{JarSvc, NotificationSvc, UserImageSvc, notification::NotificationEvent},
+};
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub struct SavingsGoalSvc {}
+
+impl SavingsGoalSvc {
+    pub fn get(context: &GraphQLContext, goal_uuid: &str) -> Result<SavingsGoal> {
+        savings_goals::table
+            .filter(savings_goals::uuid.eq(goal_uuid))
+            .select(SavingsGoal::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find savings goal")
+    }
+
+    pub fn get_by_id(context: &GraphQLContext, goal_id: i32) -> Result<SavingsGoal> {
+        savings_goals::table
+            .filter(savings_goals::id.eq(goal_id))
+            .select(SavingsGoal::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find savings goal")
+    }
+
+    /// Goals oldest first, for one user or everyone.
+    pub fn list(context: &GraphQLContext, user_id: Option<i32>) -> Result<Vec<SavingsGoal>> {
+        let mut query = savings_goals::table.into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(savings_goals::user_id.eq(user_id));
+        }
+        query
+            .select(SavingsGoal::as_select())
+            .order_by((savings_goals::created_at.asc(), savings_goals::id.asc()))
+            .load(&mut get_conn(context)?)
+            .context("Could not load savings goals")
+    }
+
+    pub fn create(context: &GraphQLContext, goal: &SavingsGoal) -> Result<SavingsGoal> {
+        Self::validate(goal)?;
+        diesel::insert_into(savings_goals::table)
+            .values(goal)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create savings goal")?;
+
+        Self::check_reached(context, goal.user_id)?;
+        Self::get(context, &goal.uuid)
+    }
+
+    /// Updates the name, target and progress source. Reaching a goal cannot be undone, so a
+    /// raised target does not clear `reachedAt`.
+    pub fn update(context: &GraphQLContext, goal: &SavingsGoal) -> Result<SavingsGoal> {
+        Self::validate(goal)?;
+        let current = Self::get(context, &goal.uuid)?;
+        if current.user_id != goal.user_id {
+            bail!("A savings goal cannot move to another user");
+        }
+
+        diesel::update(savings_goals::table)
+            .filter(savings_goals::uuid.eq(&goal.uuid))
+            .set((
+                savings_goals::name.eq(&goal.name),
+                savings_goals::target_cents.eq(goal.target_cents),
+                savings_goals::target_date.eq(goal.target_date),
+                savings_goals::progress_source.eq(&goal.progress_source),
+                savings_goals::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update savings goal")?;
+
+        Self::check_reached(context, goal.user_id)?;
+        Self::get(context, &goal.uuid)
+    }
+
+    /// Deletes the goal with its allocations and picture.
+    pub fn delete(context: &GraphQLContext, goal_uuid: &str) -> Result<()> {
+        let goal = Self::get(context, goal_uuid)?;
+        diesel::delete(savings_goals::table)
+            .filter(savings_goals::uuid.eq(goal_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete savings goal")?;
+        if let Some(image_id) = goal.image_id {
+            UserImageSvc::delete_by_id(context, image_id)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(goal: &SavingsGoal) -> Result<()> {
+        if goal.name.trim().is_empty() {
+            bail!("Savings goals need a name");
+        }
+        if goal.target_cents < 1 {
+            bail!("Savings goals need a target above zero");
+        }
+        Ok(())
+    }
+
+    /// Replaces the goal's picture.
+    pub fn set_image(
+        context: &GraphQLContext,
+        goal_uuid: &str,
+        image: UserImageInput,
+    ) -> Result<SavingsGoal> {
+        let goal = Self::get(context, goal_uuid)?;
+        let image = UserImageSvc::create_for_goal(context, image)?;
+        Self::replace_image(context, &goal, Some(image.id))
+    }
+
+    pub fn remove_image(context: &GraphQLContext, goal_uuid: &str) -> Result<SavingsGoal> {
+        let goal = Self::get(context, goal_uuid)?;
+        Self::replace_image(context, &goal, None)
+    }
+
+    fn replace_image(
+        context: &GraphQLContext,
+        goal: &SavingsGoal,
+        image_id: Option<i32>,
+    ) -> Result<SavingsGoal> {
+        diesel::update(savings_goals::table)
+            .filter(savings_goals::uuid.eq(&goal.uuid))
+            .set(savings_goals::image_id.eq(image_id))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update savings goal image")?;
+        if let Some(old_image_id) = goal.image_id {
+            UserImageSvc::delete_by_id(context, old_image_id)?;
+        }
+
+        Self::get(context, &goal.uuid)
+    }
+
+    pub fn allocations(
+        context: &GraphQLContext,
+        goal_id: i32,
+    ) -> Result<Vec<SavingsGoalAllocation>> {
+        savings_goal_allocations::table
+            .filter(savings_goal_allocations::goal_id.eq(goal_id))
+            .select(SavingsGoalAllocation::as_select())
+            .order_by((
+                savings_goal_allocations::created_at.asc(),
+                savings_goal_allocations::id.asc(),
+            ))
+            .load(&mut get_conn(context)?)
+            .context("Could not load savings goal allocations")
+    }
+
+    /// Sets `amount_cents` aside for the goal, or takes it back out when negative. A goal
+    /// never holds less than nothing, and the kid's goals together never hold more than
+    /// their save jar.
+    pub fn allocate(
+        context: &GraphQLContext,
+        goal_uuid: &str,
+        amount_cents: i32,
+        note: Option<String>,
+        admin_id: i32,
+    ) -> Result<SavingsGoalAllocation> {
+        if amount_cents == 0 {
+            bail!("Allocations need an amount");
+        }
+        let goal = Self::get(context, goal_uuid)?;
+        let goal_id = goal.id.context("Savings goal has no id")?;
+        let allocation = SavingsGoalAllocation {
+            id: None,
+            uuid: Uuid::now_v7().to_string(),
+            goal_id,
+            amount_cents,
+            note,
+            created_by_admin_id: Some(admin_id),
+            created_at: Some(Utc::now().naive_utc()),
+        };
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            if Self::allocated_in(conn, goal_id)? + i64::from(amount_cents) < 0 {
+                bail!("Cannot take out more than was set aside");
+            }
+            if amount_cents > 0 {
+                let available = i64::from(JarSvc::balances_in(conn, goal.user_id)?.save_cents)
+                    - Self::allocated_to_user_in(conn, goal.user_id)?;
+                if i64::from(amount_cents) > available {
+                    bail!("Cannot set aside more than the save jar holds");
+                }
+            }
+            diesel::insert_into(savings_goal_allocations::table)
+                .values(&allocation)
+                .execute(conn)
+                .context("Could not save allocation")?;
+            anyhow::Ok(())
+        })?;
+
+        Self::check_reached(context, goal.user_id)?;
+        savings_goal_allocations::table
+            .filter(savings_goal_allocations::uuid.eq(&allocation.uuid))
+            .select(SavingsGoalAllocation::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find allocation")
+    }
+
+    fn allocated_in(conn: &mut SqliteConnection, goal_id: i32) -> Result<i64> {
+        let allocated: Option<i64> = savings_goal_allocations::table
+            .filter(savings_goal_allocations::goal_id.eq(goal_id))
+            .select(diesel::dsl::sum(savings_goal_allocations::amount_cents))
+            .first(conn)
+            .context("Could not total allocations")?;
+        Ok(allocated.unwrap_or(0))
+    }
+
+    /// Set aside over all of the user's goals.
+    pub(crate) fn allocated_to_user_in(conn: &mut SqliteConnection, user_id: i32) -> Result<i64> {
+        let allocated: Option<i64> = savings_goal_allocations::table
+            .inner_join(savings_goals::table)
+            .filter(savings_goals::user_id.eq(user_id))
+            .select(diesel::dsl::sum(savings_goal_allocations::amount_cents))
+            .first(conn)
+            .context("Could not total allocations")?;
+        Ok(allocated.unwrap_or(0))
+    }
+
+    /// Saved towards the goal so far, in cents.
+    pub fn progress(context: &GraphQLContext, goal: &SavingsGoal) -> Result<i32> {
+        let progress = match GoalProgressSource::from(&goal.progress_source) {
+            GoalProgressSource::Balance => {
+                i64::from(JarSvc::balances(context, goal.user_id)?.save_cents)
+            }
+            GoalProgressSource::Allocations => {
+                let goal_id = goal.id.context("Savings goal has no id")?;
+                Self::allocated_in(&mut *get_conn(context)?, goal_id)?
+            }
+        };
+        Ok(i32::try_from(progress).unwrap_or(i32::MAX))
+    }
+
+    /// Marks the user's goals whose progress met the target as reached, raising an event
+    /// for each.
+    pub fn check_reached(context: &GraphQLContext, user_id: i32) -> Result<()> {
+        let open: Vec<SavingsGoal> = savings_goals::table
+            .filter(savings_goals::user_id.eq(user_id))
+            .filter(savings_goals::reached_at.is_null())
+            .select(SavingsGoal::as_select())
+            .load(&mut get_conn(context)?)
+            .context("Could not load savings goals")?;
+
+        for goal in open {
+            if Self::progress(context, &goal)? < goal.target_cents {
+                continue;
+            }
+            // Only the first caller to mark it raises the event
+            let marked = diesel::update(savings_goals::table)
+                .filter(savings_goals::id.eq(goal.id))
+                .filter(savings_goals::reached_at.is_null())
+                .set(savings_goals::reached_at.eq(Utc::now().naive_utc()))
+                .execute(&mut get_conn(context)?)
+                .context("Could not mark savings goal as reached")?;
+            if let (1, Some(goal_id)) = (marked, goal.id) {
+                NotificationSvc::notify(
+                    context,
+                    &NotificationEvent::SavingsGoalReached { goal_id, user_id },
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Jar, JarSplit, JarSplitTiming, PaymentType, SavingsGoalInput},
+        svc::ChoreCompletionSvc,
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    fn goal(
+        context: &GraphQLContext,
+        user_id: i32,
+        target_cents: i32,
+        progress_source: GoalProgressSource,
+    ) -> SavingsGoal {
+        SavingsGoalSvc::create(
+            context,
+            &SavingsGoalInput {
+                uuid: None,
+                user_id,
+                name: "Bike".to_owned(),
+                target_cents,
+                target_date: Some(create_test_date(2026, 6, 1)),
+                progress_source: Some(progress_source),
+            }
+            .into(),
+        )
+        .unwrap()
+    }
+
+    /// Puts half of everything the kid earns in the save jar as it is approved.
+    fn save_half(context: &GraphQLContext, user_id: i32) {
+        JarSvc::set_split(
+            context,
+            &JarSplit {
+                user_id,
+                spend_percent: 50,
+                save_percent: 50,
+                give_percent: 0,
+                apply_on: JarSplitTiming::Approval.into(),
+                updated_at: None,
+            },
+        )
+        .unwrap();
+    }
+
+    /// Approves a completion of a new daily chore worth `cents`.
+    fn earn(context: &GraphQLContext, user_id: i32, admin_id: i32, cents: i32) {
+        let chore = create_test_chore(
+            context,
+            "Dishes",
+            PaymentType::Daily,
+            cents,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        create_approved_test_completion(
+            context,
+            chore.id.unwrap(),
+            user_id,
+            create_test_date(2026, 4, 6),
+            admin_id,
+        );
+    }
+
+    fn image(user_id: i32) -> UserImageInput {
+        UserImageInput {
+            user_id,
+            image_data: vec![1, 2, 3],
+            content_type: "image/png".to_owned(),
+            file_size: 3,
+        }
+    }
+
+    #[test]
+    fn test_balance_goal_follows_the_save_jar() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        save_half(&context, user_id);
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            300,
+            day_patterns::every_day(),
+            admin_id,
+        );
+        let bike = goal(&context, user_id, 250, GoalProgressSource::Balance);
+
+        for day in [6, 7] {
+            create_approved_test_completion(
+                &context,
+                chore.id.unwrap(),
+                user_id,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+            let bike = SavingsGoalSvc::get(&context, &bike.uuid).unwrap();
+            assert_eq!(bike.reached_at.is_some(), day == 7);
+        }
+        assert_eq!(SavingsGoalSvc::progress(&context, &bike).unwrap(), 300);
+
+        // Paying out does not empty the save jar
+        ChoreCompletionSvc::mark_as_paid_batch(&context, &[user_id]).unwrap();
+        assert_eq!(SavingsGoalSvc::progress(&context, &bike).unwrap(), 300);
+    }
+
+    #[test]
+    fn test_allocation_goal_counts_only_allocations() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        save_half(&context, user_id);
+        earn(&context, user_id, admin_id, 1_000);
+        let bike = goal(&context, user_id, 500, GoalProgressSource::Allocations);
+
+        SavingsGoalSvc::allocate(&context, &bike.uuid, 400, None, admin_id).unwrap();
+        assert!(SavingsGoalSvc::allocate(&context, &bike.uuid, -401, None, admin_id).is_err());
+        SavingsGoalSvc::allocate(&context, &bike.uuid, -100, None, admin_id).unwrap();
+        assert_eq!(SavingsGoalSvc::progress(&context, &bike).unwrap(), 300);
+        assert!(
+            SavingsGoalSvc::get(&context, &bike.uuid)
+                .unwrap()
+                .reached_at
+                .is_none()
+        );
+
+        SavingsGoalSvc::allocate(
+            &context,
+            &bike.uuid,
+            200,
+            Some("Birthday".to_owned()),
+            admin_id,
+        )
+        .unwrap();
+        let bike = SavingsGoalSvc::get(&context, &bike.uuid).unwrap();
+        assert!(bike.reached_at.is_some());
+        assert_eq!(
+            SavingsGoalSvc::allocations(&context, bike.id.unwrap())
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_allocations_are_bounded_by_the_save_jar() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Admin", "admin@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let bike = goal(&context, user_id, 500, GoalProgressSource::Allocations);
+        let kite = goal(&context, user_id, 500, GoalProgressSource::Allocations);
+
+        // Nothing saved yet
+        assert!(SavingsGoalSvc::allocate(&context, &bike.uuid, 1, None, admin_id).is_err());
+
+        save_half(&context, user_id);
+        earn(&context, user_id, admin_id, 600);
+        SavingsGoalSvc::allocate(&context, &bike.uuid, 200, None, admin_id).unwrap();
+        // Both goals share the 300 in the save jar
+        assert!(SavingsGoalSvc::allocate(&context, &kite.uuid, 101, None, admin_id).is_err());
+        SavingsGoalSvc::allocate(&context, &kite.uuid, 100, None, admin_id).unwrap();
+
+        // What is set aside cannot be moved out of the save jar
+        assert!(
+            JarSvc::transfer(&context, user_id, Jar::Save, Jar::Spend, 1, None, admin_id).is_err()
+        );
+        SavingsGoalSvc::allocate(&context, &kite.uuid, -100, None, admin_id).unwrap();
+        JarSvc::transfer(
+            &context,
+            user_id,
+            Jar::Save,
+            Jar::Spend,
+            100,
+            None,
+            admin_id,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_goal_pictures_are_not_profile_pictures() {
+        let context = create_test_context();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let bike = goal(&context, user_id, 500, GoalProgressSource::Allocations);
+
+        let first = SavingsGoalSvc::set_image(&context, &bike.uuid, image(user_id)).unwrap();
+        let second = SavingsGoalSvc::set_image(&context, &bike.uuid, image(user_id)).unwrap();
+        assert_ne!(first.image_id, second.image_id);
+        assert!(
+            UserImageSvc::get_by_user_id(&context, user_id)
+                .unwrap()
+                .is_none()
+        );
+
+        // Replacing a profile picture leaves the goal's alone
+        UserImageSvc::create(&context, image(user_id)).unwrap();
+        UserImageSvc::delete_by_user_id(&context, user_id).unwrap();
+        let bike = SavingsGoalSvc::get(&context, &bike.uuid).unwrap();
+        assert_eq!(bike.image_id, second.image_id);
+
+        let bike = SavingsGoalSvc::remove_image(&context, &bike.uuid).unwrap();
+        assert_eq!(bike.image_id, None);
+    }
+}
@@ -0,0 +1,880 @@
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{CalendarDay, CalendarDayKind, Chore, PausePeriod},
+    schema::{
+        chore_assignments, chore_completions, chores, household_calendar_days, pause_periods,
+    },
+};
+use anyhow::{Context, Result, bail};
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use juniper::GraphQLObject;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// `(chore_id, date)` pairs.
+type Occurrences = HashSet<(i32, NaiveDate)>;
+
+/// Expected versus completed occurrences of a user's recurring chores over a date range.
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct CompletionRate {
+    pub user_id: i32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Occurrences that were due, excluding paused ones.
+    pub expected: i32,
+    /// Due occurrences with an approved completion.
+    pub completed: i32,
+    /// `completed / expected`, or null when nothing was due.
+    pub rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct ChoreCompletionRate {
+    pub chore_id: i32,
+    pub chore_name: String,
+    pub expected: i32,
+    pub completed: i32,
+    pub rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct WeekdayCompletionRate {
+    /// Monday = 0 … Sunday = 6, like the `required_days` bits.
+    pub weekday: i32,
+    pub expected: i32,
+    pub completed: i32,
+    pub rate: Option<f64>,
+}
+
+/// `CompletionRate` with per-chore and per-weekday breakdowns and streaks.
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct CompletionStats {
+    pub user_id: i32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub expected: i32,
+    pub completed: i32,
+    pub rate: Option<f64>,
+    /// Chores with at least one due occurrence, by name.
+    pub per_chore: Vec<ChoreCompletionRate>,
+    /// Always seven entries, Monday first.
+    pub per_weekday: Vec<WeekdayCompletionRate>,
+    /// Perfect days in a row up to the end of the range (or today, if earlier).
+    pub current_streak: i32,
+    /// Longest run of perfect days within the range.
+    pub longest_streak: i32,
+    /// Days with at least one due occurrence, counted like the streaks.
+    pub due_days: i32,
+}
+
+/// A recurring chore on a user's schedule.
+struct ScheduledChore {
+    chore_id: i32,
+    required_days: i32,
+    /// First day the chore is expected, when it was assigned or switched on later.
+    since: Option<NaiveDate>,
+    /// Day the chore was switched off; it is not expected from then on.
+    until: Option<NaiveDate>,
+}
+
+impl ScheduledChore {
+    fn applies_on(&self, date: NaiveDate) -> bool {
+        self.since.is_none_or(|since| since <= date) && self.until.is_none_or(|until| date < until)
+    }
+}
+
+/// A user's recurring chores together with the pauses and household calendar days that
+/// change them, loaded once so date-by-date checks don't hit the database.
+pub struct UserSchedule {
+    /// Non-bonus chores assigned to the user.
+    chores: Vec<ScheduledChore>,
+    pauses: Vec<PausePeriod>,
+    calendar: HashMap<NaiveDate, CalendarDay>,
+}
+
+impl UserSchedule {
+    /// Chores that apply on `date`, with their `required_days`.
+    fn chores_on(&self, date: NaiveDate) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.chores
+            .iter()
+            .filter(move |chore| chore.applies_on(date))
+            .map(|chore| (chore.chore_id, chore.required_days))
+    }
+
+    /// Whether a `required_days` bitmask applies on `date` once the household calendar is
+    /// taken into account: nothing is scheduled on skip days, and alternate days follow
+    /// their stand-in weekday.
+    pub fn is_scheduled(&self, required_days: i32, date: NaiveDate) -> bool {
+        let Some(day) = self.calendar.get(&date) else {
+            return ScheduleSvc::is_scheduled(required_days, date);
+        };
+        match (CalendarDayKind::from(&day.kind), day.alternate_weekday) {
+            (CalendarDayKind::Alternate, Some(weekday)) => required_days & (1 << weekday) != 0,
+            _ => false,
+        }
+    }
+
+    pub fn is_skip_day(&self, date: NaiveDate) -> bool {
+        self.calendar
+            .get(&date)
+            .is_some_and(|day| CalendarDayKind::from(&day.kind) == CalendarDayKind::Skip)
+    }
+
+    pub fn is_paused(&self, chore_id: i32, date: NaiveDate) -> bool {
+        self.pauses.iter().any(|pause| pause.covers(chore_id, date))
+    }
+
+    /// Whether a pause covering every chore applies on `date`.
+    pub fn is_user_paused(&self, date: NaiveDate) -> bool {
+        self.pauses.iter().any(|pause| {
+            pause.chore_id.is_none() && pause.start_date <= date && date <= pause.end_date
+        })
+    }
+
+    /// Ids of the chores due on `date`, leaving out paused ones.
+    pub fn due_on(&self, date: NaiveDate) -> impl Iterator<Item = i32> + '_ {
+        self.chores_on(date)
+            .filter(move |&(chore_id, required_days)| {
+                self.is_scheduled(required_days, date) && !self.is_paused(chore_id, date)
+            })
+            .map(|(chore_id, _)| chore_id)
+    }
+
+    /// A day is excused when the user is paused, the household calendar skips it, or chores
+    /// were scheduled but every one of them is paused. Excused days neither extend nor
+    /// break a streak.
+    pub fn is_day_excused(&self, date: NaiveDate) -> bool {
+        if self.is_user_paused(date) || self.is_skip_day(date) {
+            return true;
+        }
+        let mut scheduled = self
+            .chores_on(date)
+            .filter(|&(_, required_days)| self.is_scheduled(required_days, date))
+            .peekable();
+        scheduled.peek().is_some() && scheduled.all(|(chore_id, _)| self.is_paused(chore_id, date))
+    }
+
+    /// Whether `chore_id` has nothing left to do within `from..=to`: every day it is
+    /// scheduled is paused, or the household calendar removed all of its usual days. Chores
+    /// with no weekday schedule only count as excused when the whole range is paused.
+    pub fn is_chore_excused_between(
+        &self,
+        chore_id: i32,
+        required_days: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> bool {
+        let days: Vec<NaiveDate> = from.iter_days().take_while(|date| *date <= to).collect();
+        let scheduled: Vec<NaiveDate> = days
+            .iter()
+            .copied()
+            .filter(|date| self.is_scheduled(required_days, *date))
+            .collect();
+
+        if !scheduled.is_empty() {
+            return scheduled.iter().all(|date| self.is_paused(chore_id, *date));
+        }
+        let usually_scheduled = days
+            .iter()
+            .any(|date| ScheduleSvc::is_scheduled(required_days, *date));
+        usually_scheduled || days.iter().all(|date| self.is_paused(chore_id, *date))
+    }
+}
+
+pub struct ScheduleSvc {}
+
+impl ScheduleSvc {
+    /// Longest range, in days, that a schedule is walked over day by day: two years.
+    pub const MAX_RANGE_DAYS: i64 = 731;
+
+    /// Rejects ranges that end before they start or are too long to walk day by day.
+    pub fn check_range(from: NaiveDate, to: NaiveDate) -> Result<()> {
+        if to < from {
+            bail!("The end of the range must not be before its start");
+        }
+        if (to - from).num_days() >= Self::MAX_RANGE_DAYS {
+            bail!(
+                "The range must not be longer than {} days",
+                Self::MAX_RANGE_DAYS
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether a `required_days` bitmask includes `date` (Monday = bit 0 … Sunday = bit 6).
+    pub fn is_scheduled(required_days: i32, date: NaiveDate) -> bool {
+        required_days & (1 << date.weekday().num_days_from_monday()) != 0
+    }
+
+    /// The user's active chores as they are scheduled now.
+    pub fn user_schedule(context: &GraphQLContext, user_id: i32) -> Result<UserSchedule> {
+        let chores = chores::table
+            .inner_join(chore_assignments::table)
+            .filter(chore_assignments::user_id.eq(user_id))
+            .filter(chores::active.eq(true))
+            .filter(chores::bonus_date.is_null())
+            .select((chores::id.assume_not_null(), chores::required_days))
+            .load::<(i32, i32)>(&mut get_conn(context)?)
+            .context("Could not load scheduled chores")?
+            .into_iter()
+            .map(|(chore_id, required_days)| ScheduledChore {
+                chore_id,
+                required_days,
+                since: None,
+                until: None,
+            })
+            .collect();
+
+        Self::with_pauses_and_calendar(context, user_id, chores)
+    }
+
+    /// The user's chores as they were scheduled over time: each one is expected from the
+    /// day it was assigned or last switched on, and a switched off one until that day.
+    /// Looking back at past ranges with `user_schedule` would count days before a chore
+    /// was assigned as missed.
+    pub fn user_history(context: &GraphQLContext, user_id: i32) -> Result<UserSchedule> {
+        type Row = (
+            i32,
+            i32,
+            bool,
+            Option<NaiveDateTime>,
+            Option<NaiveDateTime>,
+            Option<NaiveDateTime>,
+            Option<NaiveDateTime>,
+        );
+        let rows: Vec<Row> = chores::table
+            .inner_join(chore_assignments::table)
+            .filter(chore_assignments::user_id.eq(user_id))
+            .filter(chores::bonus_date.is_null())
+            .select((
+                chores::id.assume_not_null(),
+                chores::required_days,
+                chores::active,
+                chores::created_at,
+                chores::activated_at,
+                chores::deactivated_at,
+                chore_assignments::created_at,
+            ))
+            .load(&mut get_conn(context)?)
+            .context("Could not load scheduled chores")?;
+
+        let chores = rows
+            .into_iter()
+            .map(
+                |(
+                    chore_id,
+                    required_days,
+                    active,
+                    created_at,
+                    activated_at,
+                    deactivated_at,
+                    assigned_at,
+                )| {
+                    let until = if active {
+                        None
+                    } else {
+                        // Chores created switched off were never expected
+                        deactivated_at.or(created_at).map(|at| at.date())
+                    };
+                    ScheduledChore {
+                        chore_id,
+                        required_days,
+                        since: assigned_at.max(activated_at).map(|at| at.date()),
+                        until,
+                    }
+                },
+            )
+            .collect();
+
+        Self::with_pauses_and_calendar(context, user_id, chores)
+    }
+
+    fn with_pauses_and_calendar(
+        context: &GraphQLContext,
+        user_id: i32,
+        chores: Vec<ScheduledChore>,
+    ) -> Result<UserSchedule> {
+        let pauses = pause_periods::table
+            .filter(pause_periods::user_id.eq(user_id))
+            .select(PausePeriod::as_select())
+            .load(&mut get_conn(context)?)
+            .context("Could not load pause periods")?;
+
+        let calendar = household_calendar_days::table
+            .select(CalendarDay::as_select())
+            .load::<CalendarDay>(&mut get_conn(context)?)
+            .context("Could not load household calendar")?
+            .into_iter()
+            .map(|day| (day.date, day))
+            .collect();
+
+        Ok(UserSchedule {
+            chores,
+            pauses,
+            calendar,
+        })
+    }
+
+    /// Recurring chores the user is expected to do on `date`, following the household
+    /// calendar and excluding paused ones.
+    pub fn due_chores(
+        context: &GraphQLContext,
+        user_id: i32,
+        date: NaiveDate,
+    ) -> Result<Vec<Chore>> {
+        let schedule = Self::user_schedule(context, user_id)?;
+        let due_ids: Vec<i32> = schedule.due_on(date).collect();
+
+        chores::table
+            .filter(chores::id.eq_any(due_ids))
+            .select(Chore::as_select())
+            .order_by(chores::name.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load due chores")
+    }
+
+    pub fn completion_rate(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<CompletionRate> {
+        let (_, expected, completed) = Self::occurrences(context, user_id, from, to)?;
+        let completed = completed.intersection(&expected).count();
+
+        Ok(CompletionRate {
+            user_id,
+            from,
+            to,
+            expected: count(expected.len())?,
+            completed: count(completed)?,
+            rate: rate(completed, expected.len()),
+        })
+    }
+
+    /// `completion_rate` broken down per chore and per weekday, plus the user's perfect-day
+    /// streaks within the range.
+    pub fn completion_stats(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<CompletionStats> {
+        Self::completion_stats_as_of(context, user_id, from, to, Local::now().date_naive())
+    }
+
+    fn completion_stats_as_of(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+        today: NaiveDate,
+    ) -> Result<CompletionStats> {
+        let (schedule, expected, completed) = Self::occurrences(context, user_id, from, to)?;
+        let done: HashSet<(i32, NaiveDate)> = completed.intersection(&expected).copied().collect();
+
+        // (expected, completed) per chore and per weekday
+        let mut by_chore: BTreeMap<i32, (usize, usize)> = BTreeMap::new();
+        let mut by_weekday = [(0_usize, 0_usize); 7];
+        for occurrence in &expected {
+            let is_done = done.contains(occurrence);
+            let (chore_id, date) = *occurrence;
+            for counts in [
+                by_chore.entry(chore_id).or_default(),
+                &mut by_weekday[date.weekday().num_days_from_monday() as usize],
+            ] {
+                counts.0 += 1;
+                counts.1 += usize::from(is_done);
+            }
+        }
+
+        let names: HashMap<i32, String> = chores::table
+            .filter(chores::id.eq_any(by_chore.keys().copied().collect::<Vec<_>>()))
+            .select((chores::id.assume_not_null(), chores::name))
+            .load(&mut get_conn(context)?)
+            .context("Could not load chores")?
+            .into_iter()
+            .collect();
+        let mut per_chore = Vec::new();
+        for (chore_id, (expected, completed)) in by_chore {
+            per_chore.push(ChoreCompletionRate {
+                chore_id,
+                chore_name: names.get(&chore_id).cloned().unwrap_or_default(),
+                expected: count(expected)?,
+                completed: count(completed)?,
+                rate: rate(completed, expected),
+            });
+        }
+        per_chore.sort_by(|a, b| a.chore_name.cmp(&b.chore_name));
+        let mut per_weekday = Vec::new();
+        for (weekday, (expected, completed)) in (0..).zip(by_weekday) {
+            per_weekday.push(WeekdayCompletionRate {
+                weekday,
+                expected: count(expected)?,
+                completed: count(completed)?,
+                rate: rate(completed, expected),
+            });
+        }
+
+        // A perfect day has something due and every due chore approved. Days with nothing
+        // due neither extend nor break a streak, and an unfinished today is not a miss yet.
+        let mut current_streak = 0;
+        let mut longest_streak = 0;
+        let mut due_days = 0;
+        for date in from.iter_days().take_while(|date| *date <= to.min(today)) {
+            let mut due = schedule.due_on(date).peekable();
+            if due.peek().is_none() {
+                continue;
+            }
+            due_days += 1;
+            if due.all(|chore_id| done.contains(&(chore_id, date))) {
+                current_streak += 1;
+                longest_streak = longest_streak.max(current_streak);
+            } else if date < today {
+                current_streak = 0;
+            }
+        }
+
+        let completed = done.len();
+        Ok(CompletionStats {
+            user_id,
+            from,
+            to,
+            expected: count(expected.len())?,
+            completed: count(completed)?,
+            rate: rate(completed, expected.len()),
+            per_chore,
+            per_weekday,
+            current_streak,
+            longest_streak,
+            due_days,
+        })
+    }
+
+    /// The user's schedule, the `(chore_id, date)` occurrences due in `from..=to` (paused
+    /// ones left out) and those with an approved completion.
+    fn occurrences(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<(UserSchedule, Occurrences, Occurrences)> {
+        Self::check_range(from, to)?;
+        let schedule = Self::user_history(context, user_id)?;
+
+        let expected: Occurrences = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .flat_map(|date| schedule.due_on(date).map(move |chore_id| (chore_id, date)))
+            .collect();
+
+        let completed: Vec<(i32, NaiveDate)> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::approved.eq(true))
+            .filter(chore_completions::completed_date.between(from, to))
+            .select((
+                chore_completions::chore_id,
+                chore_completions::completed_date,
+            ))
+            .load(&mut get_conn(context)?)
+            .context("Could not load completions")?;
+
+        Ok((schedule, expected, completed.into_iter().collect()))
+    }
+}
+
+fn count(value: usize) -> Result<i32> {
+    value.try_into().context("Too many occurrences")
+}
+
+/// `completed / expected`, or `None` when nothing was due.
+fn rate(completed: usize, expected: usize) -> Option<f64> {
+    (expected > 0).then(|| completed as f64 / expected as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{CalendarDayInput, PausePeriodInput, PaymentType},
+        svc::{CalendarSvc, ChoreSvc, PauseSvc},
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_chore_assignment_on, create_test_context, create_test_date,
+            create_test_user, day_patterns, days_bitmask,
+        },
+    };
+
+    fn pause(
+        context: &GraphQLContext,
+        user_id: i32,
+        chore_id: Option<i32>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) {
+        PauseSvc::create(
+            context,
+            &PausePeriodInput {
+                uuid: None,
+                user_id,
+                chore_id,
+                start_date: start,
+                end_date: end,
+                reason: None,
+            }
+            .into(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_is_scheduled_uses_monday_as_first_bit() {
+        // 2026-04-06 is a Monday, 2026-04-12 a Sunday
+        assert!(ScheduleSvc::is_scheduled(
+            day_patterns::monday_only(),
+            create_test_date(2026, 4, 6)
+        ));
+        assert!(!ScheduleSvc::is_scheduled(
+            day_patterns::weekdays(),
+            create_test_date(2026, 4, 12)
+        ));
+        assert!(ScheduleSvc::is_scheduled(
+            day_patterns::every_day(),
+            create_test_date(2026, 4, 12)
+        ));
+    }
+
+    #[test]
+    fn test_due_chores_respects_pauses() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Kid");
+        let user_id = user.id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let trash = create_test_chore(
+            &context,
+            "Trash",
+            PaymentType::Daily,
+            100,
+            day_patterns::monday_only(),
+            admin.id.unwrap(),
+        );
+        ChoreSvc::assign_user(&context, dishes.id.unwrap(), user_id).unwrap();
+        ChoreSvc::assign_user(&context, trash.id.unwrap(), user_id).unwrap();
+
+        let monday = create_test_date(2026, 4, 6);
+        let names = |date| -> Vec<String> {
+            ScheduleSvc::due_chores(&context, user_id, date)
+                .unwrap()
+                .into_iter()
+                .map(|chore| chore.name)
+                .collect()
+        };
+        assert_eq!(names(monday), vec!["Dishes", "Trash"]);
+        assert_eq!(names(create_test_date(2026, 4, 7)), vec!["Dishes"]);
+
+        // Sick from dishes only on Monday
+        pause(&context, user_id, dishes.id, monday, monday);
+        assert_eq!(names(monday), vec!["Trash"]);
+
+        // Away for the whole week
+        pause(
+            &context,
+            user_id,
+            None,
+            monday,
+            create_test_date(2026, 4, 12),
+        );
+        assert!(names(monday).is_empty());
+        assert!(names(create_test_date(2026, 4, 10)).is_empty());
+        assert_eq!(
+            names(create_test_date(2026, 4, 13)),
+            vec!["Dishes", "Trash"]
+        );
+    }
+
+    #[test]
+    fn test_ranges_are_bounded() {
+        let context = create_test_context();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let from = create_test_date(2026, 1, 1);
+
+        assert!(ScheduleSvc::check_range(from, from).is_ok());
+        assert!(ScheduleSvc::check_range(from, create_test_date(2028, 1, 1)).is_ok());
+        assert!(ScheduleSvc::check_range(from, create_test_date(2028, 1, 2)).is_err());
+        assert!(ScheduleSvc::check_range(from, create_test_date(2025, 12, 31)).is_err());
+        assert!(ScheduleSvc::completion_rate(&context, user_id, NaiveDate::MIN, from).is_err());
+    }
+
+    #[test]
+    fn test_completion_rate_excludes_paused_days() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Kid");
+        let user_id = user.id.unwrap();
+        let admin_id = admin.id.unwrap();
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::weekdays(),
+            admin_id,
+        );
+        let chore_id = chore.id.unwrap();
+        create_test_chore_assignment_on(&context, chore_id, user_id, create_test_date(2026, 4, 1));
+
+        // Mon..Wed done, Thu..Fri paused
+        for day in 6..=8 {
+            create_approved_test_completion(
+                &context,
+                chore_id,
+                user_id,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        }
+
+        let from = create_test_date(2026, 4, 6);
+        let to = create_test_date(2026, 4, 12);
+        let before = ScheduleSvc::completion_rate(&context, user_id, from, to).unwrap();
+        assert_eq!((before.expected, before.completed), (5, 3));
+
+        pause(
+            &context,
+            user_id,
+            None,
+            create_test_date(2026, 4, 9),
+            create_test_date(2026, 4, 10),
+        );
+        let after = ScheduleSvc::completion_rate(&context, user_id, from, to).unwrap();
+        assert_eq!((after.expected, after.completed), (3, 3));
+        assert_eq!(after.rate, Some(1.0));
+
+        let empty = ScheduleSvc::completion_rate(
+            &context,
+            user_id,
+            create_test_date(2026, 4, 11),
+            create_test_date(2026, 4, 12),
+        )
+        .unwrap();
+        assert_eq!(empty.rate, None);
+    }
+
+    #[test]
+    fn test_completion_stats_per_chore_weekday_and_streaks() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let dishes = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        let trash = create_test_chore(
+            &context,
+            "Trash",
+            PaymentType::Daily,
+            100,
+            day_patterns::monday_only(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        create_test_chore_assignment_on(&context, dishes, user_id, create_test_date(2026, 4, 1));
+        create_test_chore_assignment_on(&context, trash, user_id, create_test_date(2026, 4, 1));
+        let approve = |chore_id, day| {
+            create_approved_test_completion(
+                &context,
+                chore_id,
+                user_id,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        };
+
+        // Mon 6: dishes only (not perfect), Tue 7..Thu 9 perfect, Fri 10 missed,
+        // Sat 11..Sun 12 perfect, Mon 13 is today and not done yet
+        for day in [6, 7, 8, 9, 11, 12] {
+            approve(dishes, day);
+        }
+
+        let stats = ScheduleSvc::completion_stats_as_of(
+            &context,
+            user_id,
+            create_test_date(2026, 4, 6),
+            create_test_date(2026, 4, 13),
+            create_test_date(2026, 4, 13),
+        )
+        .unwrap();
+        assert_eq!((stats.expected, stats.completed), (10, 6));
+        assert_eq!(
+            stats
+                .per_chore
+                .iter()
+                .map(|c| (c.chore_name.as_str(), c.expected, c.completed))
+                .collect::<Vec<_>>(),
+            vec![("Dishes", 8, 6), ("Trash", 2, 0)]
+        );
+        assert_eq!(stats.per_weekday.len(), 7);
+        let monday = &stats.per_weekday[0];
+        assert_eq!((monday.expected, monday.completed), (4, 1));
+        assert_eq!(stats.per_weekday[4].rate, Some(0.0));
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.current_streak, 2);
+    }
+
+    #[test]
+    fn test_completion_stats_only_expect_assigned_and_active_chores() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Kid").id.unwrap();
+        let chore = |name| {
+            create_test_chore(
+                &context,
+                name,
+                PaymentType::Daily,
+                100,
+                day_patterns::every_day(),
+                admin_id,
+            )
+        };
+        let dishes = chore("Dishes").id.unwrap();
+        let trash = chore("Trash");
+        let trash_id = trash.id.unwrap();
+
+        // Dishes from Wed 8, trash from Mon 6 until it is switched off on Fri 10
+        create_test_chore_assignment_on(&context, dishes, user_id, create_test_date(2026, 4, 8));
+        create_test_chore_assignment_on(&context, trash_id, user_id, create_test_date(2026, 4, 6));
+        let trash = ChoreSvc::update(
+            &context,
+            &Chore {
+                active: false,
+                ..trash
+            },
+        )
+        .unwrap();
+        assert!(trash.deactivated_at.is_some());
+        diesel::update(chores::table.filter(chores::id.eq(trash_id)))
+            .set(chores::deactivated_at.eq(create_test_date(2026, 4, 10).and_hms_opt(9, 0, 0)))
+            .execute(&mut get_conn(&context).unwrap())
+            .unwrap();
+        for day in 8..=12 {
+            create_approved_test_completion(
+                &context,
+                dishes,
+                user_id,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        }
+        for day in 6..=9 {
+            create_approved_test_completion(
+                &context,
+                trash_id,
+                user_id,
+                create_test_date(2026, 4, day),
+                admin_id,
+            );
+        }
+
+        let stats = ScheduleSvc::completion_stats_as_of(
+            &context,
+            user_id,
+            create_test_date(2026, 4, 6),
+            create_test_date(2026, 4, 12),
+            create_test_date(2026, 4, 13),
+        )
+        .unwrap();
+        assert_eq!((stats.expected, stats.completed), (9, 9));
+        assert_eq!((stats.longest_streak, stats.due_days), (7, 7));
+    }
+
+    #[test]
+    fn test_due_chores_follow_household_calendar() {
+        let context = create_test_context();
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Kid");
+        let user_id = user.id.unwrap();
+        let homework = create_test_chore(
+            &context,
+            "Pack Backpack",
+            PaymentType::Daily,
+            100,
+            day_patterns::weekdays(),
+            admin.id.unwrap(),
+        );
+        let yard = create_test_chore(
+            &context,
+            "Rake Leaves",
+            PaymentType::Daily,
+            100,
+            days_bitmask(&[6]),
+            admin.id.unwrap(),
+        );
+        ChoreSvc::assign_user(&context, homework.id.unwrap(), user_id).unwrap();
+        ChoreSvc::assign_user(&context, yard.id.unwrap(), user_id).unwrap();
+
+        let names = |date| -> Vec<String> {
+            ScheduleSvc::due_chores(&context, user_id, date)
+                .unwrap()
+                .into_iter()
+                .map(|chore| chore.name)
+                .collect()
+        };
+        let monday = create_test_date(2026, 4, 6);
+        let tuesday = create_test_date(2026, 4, 7);
+        assert_eq!(names(monday), vec!["Pack Backpack"]);
+
+        // Monday is a holiday run like a Saturday, Tuesday is skipped entirely
+        CalendarSvc::upsert(
+            &context,
+            &CalendarDayInput {
+                uuid: None,
+                date: monday,
+                name: "Spring Holiday".to_owned(),
+                kind: CalendarDayKind::Alternate,
+                alternate_weekday: Some(5),
+            }
+            .into(),
+        )
+        .unwrap();
+        CalendarSvc::upsert(
+            &context,
+            &CalendarDayInput {
+                uuid: None,
+                date: tuesday,
+                name: "Road Trip".to_owned(),
+                kind: CalendarDayKind::Skip,
+                alternate_weekday: None,
+            }
+            .into(),
+        )
+        .unwrap();
+
+        assert_eq!(names(monday), vec!["Rake Leaves"]);
+        assert!(names(tuesday).is_empty());
+        assert_eq!(names(create_test_date(2026, 4, 8)), vec!["Pack Backpack"]);
+
+        let schedule = ScheduleSvc::user_schedule(&context, user_id).unwrap();
+        assert!(schedule.is_day_excused(tuesday));
+        assert!(!schedule.is_day_excused(monday));
+    }
+}
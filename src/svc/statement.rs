@@ -0,0 +1,606 @@
+//! Monthly allowance statements.
+//!
+//! A statement follows the running balance a kid is owed: approved completions add to it
+//! on the day they were approved, payouts take it back down on the day they were made.
+//! Completions approved before `approved_at` was recorded count on their completion date.
+//! Rewards bought with cents come off on the day they were approved, and the payout that
+//! settles them is smaller by as much. Payout lines say what went into the save and give
+//! jars.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::User,
+    pdf::{Font, PdfDocument},
+    svc::{UserSvc, email::format_cents},
+};
+use anyhow::{Context, Result};
+use chrono::{Duration, Months, NaiveDate, NaiveDateTime};
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Date, Integer, Nullable, Text, Timestamp},
+};
+use std::collections::HashMap;
+
+/// Day an approved completion counts towards the balance.
+const APPROVED_ON: &str = "COALESCE(date(approved_at), completed_date)";
+/// Day a paid completion left the balance.
+const PAID_ON: &str = "COALESCE(date(paid_out_at), completed_date)";
+/// Approved rewards bought with cents.
+const CENTS_REDEMPTIONS: &str = "status = 'approved' AND currency = 'cents'";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementLine {
+    pub date: NaiveDate,
+    pub description: String,
+    /// Positive for earnings, negative for rewards and payouts.
+    pub amount_cents: i64,
+    pub balance_cents: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub user: User,
+    pub month_start: NaiveDate,
+    pub month_end: NaiveDate,
+    pub opening_balance_cents: i64,
+    pub lines: Vec<StatementLine>,
+    pub earned_cents: i64,
+    pub redeemed_cents: i64,
+    pub paid_cents: i64,
+    pub closing_balance_cents: i64,
+}
+
+impl Statement {
+    /// e.g. `April 2026`
+    pub fn month_name(&self) -> String {
+        self.month_start.format("%B %Y").to_string()
+    }
+}
+
+#[derive(QueryableByName)]
+struct Opening {
+    #[diesel(sql_type = BigInt)]
+    earned_cents: i64,
+    #[diesel(sql_type = BigInt)]
+    paid_cents: i64,
+}
+
+#[derive(QueryableByName)]
+struct OpeningRedemptions {
+    #[diesel(sql_type = BigInt)]
+    redeemed_cents: i64,
+    #[diesel(sql_type = BigInt)]
+    settled_cents: i64,
+}
+
+#[derive(QueryableByName)]
+struct Earning {
+    #[diesel(sql_type = Date)]
+    approved_on: NaiveDate,
+    #[diesel(sql_type = Date)]
+    completed_date: NaiveDate,
+    #[diesel(sql_type = Text)]
+    chore_name: String,
+    #[diesel(sql_type = Integer)]
+    amount_cents: i32,
+}
+
+#[derive(QueryableByName)]
+struct Redemption {
+    #[diesel(sql_type = Date)]
+    redeemed_on: NaiveDate,
+    #[diesel(sql_type = Text)]
+    reward_name: String,
+    #[diesel(sql_type = Integer)]
+    cost: i32,
+}
+
+#[derive(QueryableByName)]
+struct Settlement {
+    #[diesel(sql_type = Timestamp)]
+    paid_out_at: NaiveDateTime,
+    #[diesel(sql_type = BigInt)]
+    settled_cents: i64,
+}
+
+#[derive(QueryableByName)]
+struct PayoutJars {
+    #[diesel(sql_type = Timestamp)]
+    paid_out_at: NaiveDateTime,
+    #[diesel(sql_type = BigInt)]
+    save_cents: i64,
+    #[diesel(sql_type = BigInt)]
+    give_cents: i64,
+}
+
+#[derive(QueryableByName)]
+struct Payout {
+    #[diesel(sql_type = Date)]
+    paid_on: NaiveDate,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    paid_out_at: Option<NaiveDateTime>,
+    #[diesel(sql_type = BigInt)]
+    amount_cents: i64,
+    #[diesel(sql_type = BigInt)]
+    completion_count: i64,
+}
+
+const MARGIN: f32 = 50.0;
+const DESCRIPTION_X: f32 = 130.0;
+const AMOUNT_RIGHT: f32 = 470.0;
+const BALANCE_RIGHT: f32 = 560.0;
+const ROW_HEIGHT: f32 = 14.0;
+const BOTTOM: f32 = 60.0;
+const TOP: f32 = 730.0;
+/// Room the totals under the last row need.
+const TOTALS_HEIGHT: f32 = 74.0;
+
+pub struct StatementSvc {}
+
+impl StatementSvc {
+    /// Builds the statement of `user_id` for the given calendar month.
+    pub fn build(
+        context: &GraphQLContext,
+        user_id: i32,
+        year: i32,
+        month: u32,
+    ) -> Result<Statement> {
+        let month_start = NaiveDate::from_ymd_opt(year, month, 1)
+            .with_context(|| format!("{year}-{month} is not a valid month"))?;
+        let month_end = month_start + Months::new(1) - Duration::days(1);
+        let user = UserSvc::get_by_id(context, user_id)?;
+
+        let opening: Opening = diesel::sql_query(format!(
+            "SELECT \
+               COALESCE(SUM(CASE WHEN approved = 1 AND {APPROVED_ON} < ?1 \
+                                 THEN amount_cents ELSE 0 END), 0) AS earned_cents, \
+               COALESCE(SUM(CASE WHEN paid_out = 1 AND {PAID_ON} < ?1 \
+                                 THEN amount_cents ELSE 0 END), 0) AS paid_cents \
+             FROM chore_completions WHERE user_id = ?2"
+        ))
+        .bind::<Date, _>(month_start)
+        .bind::<Integer, _>(user_id)
+        .get_result(&mut get_conn(context)?)
+        .context("Could not total the opening balance")?;
+        let opening_redemptions: OpeningRedemptions = diesel::sql_query(format!(
+            "SELECT \
+               COALESCE(SUM(CASE WHEN date(decided_at) < ?1 THEN cost ELSE 0 END), 0) \
+                 AS redeemed_cents, \
+               (SELECT COALESCE(SUM(amount_cents), 0) FROM reward_settlements \
+                WHERE user_id = ?2 AND date(paid_out_at) < ?1) AS settled_cents \
+             FROM reward_redemptions WHERE user_id = ?2 AND {CENTS_REDEMPTIONS}"
+        ))
+        .bind::<Date, _>(month_start)
+        .bind::<Integer, _>(user_id)
+        .get_result(&mut get_conn(context)?)
+        .context("Could not total the opening rewards")?;
+
+        let earnings: Vec<Earning> = diesel::sql_query(format!(
+            "SELECT {APPROVED_ON} AS approved_on, c.completed_date, ch.name AS chore_name, \
+                    c.amount_cents \
+             FROM chore_completions c JOIN chores ch ON ch.id = c.chore_id \
+             WHERE c.user_id = ?1 AND c.approved = 1 AND {APPROVED_ON} BETWEEN ?2 AND ?3 \
+             ORDER BY approved_on, c.completed_date, c.id"
+        ))
+        .bind::<Integer, _>(user_id)
+        .bind::<Date, _>(month_start)
+        .bind::<Date, _>(month_end)
+        .load(&mut get_conn(context)?)
+        .context("Could not load approved completions")?;
+
+        let redemptions: Vec<Redemption> = diesel::sql_query(format!(
+            "SELECT date(decided_at) AS redeemed_on, reward_name, cost \
+             FROM reward_redemptions \
+             WHERE user_id = ?1 AND {CENTS_REDEMPTIONS} \
+               AND date(decided_at) BETWEEN ?2 AND ?3 \
+             ORDER BY decided_at, id"
+        ))
+        .bind::<Integer, _>(user_id)
+        .bind::<Date, _>(month_start)
+        .bind::<Date, _>(month_end)
+        .load(&mut get_conn(context)?)
+        .context("Could not load redeemed rewards")?;
+
+        // Completions paid together share one `paid_out_at`, so each group is one payout
+        let payouts: Vec<Payout> = diesel::sql_query(format!(
+            "SELECT {PAID_ON} AS paid_on, paid_out_at, SUM(amount_cents) AS amount_cents, \
+                    COUNT(*) AS completion_count \
+             FROM chore_completions \
+             WHERE user_id = ?1 AND paid_out = 1 AND {PAID_ON} BETWEEN ?2 AND ?3 \
+             GROUP BY paid_out_at, paid_on ORDER BY paid_on, paid_out_at"
+        ))
+        .bind::<Integer, _>(user_id)
+        .bind::<Date, _>(month_start)
+        .bind::<Date, _>(month_end)
+        .load(&mut get_conn(context)?)
+        .context("Could not load payouts")?;
+        // What a payout settled of rewards shares its `paid_out_at`
+        let settled: HashMap<NaiveDateTime, i64> = diesel::sql_query(
+            "SELECT paid_out_at, SUM(amount_cents) AS settled_cents \
+             FROM reward_settlements \
+             WHERE user_id = ?1 AND date(paid_out_at) BETWEEN ?2 AND ?3 \
+             GROUP BY paid_out_at",
+        )
+        .bind::<Integer, _>(user_id)
+        .bind::<Date, _>(month_start)
+        .bind::<Date, _>(month_end)
+        .load::<Settlement>(&mut get_conn(context)?)
+        .context("Could not load settled rewards")?
+        .into_iter()
+        .map(|s| (s.paid_out_at, s.settled_cents))
+        .collect();
+        // Jar entries booked with a payout share its `paid_out_at` too
+        let jars: HashMap<NaiveDateTime, PayoutJars> = diesel::sql_query(
+            "SELECT paid_out_at, \
+                    COALESCE(SUM(CASE WHEN jar = 'save' THEN amount_cents END), 0) AS save_cents, \
+                    COALESCE(SUM(CASE WHEN jar = 'give' THEN amount_cents END), 0) AS give_cents \
+             FROM jar_entries \
+             WHERE user_id = ?1 AND date(paid_out_at) BETWEEN ?2 AND ?3 \
+             GROUP BY paid_out_at",
+        )
+        .bind::<Integer, _>(user_id)
+        .bind::<Date, _>(month_start)
+        .bind::<Date, _>(month_end)
+        .load::<PayoutJars>(&mut get_conn(context)?)
+        .context("Could not load payout jars")?
+        .into_iter()
+        .map(|j| (j.paid_out_at, j))
+        .collect();
+
+        let opening_balance_cents = opening.earned_cents
+            - opening_redemptions.redeemed_cents
+            - (opening.paid_cents - opening_redemptions.settled_cents);
+        let redeemed_cents: i64 = redemptions.iter().map(|r| i64::from(r.cost)).sum();
+        let mut entries: Vec<(NaiveDate, String, i64)> = earnings
+            .into_iter()
+            .map(|e| {
+                let description = if e.completed_date == e.approved_on {
+                    e.chore_name
+                } else {
+                    format!(
+                        "{} (done {})",
+                        e.chore_name,
+                        e.completed_date.format("%b %-d")
+                    )
+                };
+                (e.approved_on, description, e.amount_cents.into())
+            })
+            .collect();
+        let earned_cents = entries.iter().map(|(_, _, cents)| cents).sum();
+        entries.extend(redemptions.into_iter().map(|r| {
+            (
+                r.redeemed_on,
+                format!("Reward: {}", r.reward_name),
+                -i64::from(r.cost),
+            )
+        }));
+        let mut paid_cents = 0;
+        entries.extend(payouts.into_iter().map(|p| {
+            let plural = if p.completion_count == 1 { "" } else { "s" };
+            let settled = p
+                .paid_out_at
+                .and_then(|at| settled.get(&at).copied())
+                .unwrap_or(0);
+            paid_cents += p.amount_cents - settled;
+            let mut details = vec![format!("{} chore{plural}", p.completion_count)];
+            if let Some(jars) = p.paid_out_at.and_then(|at| jars.get(&at)) {
+                if jars.save_cents != 0 {
+                    details.push(format!("{} saved", format_cents(jars.save_cents)));
+                }
+                if jars.give_cents != 0 {
+                    details.push(format!("{} to give", format_cents(jars.give_cents)));
+                }
+            }
+            (
+                p.paid_on,
+                format!("Payout ({})", details.join(", ")),
+                settled - p.amount_cents,
+            )
+        }));
+        // Stable, so earnings stay ahead of rewards and payouts on the same day
+        entries.sort_by_key(|(date, _, _)| *date);
+
+        let mut balance = opening_balance_cents;
+        let lines = entries
+            .into_iter()
+            .map(|(date, description, amount_cents)| {
+                balance += amount_cents;
+                StatementLine {
+                    date,
+                    description,
+                    amount_cents,
+                    balance_cents: balance,
+                }
+            })
+            .collect();
+
+        Ok(Statement {
+            user,
+            month_start,
+            month_end,
+            opening_balance_cents,
+            lines,
+            earned_cents,
+            redeemed_cents,
+            paid_cents,
+            closing_balance_cents: balance,
+        })
+    }
+
+    /// Statements of every kid for the month, alphabetically.
+    pub fn build_all(context: &GraphQLContext, year: i32, month: u32) -> Result<Vec<Statement>> {
+        UserSvc::list(context, i32::MAX, 0)?
+            .into_iter()
+            .filter_map(|user| user.id)
+            .map(|user_id| Self::build(context, user_id, year, month))
+            .collect()
+    }
+
+    /// Renders statements as one PDF, each starting on a new page.
+    pub fn pdf(statements: &[Statement]) -> Vec<u8> {
+        let mut doc = PdfDocument::new();
+        for statement in statements {
+            Self::render(&mut doc, statement);
+        }
+        doc.finish()
+    }
+
+    fn render(doc: &mut PdfDocument, statement: &Statement) {
+        doc.add_page();
+        let mut y = TOP;
+        doc.text(
+            MARGIN,
+            y,
+            Font::Bold,
+            18.0,
+            &format!("Allowance statement - {}", statement.month_name()),
+        );
+        y -= 22.0;
+        doc.text(MARGIN, y, Font::Regular, 12.0, &statement.user.name);
+        y -= 14.0;
+        doc.text(
+            MARGIN,
+            y,
+            Font::Regular,
+            9.0,
+            &format!(
+                "{} to {}",
+                statement.month_start.format("%B %-d, %Y"),
+                statement.month_end.format("%B %-d, %Y")
+            ),
+        );
+        y -= 30.0;
+
+        y = Self::header(doc, y);
+        Self::row(
+            doc,
+            y,
+            &statement.month_start,
+            "Opening balance",
+            None,
+            statement.opening_balance_cents,
+        );
+        y -= ROW_HEIGHT;
+
+        for line in &statement.lines {
+            if y < BOTTOM {
+                doc.add_page();
+                y = Self::header(doc, TOP);
+            }
+            Self::row(
+                doc,
+                y,
+                &line.date,
+                &line.description,
+                Some(line.amount_cents),
+                line.balance_cents,
+            );
+            y -= ROW_HEIGHT;
+        }
+
+        if y < BOTTOM + TOTALS_HEIGHT {
+            doc.add_page();
+            y = TOP;
+        }
+        doc.line(MARGIN, BALANCE_RIGHT, y + ROW_HEIGHT - 4.0);
+        y -= 4.0;
+        let totals = [
+            ("Earned", statement.earned_cents),
+            ("Rewards", -statement.redeemed_cents),
+            ("Paid out", -statement.paid_cents),
+            ("Closing balance", statement.closing_balance_cents),
+        ];
+        for (label, cents) in totals {
+            doc.text(DESCRIPTION_X, y, Font::Bold, 10.0, label);
+            doc.text_right(BALANCE_RIGHT, y, 10.0, &format_cents(cents));
+            y -= ROW_HEIGHT;
+        }
+    }
+
+    /// Draws the column headings and returns the baseline of the first row.
+    fn header(doc: &mut PdfDocument, y: f32) -> f32 {
+        doc.text(MARGIN, y, Font::Bold, 10.0, "Date");
+        doc.text(DESCRIPTION_X, y, Font::Bold, 10.0, "Description");
+        doc.text(AMOUNT_RIGHT - 36.0, y, Font::Bold, 10.0, "Amount");
+        doc.text(BALANCE_RIGHT - 40.0, y, Font::Bold, 10.0, "Balance");
+        doc.line(MARGIN, BALANCE_RIGHT, y - 4.0);
+        y - ROW_HEIGHT - 4.0
+    }
+
+    fn row(
+        doc: &mut PdfDocument,
+        y: f32,
+        date: &NaiveDate,
+        description: &str,
+        amount_cents: Option<i64>,
+        balance_cents: i64,
+    ) {
+        doc.text(
+            MARGIN,
+            y,
+            Font::Regular,
+            10.0,
+            &date.format("%b %-d").to_string(),
+        );
+        doc.text(DESCRIPTION_X, y, Font::Regular, 10.0, description);
+        if let Some(amount) = amount_cents {
+            doc.text_right(AMOUNT_RIGHT, y, 10.0, &format_cents(amount));
+        }
+        doc.text_right(BALANCE_RIGHT, y, 10.0, &format_cents(balance_cents));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::PaymentType,
+        schema::chore_completions,
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_context, create_test_date, create_test_user, day_patterns,
+        },
+    };
+
+    /// An approved completion, approved (and optionally paid) at the given dates.
+    fn completion(
+        context: &GraphQLContext,
+        chore_id: i32,
+        user_id: i32,
+        admin_id: i32,
+        completed: NaiveDate,
+        approved: NaiveDate,
+        paid: Option<NaiveDate>,
+    ) {
+        let completion =
+            create_approved_test_completion(context, chore_id, user_id, completed, admin_id);
+        let at = |date: NaiveDate| date.and_hms_opt(18, 0, 0).unwrap();
+        diesel::update(
+            chore_completions::table.filter(chore_completions::uuid.eq(&completion.uuid)),
+        )
+        .set((
+            chore_completions::approved_at.eq(at(approved)),
+            chore_completions::paid_out.eq(paid.is_some()),
+            chore_completions::paid_out_at.eq(paid.map(at)),
+        ))
+        .execute(&mut get_conn(context).unwrap())
+        .unwrap();
+    }
+
+    #[test]
+    fn test_statement_carries_balance_across_months() {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Alice").id.unwrap();
+        let chore_id = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            150,
+            day_patterns::every_day(),
+            admin_id,
+        )
+        .id
+        .unwrap();
+        let date = create_test_date;
+
+        // March: earned 3.00, of which 1.50 was paid in March and 1.50 in April
+        completion(
+            &context,
+            chore_id,
+            user_id,
+            admin_id,
+            date(2026, 3, 2),
+            date(2026, 3, 2),
+            Some(date(2026, 3, 15)),
+        );
+        completion(
+            &context,
+            chore_id,
+            user_id,
+            admin_id,
+            date(2026, 3, 31),
+            date(2026, 3, 31),
+            Some(date(2026, 4, 5)),
+        );
+        // Done in March, approved in April
+        completion(
+            &context,
+            chore_id,
+            user_id,
+            admin_id,
+            date(2026, 3, 30),
+            date(2026, 4, 1),
+            Some(date(2026, 4, 5)),
+        );
+        completion(
+            &context,
+            chore_id,
+            user_id,
+            admin_id,
+            date(2026, 4, 10),
+            date(2026, 4, 10),
+            None,
+        );
+        // May is outside the statement
+        completion(
+            &context,
+            chore_id,
+            user_id,
+            admin_id,
+            date(2026, 5, 1),
+            date(2026, 5, 1),
+            None,
+        );
+
+        let march = StatementSvc::build(&context, user_id, 2026, 3).unwrap();
+        assert_eq!(march.opening_balance_cents, 0);
+        assert_eq!(march.earned_cents, 300);
+        assert_eq!(march.paid_cents, 150);
+        assert_eq!(march.closing_balance_cents, 150);
+
+        let april = StatementSvc::build(&context, user_id, 2026, 4).unwrap();
+        assert_eq!(april.month_name(), "April 2026");
+        assert_eq!(april.month_end, date(2026, 4, 30));
+        assert_eq!(april.opening_balance_cents, march.closing_balance_cents);
+        assert_eq!(
+            april
+                .lines
+                .iter()
+                .map(|l| (
+                    l.date,
+                    l.description.as_str(),
+                    l.amount_cents,
+                    l.balance_cents
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (date(2026, 4, 1), "Dishes (done Mar 30)", 150, 300),
+                (date(2026, 4, 5), "Payout (2 chores)", -300, 0),
+                (date(2026, 4, 10), "Dishes", 150, 150),
+            ]
+        );
+        assert_eq!(april.closing_balance_cents, 150);
+
+        assert!(StatementSvc::build(&context, user_id, 2026, 13).is_err());
+    }
+
+    #[test]
+    fn test_pdf_has_a_page_per_statement() {
+        let context = create_test_context();
+        create_test_user(&context, "Alice");
+        create_test_user(&context, "Bob");
+
+        let statements = StatementSvc::build_all(&context, 2026, 4).unwrap();
+        assert_eq!(statements.len(), 2);
+        let bytes = StatementSvc::pdf(&statements);
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.starts_with("%PDF-"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(Allowance statement - April 2026) Tj"));
+        assert!(text.contains("(Bob) Tj"));
+    }
+}
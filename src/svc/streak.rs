@@ -0,0 +1,389 @@
+//! Streaks of perfect days.
+//!
+//! A day counts when the kid had something due and every due chore was approved; days
+//! with nothing due (weekends off, pauses, household skip days) are passed over. Every
+//! `FREEZE_EVERY` days in a row earn a streak freeze, which is spent automatically to
+//! cover the next missed day.
+//!
+//! Days are evaluated once and logged in `streak_days`, so a refresh only looks at the
+//! days since the last one. Changes to the past (a late approval, a new pause) rewind the
+//! log to the changed day with `invalidate`, and the next refresh replays from there.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{StreakDay, UserStreak},
+    schema::{chore_completions, streak_days, user_streaks},
+    svc::ScheduleSvc,
+};
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveDate, Utc};
+use diesel::prelude::*;
+use std::collections::HashSet;
+
+/// A freeze is earned for every this many perfect days in a row…
+const FREEZE_EVERY: i32 = 7;
+/// …up to this many held at once.
+const MAX_FREEZES: i32 = 2;
+
+const QUALIFIED: &str = "qualified";
+const FROZEN: &str = "frozen";
+const MISSED: &str = "missed";
+
+pub struct StreakSvc {}
+
+impl StreakSvc {
+    /// The user's streak, brought up to date.
+    pub fn get(context: &GraphQLContext, user_id: i32) -> Result<UserStreak> {
+        Self::refresh_as_of(context, user_id, Local::now().date_naive())
+    }
+
+    /// Evaluated days in `from..=to`, oldest first.
+    pub fn days(
+        context: &GraphQLContext,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<StreakDay>> {
+        Self::get(context, user_id)?;
+        streak_days::table
+            .filter(streak_days::user_id.eq(user_id))
+            .filter(streak_days::date.between(from, to))
+            .select(StreakDay::as_select())
+            .order_by(streak_days::date.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load streak days")
+    }
+
+    /// Forgets evaluated days from `from` on, for one user or (for household calendar
+    /// changes) everyone. They are replayed on the next refresh.
+    pub fn invalidate(
+        context: &GraphQLContext,
+        user_id: Option<i32>,
+        from: NaiveDate,
+    ) -> Result<()> {
+        get_conn(context)?.immediate_transaction(|conn| {
+            let mut days = diesel::delete(streak_days::table)
+                .filter(streak_days::date.ge(from))
+                .into_boxed();
+            let mut streaks = diesel::update(user_streaks::table)
+                .filter(user_streaks::evaluated_through.ge(from))
+                .into_boxed();
+            if let Some(user_id) = user_id {
+                days = days.filter(streak_days::user_id.eq(user_id));
+                streaks = streaks.filter(user_streaks::user_id.eq(user_id));
+            }
+            days.execute(conn).context("Could not rewind streak days")?;
+            streaks
+                .set(user_streaks::evaluated_through.eq(from - Duration::days(1)))
+                .execute(conn)
+                .context("Could not rewind streak")?;
+            anyhow::Ok(())
+        })
+    }
+
+    /// Evaluates the days since the last refresh up to `today`. Today only counts once it
+    /// is perfect; until then it is left for the next refresh rather than counted as missed.
+    fn refresh_as_of(
+        context: &GraphQLContext,
+        user_id: i32,
+        today: NaiveDate,
+    ) -> Result<UserStreak> {
+        let mut existing: Option<UserStreak> = user_streaks::table
+            .filter(user_streaks::user_id.eq(user_id))
+            .select(UserStreak::as_select())
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not load streak")?;
+        // Asked about an earlier day than already evaluated: replay from that day
+        if let Some(streak) = existing.as_mut()
+            && streak
+                .evaluated_through
+                .is_some_and(|through| through >= today)
+        {
+            Self::invalidate(context, Some(user_id), today)?;
+            streak.evaluated_through = Some(today - Duration::days(1));
+        }
+
+        let start = match existing.as_ref().and_then(|s| s.evaluated_through) {
+            Some(through) => Some(through + Duration::days(1)),
+            None => {
+                // Never evaluated (or rewound to before the log): start at the first
+                // approved completion
+                chore_completions::table
+                    .filter(chore_completions::user_id.eq(user_id))
+                    .filter(chore_completions::approved.eq(true))
+                    .select(diesel::dsl::min(chore_completions::completed_date))
+                    .first::<Option<NaiveDate>>(&mut get_conn(context)?)
+                    .context("Could not find the first completion")?
+            }
+        };
+        let Some(start) = start.filter(|start| *start <= today) else {
+            return Ok(existing.unwrap_or(UserStreak {
+                user_id,
+                current_streak: 0,
+                longest_streak: 0,
+                last_qualifying_date: None,
+                freezes_available: 0,
+                evaluated_through: None,
+                updated_at: None,
+            }));
+        };
+
+        // Pick up from the last logged day before `start`
+        let previous: Option<StreakDay> = streak_days::table
+            .filter(streak_days::user_id.eq(user_id))
+            .filter(streak_days::date.lt(start))
+            .select(StreakDay::as_select())
+            .order_by(streak_days::date.desc())
+            .first(&mut get_conn(context)?)
+            .optional()
+            .context("Could not load the previous streak day")?;
+        let mut last_qualifying_date: Option<NaiveDate> = streak_days::table
+            .filter(streak_days::user_id.eq(user_id))
+            .filter(streak_days::date.lt(start))
+            .filter(streak_days::outcome.eq(QUALIFIED))
+            .select(diesel::dsl::max(streak_days::date))
+            .first(&mut get_conn(context)?)
+            .context("Could not load the last qualifying day")?;
+        let (mut streak, mut longest, mut freezes) =
+            previous.map_or((0, 0, 0), |day| (day.streak, day.longest, day.freezes));
+
+        let schedule = ScheduleSvc::user_schedule(context, user_id)?;
+        let done: HashSet<(i32, NaiveDate)> = chore_completions::table
+            .filter(chore_completions::user_id.eq(user_id))
+            .filter(chore_completions::approved.eq(true))
+            .filter(chore_completions::completed_date.between(start, today))
+            .select((
+                chore_completions::chore_id,
+                chore_completions::completed_date,
+            ))
+            .load::<(i32, NaiveDate)>(&mut get_conn(context)?)
+            .context("Could not load approved completions")?
+            .into_iter()
+            .collect();
+
+        let mut evaluated = Vec::new();
+        let mut evaluated_through = today - Duration::days(1);
+        for date in start.iter_days().take_while(|date| *date <= today) {
+            let mut due = schedule.due_on(date).peekable();
+            if due.peek().is_none() {
+                continue;
+            }
+            let outcome = if due.all(|chore_id| done.contains(&(chore_id, date))) {
+                streak += 1;
+                longest = longest.max(streak);
+                if streak % FREEZE_EVERY == 0 && freezes < MAX_FREEZES {
+                    freezes += 1;
+                }
+                last_qualifying_date = Some(date);
+                if date == today {
+                    evaluated_through = today;
+                }
+                QUALIFIED
+            } else if date == today {
+                break;
+            } else if freezes > 0 {
+                freezes -= 1;
+                FROZEN
+            } else {
+                streak = 0;
+                MISSED
+            };
+            evaluated.push(StreakDay {
+                id: None,
+                user_id,
+                date,
+                outcome: outcome.to_owned(),
+                streak,
+                longest,
+                freezes,
+            });
+        }
+
+        let state = UserStreak {
+            user_id,
+            current_streak: streak,
+            longest_streak: longest,
+            last_qualifying_date,
+            freezes_available: freezes,
+            evaluated_through: Some(evaluated_through),
+            updated_at: Some(Utc::now().naive_utc()),
+        };
+        get_conn(context)?.immediate_transaction(|conn| {
+            diesel::delete(streak_days::table)
+                .filter(streak_days::user_id.eq(user_id))
+                .filter(streak_days::date.ge(start))
+                .execute(conn)
+                .context("Could not clear streak days")?;
+            diesel::insert_into(streak_days::table)
+                .values(&evaluated)
+                .execute(conn)
+                .context("Could not save streak days")?;
+            diesel::replace_into(user_streaks::table)
+                .values(&state)
+                .execute(conn)
+                .context("Could not save streak")?;
+            anyhow::Ok(())
+        })?;
+
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{PausePeriodInput, PaymentType},
+        svc::PauseSvc,
+        test_helpers::test_db::{
+            create_approved_test_completion, create_test_admin, create_test_chore,
+            create_test_chore_assignment, create_test_context, create_test_date, create_test_user,
+            day_patterns,
+        },
+    };
+
+    struct Setup {
+        context: GraphQLContext,
+        user_id: i32,
+        chore_id: i32,
+        admin_id: i32,
+    }
+
+    fn setup(required_days: i32) -> Setup {
+        let context = create_test_context();
+        let admin_id = create_test_admin(&context, "Parent", "parent@test.com")
+            .id
+            .unwrap();
+        let user_id = create_test_user(&context, "Alice").id.unwrap();
+        let chore_id = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            required_days,
+            admin_id,
+        )
+        .id
+        .unwrap();
+        create_test_chore_assignment(&context, chore_id, user_id);
+        Setup {
+            context,
+            user_id,
+            chore_id,
+            admin_id,
+        }
+    }
+
+    fn approve(s: &Setup, days: impl IntoIterator<Item = u32>) {
+        for day in days {
+            create_approved_test_completion(
+                &s.context,
+                s.chore_id,
+                s.user_id,
+                create_test_date(2026, 4, day),
+                s.admin_id,
+            );
+        }
+    }
+
+    fn as_of(s: &Setup, day: u32) -> UserStreak {
+        StreakSvc::refresh_as_of(&s.context, s.user_id, create_test_date(2026, 4, day)).unwrap()
+    }
+
+    #[test]
+    fn test_late_approval_rewinds_a_missed_day() {
+        let s = setup(day_patterns::every_day());
+        approve(&s, [1, 2, 3]);
+
+        let streak = as_of(&s, 5);
+        assert_eq!((streak.current_streak, streak.longest_streak), (0, 3));
+        assert_eq!(
+            streak.last_qualifying_date,
+            Some(create_test_date(2026, 4, 3))
+        );
+        // The 5th is still open, so only the 4th was judged
+        assert_eq!(streak.evaluated_through, Some(create_test_date(2026, 4, 4)));
+
+        approve(&s, [4]);
+        let streak = as_of(&s, 5);
+        assert_eq!((streak.current_streak, streak.longest_streak), (4, 4));
+
+        approve(&s, [5]);
+        let streak = as_of(&s, 5);
+        assert_eq!(streak.current_streak, 5);
+        assert_eq!(streak.evaluated_through, Some(create_test_date(2026, 4, 5)));
+    }
+
+    #[test]
+    fn test_only_scheduled_days_count() {
+        let s = setup(day_patterns::mon_wed_fri());
+        // Monday the 6th through Monday the 13th
+        approve(&s, [6, 8, 10, 13]);
+
+        let streak = as_of(&s, 14);
+        assert_eq!(streak.current_streak, 4);
+        assert_eq!(
+            streak.last_qualifying_date,
+            Some(create_test_date(2026, 4, 13))
+        );
+    }
+
+    #[test]
+    fn test_freezes_cover_missed_days() {
+        let s = setup(day_patterns::every_day());
+        approve(&s, 1..=7);
+        let streak = as_of(&s, 8);
+        assert_eq!((streak.current_streak, streak.freezes_available), (7, 1));
+
+        // The 8th is frozen, the 10th is missed with no freeze left
+        approve(&s, [9]);
+        let streak = as_of(&s, 11);
+        assert_eq!(streak.current_streak, 0);
+        assert_eq!(streak.longest_streak, 8);
+        assert_eq!(streak.freezes_available, 0);
+
+        let outcomes: Vec<(u32, String, i32)> = StreakSvc::days(
+            &s.context,
+            s.user_id,
+            create_test_date(2026, 4, 7),
+            create_test_date(2026, 4, 10),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|day| (chrono::Datelike::day(&day.date), day.outcome, day.streak))
+        .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (7, QUALIFIED.to_owned(), 7),
+                (8, FROZEN.to_owned(), 7),
+                (9, QUALIFIED.to_owned(), 8),
+                (10, MISSED.to_owned(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_new_pause_replays_the_paused_days() {
+        let s = setup(day_patterns::every_day());
+        approve(&s, [1, 2, 5, 6]);
+        assert_eq!(as_of(&s, 7).current_streak, 2);
+
+        PauseSvc::create(
+            &s.context,
+            &PausePeriodInput {
+                uuid: None,
+                user_id: s.user_id,
+                chore_id: None,
+                start_date: create_test_date(2026, 4, 3),
+                end_date: create_test_date(2026, 4, 4),
+                reason: None,
+            }
+            .into(),
+        )
+        .unwrap();
+        assert_eq!(as_of(&s, 7).current_streak, 4);
+    }
+}
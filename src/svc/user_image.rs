@@ -8,15 +8,33 @@ use anyhow::{Context, Result};
 use diesel::prelude::*;
 use uuid::Uuid;
 
+/// `kind` of profile pictures; the profile lookups below only see these.
+const PROFILE: &str = "profile";
+/// `kind` of savings goal pictures, which are referenced by their goal instead.
+const GOAL: &str = "goal";
+
 pub struct UserImageSvc;
 
 impl UserImageSvc {
     pub fn create(context: &GraphQLContext, input: UserImageInput) -> Result<UserImage> {
+        Self::create_with_kind(context, input, PROFILE)
+    }
+
+    /// Stores the picture of a savings goal; it never shows up as the profile picture.
+    pub fn create_for_goal(context: &GraphQLContext, input: UserImageInput) -> Result<UserImage> {
+        Self::create_with_kind(context, input, GOAL)
+    }
+
+    fn create_with_kind(
+        context: &GraphQLContext,
+        input: UserImageInput,
+        kind: &str,
+    ) -> Result<UserImage> {
         let mut conn = get_conn(context)?;
         let new_user_image: NewUserImage = input.into();
 
         let id: i32 = diesel::insert_into(user_images::table)
-            .values(&new_user_image)
+            .values((&new_user_image, user_images::kind.eq(kind)))
             .returning(user_images::id)
             .get_result(&mut conn)
             .context("Failed to create user image")?;
@@ -33,6 +51,7 @@ impl UserImageSvc {
 
         user_images::table
             .filter(user_images::user_id.eq(user_id))
+            .filter(user_images::kind.eq(PROFILE))
             .order(user_images::created_at.desc())
             .select(UserImageMeta::as_select())
             .first::<UserImageMeta>(&mut conn)
@@ -49,6 +68,7 @@ impl UserImageSvc {
 
         user_images::table
             .filter(user_images::user_id.eq(user_id))
+            .filter(user_images::kind.eq(PROFILE))
             .order(user_images::created_at.desc())
             .select(UserImage::as_select())
             .first::<UserImage>(&mut conn)
@@ -81,9 +101,23 @@ impl UserImageSvc {
     pub fn delete_by_user_id(context: &GraphQLContext, user_id: i32) -> Result<usize> {
         let mut conn = get_conn(context)?;
 
-        diesel::delete(user_images::table.filter(user_images::user_id.eq(user_id)))
+        diesel::delete(
+            user_images::table
+                .filter(user_images::user_id.eq(user_id))
+                .filter(user_images::kind.eq(PROFILE)),
+        )
+        .execute(&mut conn)
+        .context("Failed to delete user images")
+    }
+
+    pub fn delete_by_id(context: &GraphQLContext, id: i32) -> Result<()> {
+        let mut conn = get_conn(context)?;
+
+        diesel::delete(user_images::table.find(id))
             .execute(&mut conn)
-            .context("Failed to delete user images")
+            .context("Failed to delete user image")?;
+
+        Ok(())
     }
 
     pub fn update_user_image_reference(
@@ -0,0 +1,725 @@
+//! Web Push channel for the installed PWA.
+//!
+//! Messages are encrypted for each browser subscription with the `aes128gcm` content
+//! encoding of RFC 8291 and sent with a VAPID (RFC 8292) `Authorization` header. The VAPID
+//! key pair is generated on first use and stored in `vapid_keys`, unless
+//! `VAPID_PRIVATE_KEY` (base64url P-256 scalar) is set. `VAPID_SUBJECT` is the contact
+//! given to push services and should be a `mailto:` or `https:` URL. Subscriptions that
+//! the push service reports as gone (404/410) are deleted.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    get_env,
+    models::WebPushSubscription,
+    schema::{vapid_keys, web_push_subscriptions},
+    svc::{ChallengeSvc, PushSvc, notification::NotificationEvent, push::PushMessage},
+};
+use aes_gcm::{Aes128Gcm, KeyInit, Nonce, aead::Aead};
+use anyhow::{Context, Result, anyhow, bail};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use hkdf::Hkdf;
+use p256::{
+    PublicKey, SecretKey,
+    ecdsa::{Signature, SigningKey, signature::Signer},
+    elliptic_curve::sec1::ToEncodedPoint,
+};
+use rand_core::{OsRng, RngCore};
+use serde_json::json;
+use sha2::Sha256;
+
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// How long a push service keeps an undelivered message (seconds).
+const MESSAGE_TTL: &str = "86400";
+/// Record size advertised in the `aes128gcm` header; messages always fit in one record.
+const RECORD_SIZE: u32 = 4096;
+const VAPID_TOKEN_LIFETIME_HOURS: i64 = 12;
+
+/// The server's VAPID key pair, base64url encoded without padding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VapidKeys {
+    pub private_key: String,
+    /// Uncompressed P-256 point; the PWA passes it to `pushManager.subscribe` as the
+    /// `applicationServerKey`.
+    pub public_key: String,
+}
+
+impl VapidKeys {
+    fn generate() -> Self {
+        Self::from_secret(&SecretKey::random(&mut OsRng))
+    }
+
+    fn from_secret(secret: &SecretKey) -> Self {
+        Self {
+            private_key: URL_SAFE_NO_PAD.encode(secret.to_bytes()),
+            public_key: URL_SAFE_NO_PAD
+                .encode(secret.public_key().to_encoded_point(false).as_bytes()),
+        }
+    }
+
+    fn from_private_key(private_key: &str) -> Result<Self> {
+        let secret = SecretKey::from_slice(&decode_base64url(private_key)?)
+            .map_err(|_| anyhow!("VAPID private key is not a P-256 scalar"))?;
+        Ok(Self::from_secret(&secret))
+    }
+
+    fn secret(&self) -> Result<SecretKey> {
+        SecretKey::from_slice(&decode_base64url(&self.private_key)?)
+            .map_err(|_| anyhow!("Stored VAPID private key is invalid"))
+    }
+}
+
+pub struct WebPushSvc {}
+
+impl WebPushSvc {
+    pub fn vapid_public_key(context: &GraphQLContext) -> Result<String> {
+        Ok(Self::vapid_keys(context)?.public_key)
+    }
+
+    /// The configured or stored VAPID keys, generating and storing a pair on first use.
+    pub fn vapid_keys(context: &GraphQLContext) -> Result<VapidKeys> {
+        let configured = get_env("VAPID_PRIVATE_KEY", "");
+        if !configured.is_empty() {
+            return VapidKeys::from_private_key(&configured);
+        }
+
+        let mut conn = get_conn(context)?;
+        let stored: Option<(String, String)> = vapid_keys::table
+            .select((vapid_keys::private_key, vapid_keys::public_key))
+            .first(&mut conn)
+            .optional()
+            .context("Could not load VAPID keys")?;
+        if let Some((private_key, public_key)) = stored {
+            return Ok(VapidKeys {
+                private_key,
+                public_key,
+            });
+        }
+
+        // Another request may generate a pair at the same time; whichever row lands first wins
+        let keys = VapidKeys::generate();
+        diesel::insert_or_ignore_into(vapid_keys::table)
+            .values((
+                vapid_keys::id.eq(1),
+                vapid_keys::private_key.eq(&keys.private_key),
+                vapid_keys::public_key.eq(&keys.public_key),
+            ))
+            .execute(&mut conn)
+            .context("Could not store VAPID keys")?;
+        vapid_keys::table
+            .select((vapid_keys::private_key, vapid_keys::public_key))
+            .first(&mut conn)
+            .map(|(private_key, public_key)| VapidKeys {
+                private_key,
+                public_key,
+            })
+            .context("Could not load VAPID keys")
+    }
+
+    /// Replaces the stored VAPID key pair and returns the new public key. Browser
+    /// subscriptions are bound to the old key, so they are all deleted and the PWA has to
+    /// subscribe again.
+    pub fn rotate_vapid_keys(context: &GraphQLContext) -> Result<String> {
+        if !get_env("VAPID_PRIVATE_KEY", "").is_empty() {
+            bail!("VAPID keys come from VAPID_PRIVATE_KEY and cannot be rotated here");
+        }
+        let keys = VapidKeys::generate();
+
+        get_conn(context)?.immediate_transaction(|conn| {
+            diesel::delete(web_push_subscriptions::table)
+                .execute(conn)
+                .context("Could not delete push subscriptions")?;
+            diesel::replace_into(vapid_keys::table)
+                .values((
+                    vapid_keys::id.eq(1),
+                    vapid_keys::private_key.eq(&keys.private_key),
+                    vapid_keys::public_key.eq(&keys.public_key),
+                ))
+                .execute(conn)
+                .context("Could not store VAPID keys")?;
+            Ok::<_, anyhow::Error>(())
+        })?;
+
+        Ok(keys.public_key)
+    }
+
+    pub fn list(
+        context: &GraphQLContext,
+        admin_id: Option<i32>,
+        user_id: Option<i32>,
+    ) -> Result<Vec<WebPushSubscription>> {
+        let mut query = web_push_subscriptions::table.into_boxed();
+
+        if let Some(admin_id) = admin_id {
+            query = query.filter(web_push_subscriptions::admin_id.eq(admin_id));
+        }
+        if let Some(user_id) = user_id {
+            query = query.filter(web_push_subscriptions::user_id.eq(user_id));
+        }
+
+        query
+            .select(WebPushSubscription::as_select())
+            .order_by(web_push_subscriptions::id.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load push subscriptions")
+    }
+
+    /// Stores a browser subscription. Subscribing the same endpoint again replaces its keys
+    /// and owner, e.g. when a different family member signs in on a shared tablet.
+    pub fn subscribe(
+        context: &GraphQLContext,
+        subscription: &WebPushSubscription,
+    ) -> Result<WebPushSubscription> {
+        Self::validate(subscription)?;
+
+        diesel::insert_into(web_push_subscriptions::table)
+            .values(subscription)
+            .on_conflict(web_push_subscriptions::endpoint)
+            .do_update()
+            .set((
+                web_push_subscriptions::p256dh.eq(&subscription.p256dh),
+                web_push_subscriptions::auth.eq(&subscription.auth),
+                web_push_subscriptions::admin_id.eq(subscription.admin_id),
+                web_push_subscriptions::user_id.eq(subscription.user_id),
+                web_push_subscriptions::user_agent.eq(&subscription.user_agent),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not save push subscription")?;
+
+        web_push_subscriptions::table
+            .filter(web_push_subscriptions::endpoint.eq(&subscription.endpoint))
+            .select(WebPushSubscription::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find push subscription")
+    }
+
+    pub fn unsubscribe(context: &GraphQLContext, endpoint: &str) -> Result<()> {
+        diesel::delete(web_push_subscriptions::table)
+            .filter(web_push_subscriptions::endpoint.eq(endpoint))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete push subscription")?;
+
+        Ok(())
+    }
+
+    pub fn delete(context: &GraphQLContext, subscription_uuid: &str) -> Result<()> {
+        diesel::delete(web_push_subscriptions::table)
+            .filter(web_push_subscriptions::uuid.eq(subscription_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete push subscription")?;
+
+        Ok(())
+    }
+
+    /// Pushes `event` to the devices of whoever should hear about it: admins for
+    /// submissions, the kid for approvals and due-today reminders. Sending happens on the
+    /// blocking pool when running inside the server and inline otherwise.
+    pub fn notify(context: &GraphQLContext, event: &NotificationEvent) -> Result<()> {
+        let subscriptions = Self::recipients(context, event)?;
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+        let Some(message) = PushSvc::render(context, event)? else {
+            return Ok(());
+        };
+        let keys = Self::vapid_keys(context)?;
+
+        let context = context.clone();
+        let send = move || {
+            if let Err(e) = Self::send_all(&context, &keys, &subscriptions, &message) {
+                tracing::warn!("Could not send web push notifications: {:?}", e);
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(send);
+            }
+            Err(_) => send(),
+        }
+
+        Ok(())
+    }
+
+    /// RFC 8291 `aes128gcm` encryption of `plaintext` for a subscription's `p256dh` key and
+    /// `auth` secret, using a fresh sender key and salt.
+    pub fn encrypt(plaintext: &[u8], p256dh: &str, auth: &str) -> Result<Vec<u8>> {
+        let mut salt = [0_u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        Self::encrypt_with(
+            plaintext,
+            &decode_base64url(p256dh)?,
+            &decode_base64url(auth)?,
+            &SecretKey::random(&mut OsRng),
+            salt,
+        )
+    }
+
+    /// `Authorization` header value for a request to `endpoint`: a VAPID JWT (ES256) for the
+    /// endpoint's origin plus the public key.
+    pub fn vapid_authorization(
+        keys: &VapidKeys,
+        endpoint: &str,
+        now: NaiveDateTime,
+    ) -> Result<String> {
+        let audience = reqwest::Url::parse(endpoint)
+            .context("Push endpoint is not a valid URL")?
+            .origin()
+            .ascii_serialization();
+        let claims = json!({
+            "aud": audience,
+            "exp": (now + Duration::hours(VAPID_TOKEN_LIFETIME_HOURS)).and_utc().timestamp(),
+            "sub": get_env("VAPID_SUBJECT", "mailto:admin@localhost"),
+        });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(json!({"typ": "JWT", "alg": "ES256"}).to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let signature: Signature = SigningKey::from(&keys.secret()?).sign(signing_input.as_bytes());
+
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+            keys.public_key
+        ))
+    }
+
+    fn encrypt_with(
+        plaintext: &[u8],
+        ua_public: &[u8],
+        auth_secret: &[u8],
+        as_secret: &SecretKey,
+        salt: [u8; 16],
+    ) -> Result<Vec<u8>> {
+        let ua_key =
+            PublicKey::from_sec1_bytes(ua_public).map_err(|_| anyhow!("Invalid p256dh key"))?;
+        let as_public = as_secret.public_key().to_encoded_point(false);
+        let shared = p256::ecdh::diffie_hellman(as_secret.to_nonzero_scalar(), ua_key.as_affine());
+
+        // IKM = HKDF(auth_secret, ecdh_secret, "WebPush: info" || 0x00 || ua_public || as_public)
+        let mut key_info = b"WebPush: info\0".to_vec();
+        key_info.extend_from_slice(ua_public);
+        key_info.extend_from_slice(as_public.as_bytes());
+        let mut ikm = [0_u8; 32];
+        Hkdf::<Sha256>::new(Some(auth_secret), shared.raw_secret_bytes())
+            .expand(&key_info, &mut ikm)
+            .map_err(|_| anyhow!("HKDF expand failed"))?;
+
+        let prk = Hkdf::<Sha256>::new(Some(&salt), &ikm);
+        let mut cek = [0_u8; 16];
+        let mut nonce = [0_u8; 12];
+        prk.expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+            .and_then(|()| prk.expand(b"Content-Encoding: nonce\0", &mut nonce))
+            .map_err(|_| anyhow!("HKDF expand failed"))?;
+
+        // A single record: the plaintext followed by the last-record delimiter
+        let mut record = plaintext.to_vec();
+        record.push(2);
+        let ciphertext = Aes128Gcm::new_from_slice(&cek)
+            .map_err(|_| anyhow!("Invalid content encryption key"))?
+            .encrypt(&Nonce::from(nonce), record.as_slice())
+            .map_err(|_| anyhow!("Could not encrypt push message"))?;
+
+        // Header: salt || record size || key id length || key id (the sender public key)
+        let mut body = salt.to_vec();
+        body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+        body.push(u8::try_from(as_public.len()).unwrap_or(65));
+        body.extend_from_slice(as_public.as_bytes());
+        body.extend_from_slice(&ciphertext);
+        Ok(body)
+    }
+
+    fn recipients(
+        context: &GraphQLContext,
+        event: &NotificationEvent,
+    ) -> Result<Vec<WebPushSubscription>> {
+        let query = web_push_subscriptions::table
+            .select(WebPushSubscription::as_select())
+            .into_boxed();
+        let query = match *event {
+            NotificationEvent::CompletionSubmitted { .. } | NotificationEvent::Digest { .. } => {
+                query.filter(web_push_subscriptions::admin_id.is_not_null())
+            }
+            NotificationEvent::CompletionApproved { completion_id } => {
+                let completion = PushSvc::completion(context, completion_id)?;
+                query.filter(web_push_subscriptions::user_id.eq(completion.user_id))
+            }
+            NotificationEvent::ChoresDueToday { user_id, .. }
+            | NotificationEvent::SavingsGoalReached { user_id, .. } => {
+                query.filter(web_push_subscriptions::user_id.eq(user_id))
+            }
+            NotificationEvent::ChallengeCompleted { challenge_id } => {
+                let user_ids: Vec<i32> = ChallengeSvc::participants(context, challenge_id)?
+                    .into_iter()
+                    .filter_map(|user| user.id)
+                    .collect();
+                query.filter(web_push_subscriptions::user_id.eq_any(user_ids))
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        query
+            .load(&mut get_conn(context)?)
+            .context("Could not load push subscriptions")
+    }
+
+    fn send_all(
+        context: &GraphQLContext,
+        keys: &VapidKeys,
+        subscriptions: &[WebPushSubscription],
+        message: &PushMessage,
+    ) -> Result<()> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Could not build HTTP client")?;
+        let payload = json!({
+            "title": message.title,
+            "body": message.message,
+            "url": "/",
+        })
+        .to_string();
+
+        for subscription in subscriptions {
+            if let Err(e) = Self::send(context, &client, keys, subscription, payload.as_bytes()) {
+                tracing::warn!(
+                    "Could not push to subscription {}: {:?}",
+                    subscription.uuid,
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn send(
+        context: &GraphQLContext,
+        client: &reqwest::blocking::Client,
+        keys: &VapidKeys,
+        subscription: &WebPushSubscription,
+        payload: &[u8],
+    ) -> Result<()> {
+        let now = Utc::now().naive_utc();
+        let body = Self::encrypt(payload, &subscription.p256dh, &subscription.auth)?;
+        let response = client
+            .post(&subscription.endpoint)
+            .header("TTL", MESSAGE_TTL)
+            .header(reqwest::header::CONTENT_ENCODING, "aes128gcm")
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .header(
+                reqwest::header::AUTHORIZATION,
+                Self::vapid_authorization(keys, &subscription.endpoint, now)?,
+            )
+            .body(body)
+            .send()
+            .context("Could not reach push service")?;
+
+        let status = response.status();
+        if status.is_success() {
+            diesel::update(web_push_subscriptions::table)
+                .filter(web_push_subscriptions::id.eq(subscription.id))
+                .set(web_push_subscriptions::last_success_at.eq(now))
+                .execute(&mut get_conn(context)?)
+                .context("Could not update push subscription")?;
+            Ok(())
+        } else if matches!(status.as_u16(), 404 | 410) {
+            // The browser unsubscribed or the subscription expired
+            tracing::info!("Pruning expired push subscription {}", subscription.uuid);
+            Self::delete(context, &subscription.uuid)
+        } else {
+            bail!("Push service returned {status}")
+        }
+    }
+
+    fn validate(subscription: &WebPushSubscription) -> Result<()> {
+        let url = reqwest::Url::parse(&subscription.endpoint)
+            .context("Push endpoint is not a valid URL")?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Push endpoint must use http or https");
+        }
+        if subscription.admin_id.is_some() == subscription.user_id.is_some() {
+            bail!("A push subscription belongs to exactly one admin or kid");
+        }
+        PublicKey::from_sec1_bytes(&decode_base64url(&subscription.p256dh)?)
+            .map_err(|_| anyhow!("Subscription p256dh is not a P-256 public key"))?;
+        if decode_base64url(&subscription.auth)?.len() != 16 {
+            bail!("Subscription auth secret must be 16 bytes");
+        }
+        Ok(())
+    }
+}
+
+/// Decodes base64url with or without padding, as browsers are not consistent about it.
+fn decode_base64url(value: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .context("Value is not base64url encoded")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::PaymentType,
+        svc::NotificationSvc,
+        test_helpers::{
+            http_sink::HttpSink,
+            test_db::{
+                create_approved_test_completion, create_test_admin, create_test_chore,
+                create_test_context, create_test_date, create_test_user, day_patterns,
+            },
+        },
+    };
+    use p256::ecdsa::{VerifyingKey, signature::Verifier};
+
+    /// A browser side key pair and auth secret.
+    struct Browser {
+        secret: SecretKey,
+        auth: [u8; 16],
+    }
+
+    impl Browser {
+        fn new() -> Self {
+            let mut auth = [0_u8; 16];
+            OsRng.fill_bytes(&mut auth);
+            Self {
+                secret: SecretKey::random(&mut OsRng),
+                auth,
+            }
+        }
+
+        fn p256dh(&self) -> String {
+            URL_SAFE_NO_PAD.encode(self.secret.public_key().to_encoded_point(false).as_bytes())
+        }
+
+        fn subscription(&self, endpoint: &str, user_id: i32) -> WebPushSubscription {
+            WebPushSubscription {
+                id: None,
+                uuid: crate::uuid_or_generate(None),
+                endpoint: endpoint.to_owned(),
+                p256dh: self.p256dh(),
+                auth: URL_SAFE_NO_PAD.encode(self.auth),
+                admin_id: None,
+                user_id: Some(user_id),
+                user_agent: None,
+                created_at: None,
+                last_success_at: None,
+            }
+        }
+
+        /// Receiver side of RFC 8291, for a single-record message.
+        fn decrypt(&self, body: &[u8]) -> Vec<u8> {
+            let (salt, rest) = body.split_at(16);
+            let id_len = usize::from(rest[4]);
+            let (as_public, ciphertext) = rest[5..].split_at(id_len);
+            let as_key = PublicKey::from_sec1_bytes(as_public).unwrap();
+            let shared =
+                p256::ecdh::diffie_hellman(self.secret.to_nonzero_scalar(), as_key.as_affine());
+            let ua_public = self.secret.public_key().to_encoded_point(false);
+
+            let mut key_info = b"WebPush: info\0".to_vec();
+            key_info.extend_from_slice(ua_public.as_bytes());
+            key_info.extend_from_slice(as_public);
+            let mut ikm = [0_u8; 32];
+            Hkdf::<Sha256>::new(Some(&self.auth), shared.raw_secret_bytes())
+                .expand(&key_info, &mut ikm)
+                .unwrap();
+            let prk = Hkdf::<Sha256>::new(Some(salt), &ikm);
+            let mut cek = [0_u8; 16];
+            let mut nonce = [0_u8; 12];
+            prk.expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+                .unwrap();
+            prk.expand(b"Content-Encoding: nonce\0", &mut nonce)
+                .unwrap();
+
+            let mut record = Aes128Gcm::new_from_slice(&cek)
+                .unwrap()
+                .decrypt(&Nonce::from(nonce), ciphertext)
+                .unwrap();
+            assert_eq!(record.pop(), Some(2), "last record delimiter");
+            record
+        }
+    }
+
+    #[test]
+    fn test_encryption_matches_rfc8291_example() {
+        // RFC 8291, Appendix A
+        let plaintext = b"When I grow up, I want to be a watermelon";
+        let as_secret = SecretKey::from_slice(
+            &decode_base64url("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw").unwrap(),
+        )
+        .unwrap();
+        let ua_public = decode_base64url(
+            "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+        )
+        .unwrap();
+        let auth = decode_base64url("BTBZMqHH6r4Tts7J_aSIgg").unwrap();
+        let salt: [u8; 16] = decode_base64url("DGv6ra1nlYgDCS1FRnbzlw")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let body =
+            WebPushSvc::encrypt_with(plaintext, &ua_public, &auth, &as_secret, salt).unwrap();
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(body),
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN"
+        );
+    }
+
+    #[test]
+    fn test_encrypt_round_trips() {
+        let browser = Browser::new();
+        let body = WebPushSvc::encrypt(
+            b"hello",
+            &browser.p256dh(),
+            &URL_SAFE_NO_PAD.encode(browser.auth),
+        )
+        .unwrap();
+        assert_eq!(browser.decrypt(&body), b"hello");
+    }
+
+    #[test]
+    fn test_vapid_keys_persist_and_rotate() {
+        let context = create_test_context();
+        let keys = WebPushSvc::vapid_keys(&context).unwrap();
+        assert_eq!(WebPushSvc::vapid_keys(&context).unwrap(), keys);
+        assert_eq!(decode_base64url(&keys.public_key).unwrap().len(), 65);
+
+        let user = create_test_user(&context, "Kid");
+        WebPushSvc::subscribe(
+            &context,
+            &Browser::new().subscription("https://push.example.com/a", user.id.unwrap()),
+        )
+        .unwrap();
+
+        let rotated = WebPushSvc::rotate_vapid_keys(&context).unwrap();
+        assert_ne!(rotated, keys.public_key);
+        assert_eq!(WebPushSvc::vapid_public_key(&context).unwrap(), rotated);
+        assert!(WebPushSvc::list(&context, None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_vapid_authorization_is_a_valid_es256_jwt() {
+        let keys = VapidKeys::generate();
+        let now = create_test_date(2026, 4, 6).and_hms_opt(12, 0, 0).unwrap();
+        let header =
+            WebPushSvc::vapid_authorization(&keys, "https://push.example.com:8443/send/x", now)
+                .unwrap();
+
+        let (token, public_key) = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(public_key, keys.public_key);
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(&decode_base64url(public_key).unwrap()).unwrap();
+        let signature = Signature::from_slice(&decode_base64url(signature).unwrap()).unwrap();
+        assert!(
+            verifying_key
+                .verify(signing_input.as_bytes(), &signature)
+                .is_ok()
+        );
+
+        let claims: serde_json::Value = serde_json::from_slice(
+            &decode_base64url(signing_input.split_once('.').unwrap().1).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims["aud"], "https://push.example.com:8443");
+        assert_eq!(
+            claims["exp"],
+            (now + Duration::hours(12)).and_utc().timestamp()
+        );
+    }
+
+    #[test]
+    fn test_subscribe_validates_and_replaces_by_endpoint() {
+        let context = create_test_context();
+        let alice = create_test_user(&context, "Alice");
+        let bob = create_test_user(&context, "Bob");
+        let browser = Browser::new();
+
+        let mut invalid = browser.subscription("https://push.example.com/a", alice.id.unwrap());
+        invalid.auth = URL_SAFE_NO_PAD.encode([0_u8; 4]);
+        assert!(WebPushSvc::subscribe(&context, &invalid).is_err());
+
+        let first = WebPushSvc::subscribe(
+            &context,
+            &browser.subscription("https://push.example.com/a", alice.id.unwrap()),
+        )
+        .unwrap();
+        let second = WebPushSvc::subscribe(
+            &context,
+            &browser.subscription("https://push.example.com/a", bob.id.unwrap()),
+        )
+        .unwrap();
+        assert_eq!(first.uuid, second.uuid);
+        assert_eq!(second.user_id, bob.id);
+        assert_eq!(WebPushSvc::list(&context, None, None).unwrap().len(), 1);
+
+        WebPushSvc::unsubscribe(&context, "https://push.example.com/a").unwrap();
+        assert!(WebPushSvc::list(&context, None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_approval_is_pushed_and_gone_subscription_pruned() {
+        let context = create_test_context();
+        let sink = HttpSink::start(vec![201, 410]);
+        let admin = create_test_admin(&context, "Parent", "parent@test.com");
+        let user = create_test_user(&context, "Alice");
+        let browser = Browser::new();
+        WebPushSvc::subscribe(
+            &context,
+            &browser.subscription(&format!("{}/push/alice", sink.url), user.id.unwrap()),
+        )
+        .unwrap();
+        let chore = create_test_chore(
+            &context,
+            "Dishes",
+            PaymentType::Daily,
+            150,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        let completion = create_approved_test_completion(
+            &context,
+            chore.id.unwrap(),
+            user.id.unwrap(),
+            create_test_date(2026, 4, 6),
+            admin.id.unwrap(),
+        );
+
+        let requests = sink.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.request_line, "POST /push/alice HTTP/1.1");
+        assert_eq!(request.header("Content-Encoding"), Some("aes128gcm"));
+        assert_eq!(request.header("TTL"), Some(MESSAGE_TTL));
+        assert!(
+            request
+                .header("Authorization")
+                .unwrap()
+                .starts_with("vapid t=")
+        );
+        let payload: serde_json::Value =
+            serde_json::from_slice(&browser.decrypt(&request.body_bytes)).unwrap();
+        assert_eq!(payload["title"], "\"Dishes\" approved");
+        assert_eq!(payload["body"], "Nice work! You earned $1.50.");
+        let subscription = &WebPushSvc::list(&context, None, user.id).unwrap()[0];
+        assert!(subscription.last_success_at.is_some());
+
+        // The push service now reports the subscription as gone
+        NotificationSvc::notify(
+            &context,
+            &NotificationEvent::CompletionApproved {
+                completion_id: completion.id.unwrap(),
+            },
+        );
+        assert_eq!(sink.requests().len(), 2);
+        assert!(WebPushSvc::list(&context, None, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,900 @@
+//! Outgoing webhooks.
+//!
+//! Events are queued as one `webhook_deliveries` row per subscribed endpoint and sent as
+//! JSON `POST`s. Each request carries `X-Webhook-Event`, `X-Webhook-Delivery`,
+//! `X-Webhook-Timestamp` and `X-Webhook-Signature: sha256=<hex>`, an HMAC-SHA256 of
+//! `"{timestamp}.{body}"` keyed with the endpoint secret. Failed deliveries are retried
+//! with exponential backoff by the scheduler until they succeed or run out of attempts.
+
+use crate::{
+    context::GraphQLContext,
+    db::get_conn,
+    models::{ChoreCompletion, DeliveryStatus, WebhookDelivery, WebhookEndpoint, WebhookEventType},
+    schema::{chore_completions, webhook_deliveries, webhook_endpoints},
+    svc::{
+        ChallengeSvc, ChoreSvc, DigestSvc, SavingsGoalSvc, UserSvc, digest::DigestEntry,
+        notification::NotificationEvent,
+    },
+};
+use anyhow::{Context, Result, bail};
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// Attempts before a delivery is given up on and marked failed.
+pub const MAX_ATTEMPTS: i32 = 8;
+/// Delay before the first retry; doubles with every further attempt.
+const BASE_BACKOFF_SECS: i64 = 30;
+/// How long a delivery being sent is hidden from other senders.
+const SEND_LEASE_SECS: i64 = 300;
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Deliveries sent per run, so a long outage drains over several scheduler ticks.
+const BATCH_SIZE: i64 = 50;
+
+pub struct WebhookSvc {}
+
+impl WebhookSvc {
+    pub fn get_endpoint(context: &GraphQLContext, endpoint_uuid: &str) -> Result<WebhookEndpoint> {
+        webhook_endpoints::table
+            .filter(webhook_endpoints::uuid.eq(endpoint_uuid))
+            .select(WebhookEndpoint::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find webhook endpoint")
+    }
+
+    pub fn get_endpoint_by_id(
+        context: &GraphQLContext,
+        endpoint_id: i32,
+    ) -> Result<WebhookEndpoint> {
+        webhook_endpoints::table
+            .filter(webhook_endpoints::id.eq(endpoint_id))
+            .select(WebhookEndpoint::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find webhook endpoint")
+    }
+
+    pub fn list_endpoints(context: &GraphQLContext) -> Result<Vec<WebhookEndpoint>> {
+        webhook_endpoints::table
+            .select(WebhookEndpoint::as_select())
+            .order_by(webhook_endpoints::id.asc())
+            .load(&mut get_conn(context)?)
+            .context("Could not load webhook endpoints")
+    }
+
+    pub fn create_endpoint(
+        context: &GraphQLContext,
+        endpoint: &WebhookEndpoint,
+    ) -> Result<WebhookEndpoint> {
+        Self::validate(endpoint)?;
+        let mut endpoint = endpoint.clone();
+        if endpoint.secret.is_empty() {
+            endpoint.secret = Self::generate_secret();
+        }
+
+        diesel::insert_into(webhook_endpoints::table)
+            .values(&endpoint)
+            .execute(&mut get_conn(context)?)
+            .context("Could not create webhook endpoint")?;
+
+        Self::get_endpoint(context, &endpoint.uuid)
+    }
+
+    /// Updates URL, description, subscriptions and the active flag. An empty secret keeps
+    /// the current one.
+    pub fn update_endpoint(
+        context: &GraphQLContext,
+        endpoint: &WebhookEndpoint,
+    ) -> Result<WebhookEndpoint> {
+        Self::validate(endpoint)?;
+        let current = Self::get_endpoint(context, &endpoint.uuid)?;
+        let secret = if endpoint.secret.is_empty() {
+            current.secret
+        } else {
+            endpoint.secret.clone()
+        };
+
+        diesel::update(webhook_endpoints::table)
+            .filter(webhook_endpoints::uuid.eq(&endpoint.uuid))
+            .set((
+                webhook_endpoints::url.eq(&endpoint.url),
+                webhook_endpoints::description.eq(&endpoint.description),
+                webhook_endpoints::secret.eq(secret),
+                webhook_endpoints::event_types.eq(&endpoint.event_types),
+                webhook_endpoints::active.eq(endpoint.active),
+                webhook_endpoints::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not update webhook endpoint")?;
+
+        Self::get_endpoint(context, &endpoint.uuid)
+    }
+
+    /// Deletes the endpoint together with its delivery log.
+    pub fn delete_endpoint(context: &GraphQLContext, endpoint_uuid: &str) -> Result<()> {
+        diesel::delete(webhook_endpoints::table)
+            .filter(webhook_endpoints::uuid.eq(endpoint_uuid))
+            .execute(&mut get_conn(context)?)
+            .context("Could not delete webhook endpoint")?;
+
+        Ok(())
+    }
+
+    pub fn get_delivery(context: &GraphQLContext, delivery_uuid: &str) -> Result<WebhookDelivery> {
+        webhook_deliveries::table
+            .filter(webhook_deliveries::uuid.eq(delivery_uuid))
+            .select(WebhookDelivery::as_select())
+            .first(&mut get_conn(context)?)
+            .context("Could not find webhook delivery")
+    }
+
+    /// Delivery log, newest first.
+    pub fn list_deliveries(
+        context: &GraphQLContext,
+        endpoint_uuid: Option<&str>,
+        status: Option<DeliveryStatus>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let limit: i64 = limit.into();
+        let offset: i64 = offset.into();
+        let mut query = webhook_deliveries::table.into_boxed();
+
+        if let Some(endpoint_uuid) = endpoint_uuid {
+            let endpoint = Self::get_endpoint(context, endpoint_uuid)?;
+            query = query.filter(webhook_deliveries::endpoint_id.eq(endpoint.id.unwrap_or(-1)));
+        }
+        if let Some(status) = status {
+            query = query.filter(webhook_deliveries::status.eq(String::from(status)));
+        }
+
+        query
+            .select(WebhookDelivery::as_select())
+            .order_by(webhook_deliveries::id.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(&mut get_conn(context)?)
+            .context("Could not load webhook deliveries")
+    }
+
+    /// Puts a delivery back in the queue to be sent on the next run, with a fresh set of
+    /// attempts.
+    pub fn retry_delivery(
+        context: &GraphQLContext,
+        delivery_uuid: &str,
+    ) -> Result<WebhookDelivery> {
+        diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::uuid.eq(delivery_uuid))
+            .set((
+                webhook_deliveries::status.eq(String::from(DeliveryStatus::Pending)),
+                webhook_deliveries::attempts.eq(0),
+                webhook_deliveries::next_attempt_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not retry webhook delivery")?;
+
+        Self::dispatch(context);
+        Self::get_delivery(context, delivery_uuid)
+    }
+
+    /// Queues a delivery of `event` for every active endpoint subscribed to it and kicks
+    /// off sending in the background. Returns the number of deliveries queued.
+    pub fn enqueue(context: &GraphQLContext, event: &NotificationEvent) -> Result<usize> {
+        let Some(event_type) = Self::event_type(event) else {
+            return Ok(0);
+        };
+        let endpoints: Vec<WebhookEndpoint> = webhook_endpoints::table
+            .filter(webhook_endpoints::active.eq(true))
+            .select(WebhookEndpoint::as_select())
+            .load(&mut get_conn(context)?)
+            .context("Could not load webhook endpoints")?;
+        let endpoints: Vec<WebhookEndpoint> = endpoints
+            .into_iter()
+            .filter(|endpoint| endpoint.subscribed().contains(&event_type))
+            .collect();
+        if endpoints.is_empty() {
+            return Ok(0);
+        }
+
+        let now = Utc::now().naive_utc();
+        let event_id = Uuid::now_v7().to_string();
+        let payload = json!({
+            "id": event_id,
+            "type": event_type.as_str(),
+            "createdAt": now.and_utc().to_rfc3339(),
+            "data": Self::event_data(context, event)?,
+        })
+        .to_string();
+
+        let deliveries: Vec<WebhookDelivery> = endpoints
+            .iter()
+            .filter_map(|endpoint| endpoint.id)
+            .map(|endpoint_id| WebhookDelivery {
+                id: None,
+                uuid: Uuid::now_v7().to_string(),
+                endpoint_id,
+                event_id: event_id.clone(),
+                event_type: event_type.as_str().to_owned(),
+                payload: payload.clone(),
+                status: DeliveryStatus::Pending.into(),
+                attempts: 0,
+                next_attempt_at: Some(now),
+                last_attempt_at: None,
+                response_status: None,
+                last_error: None,
+                created_at: Some(now),
+                delivered_at: None,
+            })
+            .collect();
+        diesel::insert_into(webhook_deliveries::table)
+            .values(&deliveries)
+            .execute(&mut get_conn(context)?)
+            .context("Could not queue webhook deliveries")?;
+
+        Self::dispatch(context);
+        Ok(deliveries.len())
+    }
+
+    /// Sends every pending delivery that is due at `now`. Returns how many were attempted.
+    pub fn deliver_due(context: &GraphQLContext, now: NaiveDateTime) -> Result<usize> {
+        let due = Self::lease_due(context, now)?;
+        if due.is_empty() {
+            return Ok(0);
+        }
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Could not build HTTP client")?;
+
+        for (delivery, endpoint) in &due {
+            let outcome = Self::send(&client, endpoint, delivery, now);
+            Self::record_attempt(context, delivery, &outcome, now)?;
+        }
+
+        Ok(due.len())
+    }
+
+    /// HMAC-SHA256 signature of `"{timestamp}.{body}"`, hex encoded.
+    pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body.as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Delay before the attempt after `attempts` failed ones: 30s, 1m, 2m, 4m, ...
+    pub fn backoff(attempts: i32) -> Duration {
+        let exponent = u32::try_from(attempts.saturating_sub(1).clamp(0, 20)).unwrap_or(0);
+        Duration::seconds(BASE_BACKOFF_SECS * 2_i64.pow(exponent))
+    }
+
+    const fn event_type(event: &NotificationEvent) -> Option<WebhookEventType> {
+        match event {
+            NotificationEvent::CompletionSubmitted { .. } => {
+                Some(WebhookEventType::CompletionCreated)
+            }
+            NotificationEvent::CompletionApproved { .. } => {
+                Some(WebhookEventType::CompletionApproved)
+            }
+            NotificationEvent::CompletionRejected { .. } => {
+                Some(WebhookEventType::CompletionRejected)
+            }
+            NotificationEvent::PayoutMade { .. } => Some(WebhookEventType::PayoutMade),
+            NotificationEvent::BadgeEarned { .. } => Some(WebhookEventType::BadgeEarned),
+            NotificationEvent::Digest { .. } => Some(WebhookEventType::DigestCreated),
+            NotificationEvent::SavingsGoalReached { .. } => {
+                Some(WebhookEventType::SavingsGoalReached)
+            }
+            NotificationEvent::ChallengeCompleted { .. } => {
+                Some(WebhookEventType::ChallengeCompleted)
+            }
+            NotificationEvent::BonusChoreClaimed { .. }
+            | NotificationEvent::WeeklyPayoutSummary
+            | NotificationEvent::ChoresDueToday { .. } => None,
+        }
+    }
+
+    fn event_data(context: &GraphQLContext, event: &NotificationEvent) -> Result<Value> {
+        match event {
+            NotificationEvent::CompletionSubmitted { completion_id }
+            | NotificationEvent::CompletionApproved { completion_id } => {
+                let completion: ChoreCompletion = chore_completions::table
+                    .filter(chore_completions::id.eq(completion_id))
+                    .select(ChoreCompletion::as_select())
+                    .first(&mut get_conn(context)?)
+                    .context("Could not find chore completion")?;
+                let user = UserSvc::get_by_id(context, completion.user_id)?;
+                let chore = ChoreSvc::get_by_id(context, completion.chore_id)?;
+
+                Ok(json!({
+                    "completion": {
+                        "uuid": completion.uuid,
+                        "completedDate": completion.completed_date,
+                        "amountCents": completion.amount_cents,
+                        "approved": completion.approved,
+                        "approvedAt": completion.approved_at,
+                        "paidOut": completion.paid_out,
+                    },
+                    "user": { "id": user.id, "uuid": user.uuid, "name": user.name },
+                    "chore": { "id": chore.id, "uuid": chore.uuid, "name": chore.name },
+                }))
+            }
+            NotificationEvent::CompletionRejected {
+                completion_uuid,
+                user_id,
+                chore_id,
+                completed_date,
+            } => {
+                let user = UserSvc::get_by_id(context, *user_id)?;
+                let chore = ChoreSvc::get_by_id(context, *chore_id)?;
+
+                Ok(json!({
+                    "completion": { "uuid": completion_uuid, "completedDate": completed_date },
+                    "user": { "id": user.id, "uuid": user.uuid, "name": user.name },
+                    "chore": { "id": chore.id, "uuid": chore.uuid, "name": chore.name },
+                }))
+            }
+            NotificationEvent::PayoutMade {
+                user_id,
+                amount_cents,
+                completion_count,
+                jars,
+            } => {
+                let user = UserSvc::get_by_id(context, *user_id)?;
+
+                Ok(json!({
+                    "user": { "id": user.id, "uuid": user.uuid, "name": user.name },
+                    "amountCents": amount_cents,
+                    "completionCount": completion_count,
+                    "jars": {
+                        "spendCents": jars.spend_cents,
+                        "saveCents": jars.save_cents,
+                        "giveCents": jars.give_cents,
+                    },
+                }))
+            }
+            NotificationEvent::BadgeEarned {
+                user_id,
+                badge_type,
+                badge_name,
+            } => {
+                let user = UserSvc::get_by_id(context, *user_id)?;
+
+                Ok(json!({
+                    "user": { "id": user.id, "uuid": user.uuid, "name": user.name },
+                    "badgeType": badge_type,
+                    "badgeName": badge_name,
+                }))
+            }
+            NotificationEvent::Digest { from, to } => {
+                let digest = DigestSvc::build(context, *from, *to)?;
+                let entries = |entries: &[DigestEntry]| -> Vec<Value> {
+                    entries
+                        .iter()
+                        .map(|entry| {
+                            json!({
+                                "choreId": entry.chore_id,
+                                "choreName": entry.chore_name,
+                                "date": entry.date,
+                                "amountCents": entry.amount_cents,
+                            })
+                        })
+                        .collect()
+                };
+                let kids: Vec<Value> = digest
+                    .kids
+                    .iter()
+                    .map(|kid| {
+                        json!({
+                            "user": { "id": kid.user_id, "name": kid.name },
+                            "done": entries(&kid.done),
+                            "pending": entries(&kid.pending),
+                            "missed": entries(&kid.missed),
+                            "owedCents": kid.owed_cents,
+                        })
+                    })
+                    .collect();
+
+                Ok(json!({
+                    "from": digest.from,
+                    "to": digest.to,
+                    "kids": kids,
+                    "totalOwedCents": digest.total_owed_cents,
+                    "text": digest.text,
+                }))
+            }
+            NotificationEvent::SavingsGoalReached { goal_id, user_id } => {
+                let goal = SavingsGoalSvc::get_by_id(context, *goal_id)?;
+                let user = UserSvc::get_by_id(context, *user_id)?;
+
+                Ok(json!({
+                    "user": { "id": user.id, "uuid": user.uuid, "name": user.name },
+                    "goal": {
+                        "uuid": goal.uuid,
+                        "name": goal.name,
+                        "targetCents": goal.target_cents,
+                        "targetDate": goal.target_date,
+                        "reachedAt": goal.reached_at,
+                    },
+                }))
+            }
+            NotificationEvent::ChallengeCompleted { challenge_id } => {
+                let challenge = ChallengeSvc::get_by_id(context, *challenge_id)?;
+                let contributions: Vec<Value> = ChallengeSvc::contributions(context, &challenge)?
+                    .into_iter()
+                    .map(|c| {
+                        json!({
+                            "user": { "id": c.user.id, "uuid": c.user.uuid, "name": c.user.name },
+                            "value": c.value,
+                        })
+                    })
+                    .collect();
+
+                Ok(json!({
+                    "challenge": {
+                        "uuid": challenge.uuid,
+                        "name": challenge.name,
+                        "prize": challenge.prize,
+                        "metric": challenge.metric,
+                        "target": challenge.target,
+                        "startDate": challenge.start_date,
+                        "endDate": challenge.end_date,
+                        "completedAt": challenge.completed_at,
+                    },
+                    "contributions": contributions,
+                }))
+            }
+            NotificationEvent::BonusChoreClaimed { .. }
+            | NotificationEvent::WeeklyPayoutSummary
+            | NotificationEvent::ChoresDueToday { .. } => {
+                bail!("{event:?} is not a webhook event")
+            }
+        }
+    }
+
+    /// Claims up to `BATCH_SIZE` due deliveries by pushing their next attempt past the send
+    /// lease, so an overlapping run doesn't send them twice.
+    fn lease_due(
+        context: &GraphQLContext,
+        now: NaiveDateTime,
+    ) -> Result<Vec<(WebhookDelivery, WebhookEndpoint)>> {
+        get_conn(context)?.immediate_transaction(|conn| {
+            let due: Vec<(WebhookDelivery, WebhookEndpoint)> = webhook_deliveries::table
+                .inner_join(webhook_endpoints::table)
+                .filter(webhook_deliveries::status.eq(String::from(DeliveryStatus::Pending)))
+                .filter(webhook_deliveries::next_attempt_at.le(now))
+                .select((WebhookDelivery::as_select(), WebhookEndpoint::as_select()))
+                .order_by(webhook_deliveries::id.asc())
+                .limit(BATCH_SIZE)
+                .load(conn)
+                .context("Could not load due webhook deliveries")?;
+
+            let ids: Vec<i32> = due.iter().filter_map(|(delivery, _)| delivery.id).collect();
+            diesel::update(webhook_deliveries::table)
+                .filter(webhook_deliveries::id.eq_any(ids))
+                .set(
+                    webhook_deliveries::next_attempt_at
+                        .eq(now + Duration::seconds(SEND_LEASE_SECS)),
+                )
+                .execute(conn)
+                .context("Could not lease webhook deliveries")?;
+
+            Ok(due)
+        })
+    }
+
+    /// POSTs the payload and returns the response status, or an error message when the
+    /// request failed or the endpoint answered with a non-2xx status.
+    fn send(
+        client: &reqwest::blocking::Client,
+        endpoint: &WebhookEndpoint,
+        delivery: &WebhookDelivery,
+        now: NaiveDateTime,
+    ) -> SendOutcome {
+        let timestamp = now.and_utc().timestamp();
+        let signature = Self::sign(&endpoint.secret, timestamp, &delivery.payload);
+        let response = client
+            .post(&endpoint.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Event", &delivery.event_type)
+            .header("X-Webhook-Delivery", &delivery.uuid)
+            .header("X-Webhook-Timestamp", timestamp.to_string())
+            .header("X-Webhook-Signature", format!("sha256={signature}"))
+            .body(delivery.payload.clone())
+            .send();
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                SendOutcome {
+                    status: Some(i32::from(status.as_u16())),
+                    error: (!status.is_success()).then(|| format!("Endpoint returned {status}")),
+                }
+            }
+            Err(e) => SendOutcome {
+                status: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    fn record_attempt(
+        context: &GraphQLContext,
+        delivery: &WebhookDelivery,
+        outcome: &SendOutcome,
+        now: NaiveDateTime,
+    ) -> Result<()> {
+        let attempts = delivery.attempts + 1;
+        let (status, next_attempt_at, delivered_at) = match outcome.error {
+            None => (DeliveryStatus::Delivered, None, Some(now)),
+            Some(_) if attempts >= MAX_ATTEMPTS => (DeliveryStatus::Failed, None, None),
+            Some(_) => (
+                DeliveryStatus::Pending,
+                Some(now + Self::backoff(attempts)),
+                None,
+            ),
+        };
+        if let Some(error) = &outcome.error {
+            tracing::warn!(
+                "Webhook delivery {} attempt {} failed: {}",
+                delivery.uuid,
+                attempts,
+                error
+            );
+        }
+
+        diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq(delivery.id))
+            .set((
+                webhook_deliveries::status.eq(String::from(status)),
+                webhook_deliveries::attempts.eq(attempts),
+                webhook_deliveries::next_attempt_at.eq(next_attempt_at),
+                webhook_deliveries::last_attempt_at.eq(now),
+                webhook_deliveries::response_status.eq(outcome.status),
+                webhook_deliveries::last_error.eq(&outcome.error),
+                webhook_deliveries::delivered_at.eq(delivered_at),
+            ))
+            .execute(&mut get_conn(context)?)
+            .context("Could not record webhook delivery attempt")?;
+
+        Ok(())
+    }
+
+    /// Sends due deliveries on the blocking pool when running inside the server. Elsewhere
+    /// they wait for the scheduler.
+    fn dispatch(context: &GraphQLContext) {
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let context = context.clone();
+            handle.spawn_blocking(move || {
+                if let Err(e) = Self::deliver_due(&context, Utc::now().naive_utc()) {
+                    tracing::warn!("Could not send webhook deliveries: {:?}", e);
+                }
+            });
+        }
+    }
+
+    fn validate(endpoint: &WebhookEndpoint) -> Result<()> {
+        let url = reqwest::Url::parse(&endpoint.url).context("Webhook URL is not a valid URL")?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Webhook URL must use http or https");
+        }
+        Ok(())
+    }
+
+    fn generate_secret() -> String {
+        format!("whsec_{}", Uuid::new_v4().simple())
+    }
+}
+
+struct SendOutcome {
+    status: Option<i32>,
+    error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{ChoreCompletionInput, PaymentType, WebhookEndpointInput},
+        svc::{ChoreCompletionSvc, NotificationSvc},
+        test_helpers::{
+            http_sink::HttpSink,
+            test_db::{
+                create_test_admin, create_test_chore, create_test_context, create_test_date,
+                create_test_user, day_patterns,
+            },
+        },
+    };
+    fn create_endpoint(
+        context: &GraphQLContext,
+        url: &str,
+        event_types: Vec<WebhookEventType>,
+    ) -> WebhookEndpoint {
+        WebhookSvc::create_endpoint(
+            context,
+            &WebhookEndpointInput {
+                uuid: None,
+                url: url.to_owned(),
+                description: None,
+                secret: None,
+                event_types,
+                active: None,
+            }
+            .into(),
+        )
+        .unwrap()
+    }
+
+    /// Creates a kid, a chore and a pending completion; returns the completion id.
+    fn submit_completion(context: &GraphQLContext) -> i32 {
+        let admin = create_test_admin(context, "Parent", "parent@test.com");
+        let user = create_test_user(context, "Alice");
+        let chore = create_test_chore(
+            context,
+            "Dishes",
+            PaymentType::Daily,
+            100,
+            day_patterns::every_day(),
+            admin.id.unwrap(),
+        );
+        ChoreCompletionSvc::create(
+            context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id: chore.id.unwrap(),
+                user_id: user.id.unwrap(),
+                completed_date: create_test_date(2026, 4, 6),
+            },
+        )
+        .unwrap()
+        .id
+        .unwrap()
+    }
+
+    #[test]
+    fn test_endpoint_crud_keeps_secret() {
+        let context = create_test_context();
+        let endpoint = create_endpoint(
+            &context,
+            "https://example.com/hook",
+            vec![WebhookEventType::PayoutMade],
+        );
+        assert!(endpoint.secret.starts_with("whsec_"));
+        assert_eq!(endpoint.subscribed(), vec![WebhookEventType::PayoutMade]);
+
+        let mut update = WebhookEndpoint::from(WebhookEndpointInput {
+            uuid: Some(endpoint.uuid.clone()),
+            url: "https://example.com/other".to_owned(),
+            description: Some("Home Assistant".to_owned()),
+            secret: None,
+            event_types: vec![WebhookEventType::BadgeEarned],
+            active: Some(false),
+        });
+        let updated = WebhookSvc::update_endpoint(&context, &update).unwrap();
+        assert_eq!(updated.secret, endpoint.secret);
+        assert_eq!(updated.subscribed(), vec![WebhookEventType::BadgeEarned]);
+        assert!(!updated.active);
+
+        update.url = "ftp://example.com".to_owned();
+        assert!(WebhookSvc::update_endpoint(&context, &update).is_err());
+
+        WebhookSvc::delete_endpoint(&context, &endpoint.uuid).unwrap();
+        assert!(WebhookSvc::list_endpoints(&context).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_enqueue_only_for_subscribed_active_endpoints() {
+        let context = create_test_context();
+        let subscribed = create_endpoint(
+            &context,
+            "http://127.0.0.1:9/a",
+            vec![WebhookEventType::CompletionCreated],
+        );
+        create_endpoint(
+            &context,
+            "http://127.0.0.1:9/b",
+            vec![WebhookEventType::PayoutMade],
+        );
+
+        // Creating a completion raises the event through NotificationSvc
+        let completion_id = submit_completion(&context);
+        let deliveries = WebhookSvc::list_deliveries(&context, None, None, 100, 0).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].endpoint_id, subscribed.id.unwrap());
+        assert_eq!(deliveries[0].event_type, "completion.created");
+        let payload: Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(payload["data"]["user"]["name"], "Alice");
+        assert_eq!(payload["data"]["chore"]["name"], "Dishes");
+
+        let mut inactive = subscribed;
+        inactive.active = false;
+        WebhookSvc::update_endpoint(&context, &inactive).unwrap();
+        NotificationSvc::notify(
+            &context,
+            &NotificationEvent::CompletionSubmitted { completion_id },
+        );
+        assert_eq!(
+            WebhookSvc::list_deliveries(&context, None, None, 100, 0)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_delivery_is_signed_and_logged() {
+        let context = create_test_context();
+        let sink = HttpSink::start(vec![200]);
+        let endpoint = create_endpoint(
+            &context,
+            &format!("{}/hook", sink.url),
+            vec![WebhookEventType::CompletionCreated],
+        );
+        submit_completion(&context);
+
+        let now = Utc::now().naive_utc();
+        assert_eq!(WebhookSvc::deliver_due(&context, now).unwrap(), 1);
+
+        let requests = sink.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.request_line, "POST /hook HTTP/1.1");
+        assert_eq!(
+            request.header("X-Webhook-Event"),
+            Some("completion.created")
+        );
+        let timestamp: i64 = request
+            .header("X-Webhook-Timestamp")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let expected = format!(
+            "sha256={}",
+            WebhookSvc::sign(&endpoint.secret, timestamp, &request.body)
+        );
+        assert_eq!(
+            request.header("X-Webhook-Signature"),
+            Some(expected.as_str())
+        );
+
+        let delivery = &WebhookSvc::list_deliveries(
+            &context,
+            Some(&endpoint.uuid),
+            Some(DeliveryStatus::Delivered),
+            100,
+            0,
+        )
+        .unwrap()[0];
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(200));
+        assert!(delivery.delivered_at.is_some());
+        assert_eq!(
+            request.header("X-Webhook-Delivery"),
+            Some(delivery.uuid.as_str())
+        );
+
+        // Nothing left to send
+        assert_eq!(WebhookSvc::deliver_due(&context, now).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_failed_delivery_backs_off_then_gives_up() {
+        let context = create_test_context();
+        let sink = HttpSink::start(vec![500; MAX_ATTEMPTS as usize]);
+        create_endpoint(
+            &context,
+            &format!("{}/hook", sink.url),
+            vec![WebhookEventType::CompletionCreated],
+        );
+        submit_completion(&context);
+
+        let mut now = Utc::now().naive_utc();
+        WebhookSvc::deliver_due(&context, now).unwrap();
+        let delivery = &WebhookSvc::list_deliveries(&context, None, None, 100, 0).unwrap()[0];
+        assert_eq!(
+            DeliveryStatus::from(&delivery.status),
+            DeliveryStatus::Pending
+        );
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(500));
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(30)));
+
+        // Not due again until the backoff has passed
+        assert_eq!(
+            WebhookSvc::deliver_due(&context, now + Duration::seconds(29)).unwrap(),
+            0
+        );
+
+        for attempt in 2..=MAX_ATTEMPTS {
+            now += WebhookSvc::backoff(attempt - 1);
+            assert_eq!(WebhookSvc::deliver_due(&context, now).unwrap(), 1);
+        }
+        let delivery = WebhookSvc::get_delivery(&context, &delivery.uuid).unwrap();
+        assert_eq!(
+            DeliveryStatus::from(&delivery.status),
+            DeliveryStatus::Failed
+        );
+        assert_eq!(delivery.attempts, MAX_ATTEMPTS);
+        assert_eq!(delivery.next_attempt_at, None);
+        assert_eq!(sink.requests().len(), MAX_ATTEMPTS as usize);
+
+        let retried = WebhookSvc::retry_delivery(&context, &delivery.uuid).unwrap();
+        assert_eq!(
+            DeliveryStatus::from(&retried.status),
+            DeliveryStatus::Pending
+        );
+        assert_eq!(retried.attempts, 0);
+    }
+
+    #[test]
+    fn test_payout_and_rejection_events() {
+        let context = create_test_context();
+        create_endpoint(
+            &context,
+            "http://127.0.0.1:9/hook",
+            vec![
+                WebhookEventType::PayoutMade,
+                WebhookEventType::CompletionRejected,
+            ],
+        );
+        let completion_id = submit_completion(&context);
+        let completion: ChoreCompletion = chore_completions::table
+            .filter(chore_completions::id.eq(completion_id))
+            .select(ChoreCompletion::as_select())
+            .first(&mut get_conn(&context).unwrap())
+            .unwrap();
+        let admin_id = crate::svc::AdminSvc::list(&context, 1, 0).unwrap()[0]
+            .id
+            .unwrap();
+        ChoreCompletionSvc::approve(&context, &completion.uuid, admin_id).unwrap();
+        ChoreCompletionSvc::mark_as_paid_batch(&context, &[completion.user_id]).unwrap();
+
+        let deliveries = WebhookSvc::list_deliveries(&context, None, None, 100, 0).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event_type, "payout.made");
+        let payload: Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(payload["data"]["amountCents"], 100);
+        assert_eq!(payload["data"]["completionCount"], 1);
+
+        // Nothing left to pay: no second payout event
+        ChoreCompletionSvc::mark_as_paid(&context, None).unwrap();
+        assert_eq!(
+            WebhookSvc::list_deliveries(&context, None, None, 100, 0)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let pending = ChoreCompletionSvc::create(
+            &context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id: completion.chore_id,
+                user_id: completion.user_id,
+                completed_date: create_test_date(2026, 4, 7),
+            },
+        )
+        .unwrap();
+        ChoreCompletionSvc::reject(&context, &pending.uuid).unwrap();
+        let latest = &WebhookSvc::list_deliveries(&context, None, None, 1, 0).unwrap()[0];
+        assert_eq!(latest.event_type, "completion.rejected");
+        let payload: Value = serde_json::from_str(&latest.payload).unwrap();
+        assert_eq!(payload["data"]["completion"]["uuid"], pending.uuid.as_str());
+        assert_eq!(payload["data"]["completion"]["completedDate"], "2026-04-07");
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        assert_eq!(WebhookSvc::backoff(1), Duration::seconds(30));
+        assert_eq!(WebhookSvc::backoff(2), Duration::seconds(60));
+        assert_eq!(WebhookSvc::backoff(4), Duration::seconds(240));
+    }
+}
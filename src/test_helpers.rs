@@ -3,8 +3,12 @@ pub mod test_db {
     use crate::{
         context::GraphQLContext,
         db::{ConnectionOptions, run_migrations},
-        models::{Admin, Chore, ChoreAssignment, ChoreInput, PaymentType, User},
+        models::{
+            Admin, Chore, ChoreAssignment, ChoreCompletion, ChoreCompletionInput, ChoreInput,
+            PaymentType, User,
+        },
         schema::{admins, chore_assignments, chores, users},
+        svc::ChoreCompletionSvc,
     };
     use chrono::Datelike;
     use chrono::{NaiveDate, Utc};
@@ -111,6 +115,7 @@ pub mod test_db {
             created_by_admin_id: admin_id,
             bonus_date: None,
             max_claims: None,
+            points: None,
         };
 
         let chore = Chore::from(chore_input);
@@ -153,6 +158,56 @@ pub mod test_db {
             .unwrap()
     }
 
+    /// Test data factory for chore assignments made on `assigned_on`, for tests that look
+    /// back at dates before today
+    pub fn create_test_chore_assignment_on(
+        context: &GraphQLContext,
+        chore_id: i32,
+        user_id: i32,
+        assigned_on: NaiveDate,
+    ) -> ChoreAssignment {
+        let assignment = ChoreAssignment {
+            id: None,
+            chore_id,
+            user_id,
+            created_at: assigned_on.and_hms_opt(0, 0, 0),
+        };
+
+        diesel::insert_into(chore_assignments::table)
+            .values(&assignment)
+            .execute(&mut context.pool.get().unwrap())
+            .unwrap();
+
+        chore_assignments::table
+            .filter(chore_assignments::chore_id.eq(chore_id))
+            .filter(chore_assignments::user_id.eq(user_id))
+            .select(ChoreAssignment::as_select())
+            .first(&mut context.pool.get().unwrap())
+            .unwrap()
+    }
+
+    /// Test data factory for completions that were submitted and approved
+    pub fn create_approved_test_completion(
+        context: &GraphQLContext,
+        chore_id: i32,
+        user_id: i32,
+        completed_date: NaiveDate,
+        admin_id: i32,
+    ) -> ChoreCompletion {
+        let completion = ChoreCompletionSvc::create(
+            context,
+            &ChoreCompletionInput {
+                uuid: None,
+                chore_id,
+                user_id,
+                completed_date,
+            },
+        )
+        .unwrap();
+
+        ChoreCompletionSvc::approve(context, &completion.uuid, admin_id).unwrap()
+    }
+
     /// Helper function to get Monday of current week for testing
     pub fn get_test_week_start() -> NaiveDate {
         let today = Utc::now().date_naive();
@@ -196,3 +251,92 @@ pub mod test_db {
         }
     }
 }
+
+#[cfg(test)]
+pub mod http_sink {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    /// A request received by `HttpSink`.
+    #[derive(Debug, Clone)]
+    pub struct HttpRequest {
+        /// e.g. `POST /hook HTTP/1.1`
+        pub request_line: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+        pub body_bytes: Vec<u8>,
+    }
+
+    impl HttpRequest {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Minimal HTTP server for tests of outgoing requests. Answers each request with the
+    /// next status from `statuses`, then stops accepting.
+    pub struct HttpSink {
+        /// Base URL without a trailing slash, e.g. `http://127.0.0.1:40123`
+        pub url: String,
+        requests: Arc<Mutex<Vec<HttpRequest>>>,
+    }
+
+    impl HttpSink {
+        pub fn start(statuses: Vec<u16>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&requests);
+            thread::spawn(move || {
+                for status in statuses {
+                    let Ok((stream, _)) = listener.accept() else {
+                        return;
+                    };
+                    let mut reader = BufReader::new(stream);
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).unwrap();
+                    let mut headers = Vec::new();
+                    loop {
+                        let mut line = String::new();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                            break;
+                        }
+                        if let Some((key, value)) = line.split_once(':') {
+                            headers.push((key.trim().to_owned(), value.trim().to_owned()));
+                        }
+                    }
+                    let content_length = headers
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                        .and_then(|(_, value)| value.parse().ok())
+                        .unwrap_or(0);
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+                    recorded.lock().unwrap().push(HttpRequest {
+                        request_line: request_line.trim_end().to_owned(),
+                        headers,
+                        body: String::from_utf8_lossy(&body).into_owned(),
+                        body_bytes: body,
+                    });
+                    let response = format!(
+                        "HTTP/1.1 {status} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    );
+                    reader.get_mut().write_all(response.as_bytes()).unwrap();
+                }
+            });
+
+            Self { url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<HttpRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+}